@@ -0,0 +1,27 @@
+-- 체인별 마지막 처리 블록
+CREATE TABLE IF NOT EXISTS blockchain_state (
+    chain_name VARCHAR(50) PRIMARY KEY,
+    last_processed_block BIGINT NOT NULL
+);
+
+-- 입금 이벤트 (wallet_id, account_id 포함)
+CREATE TABLE IF NOT EXISTS deposit_events (
+    id SERIAL PRIMARY KEY,
+    address VARCHAR(255) NOT NULL,
+    wallet_id VARCHAR(255) NOT NULL,
+    account_id VARCHAR(255),
+    chain_name VARCHAR(50) NOT NULL,
+    tx_hash VARCHAR(255) NOT NULL,
+    block_number BIGINT NOT NULL,
+    amount VARCHAR(255) NOT NULL,
+    amount_decimal NUMERIC(36, 18),
+    confirmed BOOLEAN DEFAULT FALSE,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(chain_name, tx_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_de_address ON deposit_events (address);
+CREATE INDEX IF NOT EXISTS idx_de_block_number ON deposit_events (block_number);
+CREATE INDEX IF NOT EXISTS idx_de_confirmed ON deposit_events (confirmed) WHERE confirmed = FALSE;
+CREATE INDEX IF NOT EXISTS idx_de_wallet_id ON deposit_events (wallet_id);
+CREATE INDEX IF NOT EXISTS idx_de_account_id ON deposit_events (account_id) WHERE account_id IS NOT NULL;
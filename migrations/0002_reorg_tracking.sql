@@ -0,0 +1,12 @@
+-- Reorg로 canonical 체인에서 사라진 입금 표시
+ALTER TABLE deposit_events ADD COLUMN IF NOT EXISTS orphaned BOOLEAN DEFAULT FALSE;
+
+-- 최근 처리 블록 해시 (reorg 감지용)
+CREATE TABLE IF NOT EXISTS block_hashes (
+    chain_name VARCHAR(50) NOT NULL,
+    block_number BIGINT NOT NULL,
+    block_hash VARCHAR(255) NOT NULL,
+    parent_hash VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (chain_name, block_number)
+);
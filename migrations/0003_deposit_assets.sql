@@ -0,0 +1,5 @@
+-- 입금 자산 정보 (네이티브 코인 / 토큰) 및 입금이 포함된 블록 해시
+ALTER TABLE deposit_events ADD COLUMN IF NOT EXISTS asset VARCHAR(50),
+    ADD COLUMN IF NOT EXISTS token_address VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS decimals INT,
+    ADD COLUMN IF NOT EXISTS block_hash VARCHAR(255);
@@ -0,0 +1,14 @@
+-- 실패한 트랜잭션의 입금 시도 (감사용)
+CREATE TABLE IF NOT EXISTS failed_deposit_events (
+    id SERIAL PRIMARY KEY,
+    address VARCHAR(255) NOT NULL,
+    chain_name VARCHAR(50) NOT NULL,
+    tx_hash VARCHAR(255) NOT NULL,
+    block_number BIGINT NOT NULL,
+    amount VARCHAR(255) NOT NULL,
+    asset VARCHAR(50),
+    token_address VARCHAR(255),
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(chain_name, tx_hash, address)
+);
@@ -0,0 +1,12 @@
+-- 관리 대상 주소 인덱스 (PostgreSQL AddressIndex용)
+-- 주소 원본은 blockbit-back-custody (Backend)가 관리하고, xScanner는 SQS로 받은 사본만 보관
+CREATE TABLE IF NOT EXISTS monitored_addresses (
+    chain_name VARCHAR(50) NOT NULL,
+    address VARCHAR(255) NOT NULL,
+    wallet_id VARCHAR(255) NOT NULL,
+    account_id VARCHAR(255),
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (chain_name, address)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ma_wallet_id ON monitored_addresses (wallet_id);
@@ -0,0 +1,15 @@
+-- 입금 알림 outbox (입금 저장/확정과 같은 트랜잭션에 기록, dispatcher가 전송)
+CREATE TABLE IF NOT EXISTS notification_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    event_type VARCHAR(50) NOT NULL,
+    chain_name VARCHAR(50) NOT NULL,
+    tx_hash VARCHAR(255) NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_outbox_pending ON notification_outbox (chain_name, tx_hash, id) WHERE sent_at IS NULL;
@@ -0,0 +1,14 @@
+-- 한 트랜잭션의 여러 입금 (UTXO output / 이벤트 로그 위치), 기존 행은 output_index = 0
+ALTER TABLE deposit_events ADD COLUMN IF NOT EXISTS output_index INT NOT NULL DEFAULT 0;
+ALTER TABLE failed_deposit_events ADD COLUMN IF NOT EXISTS output_index INT NOT NULL DEFAULT 0;
+
+-- UNIQUE 제약을 (chain_name, tx_hash, output_index)로 교체
+ALTER TABLE deposit_events DROP CONSTRAINT IF EXISTS deposit_events_chain_name_tx_hash_key;
+ALTER TABLE deposit_events DROP CONSTRAINT IF EXISTS deposit_events_chain_name_tx_hash_output_index_key;
+ALTER TABLE deposit_events ADD CONSTRAINT deposit_events_chain_name_tx_hash_output_index_key
+    UNIQUE (chain_name, tx_hash, output_index);
+
+ALTER TABLE failed_deposit_events DROP CONSTRAINT IF EXISTS failed_deposit_events_chain_name_tx_hash_address_key;
+ALTER TABLE failed_deposit_events DROP CONSTRAINT IF EXISTS failed_deposit_events_chain_name_tx_hash_output_index_key;
+ALTER TABLE failed_deposit_events ADD CONSTRAINT failed_deposit_events_chain_name_tx_hash_output_index_key
+    UNIQUE (chain_name, tx_hash, output_index);
@@ -0,0 +1,40 @@
+-- 입금 상태 머신: confirmed/orphaned boolean을 status + status_version(낙관적 동시성 제어)으로 교체
+ALTER TABLE deposit_events ADD COLUMN IF NOT EXISTS status VARCHAR(32) NOT NULL DEFAULT 'detected';
+ALTER TABLE deposit_events ADD COLUMN IF NOT EXISTS status_version BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE deposit_events ADD COLUMN IF NOT EXISTS confirmations BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE deposit_events ADD COLUMN IF NOT EXISTS status_updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP;
+
+UPDATE deposit_events SET status = CASE
+    WHEN orphaned THEN 'orphaned'
+    WHEN confirmed THEN 'confirmed'
+    ELSE 'detected'
+END;
+
+ALTER TABLE deposit_events DROP COLUMN IF EXISTS confirmed;
+ALTER TABLE deposit_events DROP COLUMN IF EXISTS orphaned;
+
+ALTER TABLE deposit_events DROP CONSTRAINT IF EXISTS deposit_events_status_check;
+ALTER TABLE deposit_events ADD CONSTRAINT deposit_events_status_check CHECK (status IN (
+    'detected', 'confirming', 'confirmed', 'orphaned', 'failed', 'flagged_for_review'
+));
+
+CREATE INDEX IF NOT EXISTS idx_de_status ON deposit_events (status) WHERE status IN ('detected', 'confirming');
+
+-- 입금 상태 전이 이력 (감사용, from_status가 NULL이면 최초 감지)
+CREATE TABLE IF NOT EXISTS deposit_status_history (
+    id BIGSERIAL PRIMARY KEY,
+    chain_name VARCHAR(50) NOT NULL,
+    tx_hash VARCHAR(255) NOT NULL,
+    output_index INT NOT NULL,
+    from_status VARCHAR(32),
+    to_status VARCHAR(32) NOT NULL,
+    confirmations BIGINT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_dsh_deposit ON deposit_status_history (chain_name, tx_hash, output_index, id);
+
+-- 기존 입금의 현재 상태를 이력의 시작점으로 기록
+INSERT INTO deposit_status_history (chain_name, tx_hash, output_index, from_status, to_status, confirmations, created_at)
+SELECT chain_name, tx_hash, output_index, NULL, status, confirmations, COALESCE(created_at, CURRENT_TIMESTAMP)
+FROM deposit_events;
@@ -0,0 +1,16 @@
+-- 블록 구간 backfill 작업 (live cursor와 별개로 진행 위치를 저장해 재시작 후 이어서 실행)
+CREATE TABLE IF NOT EXISTS backfill_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    chain_name VARCHAR(50) NOT NULL,
+    from_block BIGINT NOT NULL,
+    to_block BIGINT NOT NULL,
+    next_block BIGINT NOT NULL,
+    address VARCHAR(255),
+    status VARCHAR(32) NOT NULL DEFAULT 'pending',
+    error TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT backfill_jobs_status_check CHECK (status IN ('pending', 'running', 'finished', 'failed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_backfill_jobs_unfinished ON backfill_jobs (chain_name, id) WHERE status IN ('pending', 'running');
@@ -0,0 +1,11 @@
+-- 알림 순서 보장을 입금(output) 단위로, 재시도 한도를 넘은 알림은 dead로 격리 (더 이상 전송하지 않음)
+ALTER TABLE notification_outbox ADD COLUMN IF NOT EXISTS output_index INT NOT NULL DEFAULT 0;
+ALTER TABLE notification_outbox ADD COLUMN IF NOT EXISTS dead_at TIMESTAMP;
+
+UPDATE notification_outbox
+SET output_index = COALESCE((payload::jsonb ->> 'output_index')::int, 0)
+WHERE sent_at IS NULL;
+
+DROP INDEX IF EXISTS idx_outbox_pending;
+CREATE INDEX IF NOT EXISTS idx_outbox_pending ON notification_outbox (chain_name, tx_hash, output_index, id)
+    WHERE sent_at IS NULL AND dead_at IS NULL;
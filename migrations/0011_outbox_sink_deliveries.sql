@@ -0,0 +1,12 @@
+-- sink별 알림 전송 상태 (한 sink의 실패가 다른 sink로의 전송을 막거나 중복 전송하지 않도록)
+-- notification_outbox.sent_at / dead_at은 모든 sink로 전송(또는 dead 처리)된 뒤에 기록된다
+CREATE TABLE IF NOT EXISTS notification_deliveries (
+    outbox_id BIGINT NOT NULL REFERENCES notification_outbox (id) ON DELETE CASCADE,
+    sink VARCHAR(100) NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at TIMESTAMP,
+    dead_at TIMESTAMP,
+    PRIMARY KEY (outbox_id, sink)
+);
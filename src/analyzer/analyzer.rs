@@ -1,303 +1,671 @@
 use super::chains::DepositInfo;
-use crate::respository::{Repository, RepositoryWrapper};
+use super::token_registry::TokenRegistry;
+use crate::tasks::ChainHeadTracker;
+use crate::respository::{AddressIndex, Repository, RepositoryWrapper};
 use crate::config::ChainConfig;
-use crate::notification::sqs_client::SqsNotifier;
-use crate::types::BlockData;
+use crate::fetcher::fetcher::BlockFetcher;
+use crate::notification::{DepositEvent, EventPublisher};
+use crate::types::{
+    BlockCommit, BlockData, ChainEvent, CursorUpdate, DepositStatus, DepositTransition, FailedDeposit, NewDeposit,
+    RetrySender, StatusTransition,
+};
 use std::sync::Arc;
 use std::collections::HashMap;
-use log::{error, info};
+use log::{error, info, warn};
 use tokio::sync::mpsc::Receiver;
-use rust_decimal::Decimal;
-
-#[cfg(feature = "rocksdb-backend")]
-use crate::respository::is_monitored_address_in_rocksdb;
-#[cfg(feature = "rocksdb-backend")]
-use rocksdb::DB as RocksDB;
-
-#[cfg(feature = "leveldb-backend")]
-use leveldb::database::Database as LevelDB;
-
-// Unified database type for conditional compilation
-#[cfg(feature = "leveldb-backend")]
-pub type KeyValueDB = LevelDB;
-
-#[cfg(feature = "rocksdb-backend")]
-pub type KeyValueDB = RocksDB;
-
-#[cfg(not(any(feature = "leveldb-backend", feature = "rocksdb-backend")))]
-pub type KeyValueDB = ();
+use tokio::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+// reorg rollback 재시도 간격 (실패할 때마다 두 배, 최대값까지)
+const ROLLBACK_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+const ROLLBACK_RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// 체인별 analyzer worker가 공유하는 핸들과 설정
+pub struct AnalyzerContext {
+    pub repository: Arc<RepositoryWrapper>,
+    pub address_index: Arc<dyn AddressIndex>,
+    pub publisher: Option<Arc<EventPublisher>>,
+    pub chain_configs: HashMap<String, ChainConfig>,
+    pub token_registry: TokenRegistry,
+    pub head_tracker: Arc<ChainHeadTracker>,
+    pub fetchers: HashMap<String, Arc<dyn BlockFetcher>>, // 체인 이름(대문자) -> fetcher (트랜잭션 실행 결과 확인용)
+}
 
-/// Main analyzer loop - receives blocks and processes them
+/// 체인 하나의 analyzer worker - 그 체인의 fetcher와 backfill이 보낸 블록만 받은 순서대로 처리
+///
+/// 체인마다 채널과 worker가 따로 있으므로 한 체인의 느린 DB 쓰기나 블록 폭주가 다른 체인을 막지 않는다.
+/// 블록의 입금과 last_processed_block은 `commit_block`으로 함께 commit되고, 실패하면 `retry`로
+/// fetcher에게 같은 블록을 다시 보내도록 요청한다 (그 사이 도착한 이후 블록은 버림).
+/// reorg rollback은 성공할 때까지 재시도하며, 그동안 이후 블록은 채널에서 기다린다.
+///
+/// `shutdown`이 취소되면 처리 중인 블록만 commit하고 종료한다. 채널에 남은 블록은 진행 위치 이후이므로
+/// 재시작 후 fetcher가 다시 가져오고, 남은 backfill 블록은 작업이 중단된 것으로 기록된다.
 pub async fn run_analyzer(
-    mut receiver: Receiver<BlockData>,
-    repository: Arc<RepositoryWrapper>,
-    kv_db: Option<Arc<KeyValueDB>>,
-    sqs_notifier: Option<Arc<SqsNotifier>>,
-    chain_configs: HashMap<String, ChainConfig>,
+    chain_name: String,
+    mut receiver: Receiver<ChainEvent>,
+    retry: RetrySender,
+    context: Arc<AnalyzerContext>,
+    shutdown: CancellationToken,
 ) {
-    info!("[Analyzer] Starting loop...");
-
-    while let Some(block_data) = receiver.recv().await {
-        info!("[Analyzer] 블록 데이터 수신! 분석 시작...");
-        let repository_clone = repository.clone();
-        let kv_db_clone = kv_db.clone();
-        let sqs_clone = sqs_notifier.clone();
-
-        // 블록 분석 및 주소 매칭
-        let result = analyze_block(block_data, &repository_clone, kv_db_clone.as_deref()).await;
-
-        match result {
-            Ok((chain_name, block_number, deposits)) => {
-                info!(
-                    "[Analyzer] Finished processing {} block {}, found {} deposits",
-                    chain_name, block_number, deposits.len()
-                );
-
-                // Get chain config for required confirmations
-                let required_confirmations = chain_configs.get(&chain_name.to_uppercase())
-                    .or_else(|| chain_configs.get(&chain_name.to_lowercase()))
-                    .map(|c| c.required_confirmations)
-                    .unwrap_or(12); // Default to 12 if not found
-
-                // 입금 처리
-                for deposit in deposits {
-                    if let Err(e) = process_deposit(
-                        &repository_clone,
-                        &chain_name,
-                        deposit,
-                        block_number,
-                        required_confirmations,
-                        sqs_clone.as_deref(),
-                        #[cfg(feature = "rocksdb-backend")]
-                        kv_db_clone.as_deref(),
-                    ).await {
-                        error!("[Analyzer] Failed to process deposit: {}", e);
-                    }
+    info!("[Analyzer:{}] Starting loop...", chain_name);
+
+    // 마지막으로 commit한 블록 (reorg rollback 후에는 common ancestor)
+    let mut committed: Option<u64> = None;
+    // commit에 실패해 fetcher가 다시 보내기를 기다리는 블록
+    let mut retrying: Option<u64> = None;
+
+    loop {
+        let event = tokio::select! {
+            biased;
+            _ = shutdown.cancelled() => {
+                info!("[Analyzer:{}] Shutdown requested, {} queued block(s) left for restart", chain_name, receiver.len());
+                break;
+            }
+            event = receiver.recv() => match event {
+                Some(event) => event,
+                None => {
+                    info!("[Analyzer:{}] Loop finished because the channel was closed.", chain_name);
+                    break;
                 }
-
-                // 마지막 처리 블록 업데이트
-                if let Err(e) = repository_clone.update_last_processed_block(&chain_name, block_number).await {
-                    error!(
-                        "[Analyzer] Failed to update last processed block for {} block {}: {}",
-                        chain_name, block_number, e
+            },
+        };
+        crate::metrics::set_analyzer_backlog(&chain_name, receiver.len());
+
+        // backfill 블록은 입금만 처리 (live cursor와 reorg 추적용 블록 해시는 그대로)
+        let (block_data, live_block, processed, address) = match event {
+            ChainEvent::Block { block_number, block } => {
+                if retrying.is_some_and(|failed| block_number > failed) {
+                    info!("[Analyzer:{}] Dropping block {} until block {} is retried", chain_name, block_number, retrying.unwrap_or_default());
+                    continue;
+                }
+                (block, Some(block_number), None, None)
+            }
+            ChainEvent::Rescan { block, address, processed } => (block, None, Some(processed), address),
+            ChainEvent::Reorg { chain_name, common_ancestor } => {
+                // rollback 전에는 이후 블록을 받지 않는다 (fetcher는 이미 common_ancestor + 1부터 다시 보내는 중)
+                if !rollback_reorg(&context, &chain_name, common_ancestor, &shutdown).await {
+                    info!("[Analyzer:{}] Shutdown requested before the reorg rollback succeeded", chain_name);
+                    break;
+                }
+                committed = Some(common_ancestor);
+                continue;
+            }
+        };
+
+        info!("[Analyzer:{}] 블록 데이터 수신! 분석 시작...", chain_name);
+        let block_ref = block_data.block_ref();
+        let started = Instant::now();
+
+        let result = match process_block(&context, block_data, block_ref.as_ref().map(|r| r.hash.clone()), address.as_deref()).await {
+            Ok(mut commit) => {
+                // 진행 위치는 앞으로만 (reorg rollback 이후 재전달된 블록은 허용)
+                let live = live_block.is_some();
+                if live && committed.is_some_and(|last| commit.block_number <= last) {
+                    warn!(
+                        "[Analyzer] {} block {} arrived after block {} was committed, not moving the cursor back",
+                        commit.chain_name, commit.block_number, committed.unwrap_or_default()
                     );
+                } else if live {
+                    let reorg_depth = chain_config(&context.chain_configs, &commit.chain_name).map(|c| c.reorg_depth).unwrap_or(0);
+                    commit.cursor = Some(CursorUpdate {
+                        block_ref: block_ref.filter(|_| reorg_depth > 0),
+                        prune_below: commit.block_number.saturating_sub(reorg_depth),
+                    });
                 }
+                commit_block(&context, &commit).await.map(|()| commit)
             }
+            Err(e) => Err(e),
+        };
+
+        let commit = match result {
+            Ok(commit) => commit,
             Err(e) => {
-                error!("[Analyzer] ❌ 블록 분석 실패: {}", e);
+                error!("[Analyzer:{}] ❌ 블록 처리 실패: {}", chain_name, e);
+                if let Some(processed) = processed {
+                    let _ = processed.send(Err(e));
+                }
+                if let Some(block_number) = live_block {
+                    request_retry(&chain_name, &retry, &mut retrying, block_number);
+                }
+                continue;
             }
+        };
+
+        crate::metrics::observe_block_analysis(&commit.chain_name, started.elapsed().as_secs_f64());
+
+        if let Some(processed) = processed {
+            info!("[Analyzer] Backfilled {} block {}", commit.chain_name, commit.block_number);
+            let _ = processed.send(Ok(()));
+            continue;
         }
+
+        if commit.cursor.is_some() {
+            committed = Some(commit.block_number);
+            crate::metrics::set_last_processed_block(&commit.chain_name, commit.block_number);
+        }
+        if retrying.is_some_and(|failed| commit.block_number >= failed) {
+            info!("[Analyzer:{}] Block {} committed after retry", chain_name, commit.block_number);
+            retrying = None;
+        }
+    }
+
+    info!("[Analyzer:{}] Stopped (last committed block: {:?})", chain_name, committed);
+}
+
+/// reorg rollback - 성공할 때까지 재시도 (종료 요청으로 중단되면 false)
+///
+/// 실패한 채로 이후 블록을 처리하면 orphaned 입금이 stale block hash로 detected에 남아 확정도 orphaned 알림도 되지 않는다.
+/// 중단되어도 rollback은 트랜잭션이므로 아무것도 바뀌지 않고, 재시작 후 fetcher가 저장된 블록 해시로 reorg를 다시 감지한다.
+async fn rollback_reorg(context: &AnalyzerContext, chain_name: &str, common_ancestor: u64, shutdown: &CancellationToken) -> bool {
+    let mut delay = ROLLBACK_RETRY_BASE_DELAY;
+    loop {
+        match handle_reorg(&context.repository, chain_name, common_ancestor, context.publisher.is_some()).await {
+            Ok(()) => return true,
+            Err(e) => error!(
+                "[Analyzer] ❌ Reorg rollback 실패 ({} -> #{}): {} | {:?} 후 재시도",
+                chain_name, common_ancestor, e, delay
+            ),
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = shutdown.cancelled() => return false,
+        }
+        delay = (delay * 2).min(ROLLBACK_RETRY_MAX_DELAY);
     }
+}
 
-    info!("[Analyzer] Loop finished because the channel was closed.");
+/// fetcher에게 block_number부터 다시 보내도록 요청 (이미 기다리는 블록이 더 앞이면 그 블록부터)
+fn request_retry(chain_name: &str, retry: &RetrySender, retrying: &mut Option<u64>, block_number: u64) {
+    let failed = retrying.map_or(block_number, |pending| pending.min(block_number));
+    *retrying = Some(failed);
+
+    warn!("[Analyzer:{}] Block {} was not committed, asking the fetcher to send it again", chain_name, failed);
+    if retry.send(failed).is_err() {
+        error!("[Analyzer:{}] Fetcher stopped, block {} will be processed after restart", chain_name, failed);
+    }
+}
+
+/// 블록 commit 후 입금 메트릭 기록
+async fn commit_block(context: &AnalyzerContext, commit: &BlockCommit) -> Result<(), String> {
+    let confirmed = context.repository.commit_block(commit).await
+        .map_err(|e| format!("Failed to commit {} block {}: {}", commit.chain_name, commit.block_number, e))?;
+
+    for _ in &commit.deposits {
+        crate::metrics::inc_deposits_detected(&commit.chain_name);
+    }
+    for _ in 0..confirmed {
+        crate::metrics::inc_deposits_confirmed(&commit.chain_name);
+    }
+
+    info!(
+        "[Analyzer] Committed {} block {} ({} new deposit(s), {} confirmed, {} failed tx)",
+        commit.chain_name, commit.block_number, commit.deposits.len(), confirmed, commit.failed_deposits.len()
+    );
+    Ok(())
+}
+
+fn chain_config<'a>(chain_configs: &'a HashMap<String, ChainConfig>, chain_name: &str) -> Option<&'a ChainConfig> {
+    chain_configs.get(&chain_name.to_uppercase())
+        .or_else(|| chain_configs.get(&chain_name.to_lowercase()))
+}
+
+/// 블록 분석 - 저장할 입금 / 상태 전이 / 실패 기록을 모은 BlockCommit 반환 (cursor는 호출한 쪽이 채움)
+/// `only_address`가 있으면 그 주소의 입금만 처리 (주소 지정 backfill)
+async fn process_block(
+    context: &AnalyzerContext,
+    block_data: BlockData,
+    block_hash: Option<String>,
+    only_address: Option<&str>,
+) -> Result<BlockCommit, String> {
+    // 블록 분석 및 주소 매칭
+    let (chain_name, block_number, mut deposits) = analyze_block(block_data, context.address_index.as_ref()).await?;
+    if let Some(address) = only_address {
+        deposits.retain(|deposit| deposit.address == address);
+    }
+    info!(
+        "[Analyzer] Finished processing {} block {}, found {} deposits",
+        chain_name, block_number, deposits.len()
+    );
+
+    // Get chain config for required confirmations
+    let required_confirmations = chain_config(&context.chain_configs, &chain_name)
+        .map(|c| c.required_confirmations)
+        .unwrap_or(12); // Default to 12 if not found
+
+    let mut commit = BlockCommit {
+        chain_name: chain_name.clone(),
+        block_number,
+        deposits: Vec::new(),
+        transitions: Vec::new(),
+        failed_deposits: Vec::new(),
+        cursor: None,
+    };
+
+    for mut deposit in deposits {
+        // 블록과 함께 실행 결과를 가져오지 못했으면 관리 주소로 온 트랜잭션만 따로 확인
+        if deposit.status_unknown && deposit.failure.is_none() {
+            deposit.failure = transaction_failure(context, &chain_name, &deposit.tx_hash).await?;
+        }
+
+        // 실패한 트랜잭션은 입금으로 처리하지 않고 감사 테이블에만 기록 (토큰 레지스트리와 무관하게)
+        if let Some(reason) = deposit.failure.take() {
+            warn!(
+                "[Analyzer] ⚠️ {} tx {} to {} failed ({}), not a deposit",
+                chain_name, deposit.tx_hash, deposit.address, reason
+            );
+            commit.failed_deposits.push(FailedDeposit {
+                address: deposit.address,
+                tx_hash: deposit.tx_hash,
+                output_index: deposit.output_index,
+                block_number: deposit.block_number,
+                amount: deposit.amount,
+                asset: deposit.asset,
+                reason,
+            });
+            continue;
+        }
+
+        // 입금 처리 (토큰 레지스트리에 등록된 자산만)
+        let Some(mut deposit) = context.token_registry.resolve(&chain_name, deposit) else {
+            continue;
+        };
+
+        // confirmation 수는 스캐너 진행 상황이 아닌 체인 head 기준
+        let confirmations = context.head_tracker.confirmations(&chain_name, deposit.block_number, block_number).await;
+        deposit.block_hash = block_hash.clone();
+
+        // 조회 실패는 블록 전체를 다시 처리 (입금을 건너뛴 채 진행 위치를 옮기지 않음)
+        plan_deposit(context, &chain_name, deposit, confirmations, required_confirmations, &mut commit).await?;
+    }
+
+    Ok(commit)
+}
+
+/// 트랜잭션 실행 결과 확인 - 실패했으면 사유 (조회 실패는 블록 전체를 다시 처리)
+async fn transaction_failure(context: &AnalyzerContext, chain_name: &str, tx_hash: &str) -> Result<Option<String>, String> {
+    let Some(fetcher) = context.fetchers.get(chain_name) else {
+        return Ok(None);
+    };
+    fetcher.fetch_transaction_failure(tx_hash).await
+        .map_err(|e| format!("Failed to check {} transaction {}: {}", chain_name, tx_hash, e))
+}
+
+/// Reorg 처리 - common_ancestor 이후 입금을 orphaned로 전이
+///
+/// DepositOrphaned 알림은 rollback과 같은 트랜잭션에서 outbox에 기록된다.
+async fn handle_reorg(
+    repository: &Arc<RepositoryWrapper>,
+    chain_name: &str,
+    common_ancestor: u64,
+    notifications_enabled: bool,
+) -> Result<(), String> {
+    warn!("[Analyzer] ⚠️ {} reorg: rolling back to common ancestor #{}", chain_name, common_ancestor);
+
+    let orphaned = repository
+        .rollback_to_block(chain_name, common_ancestor, notifications_enabled)
+        .await
+        .map_err(|e| format!("Failed to roll back: {}", e))?;
+
+    info!("[Analyzer] {} reorg: {} deposit(s) orphaned", chain_name, orphaned.len());
+
+    for deposit in orphaned {
+        warn!(
+            "[DEPOSIT_ORPHANED] tx {} at address {} (block {}) is no longer canonical",
+            deposit.tx_hash, deposit.address, deposit.block_number
+        );
+    }
+
+    Ok(())
 }
 
 /// Analyze a block and extract deposits
 async fn analyze_block(
     block_data: BlockData,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
+    address_index: &dyn AddressIndex,
 ) -> Result<(String, u64, Vec<DepositInfo>), String> {
-    // Create async closure for is_monitored_address
-    // We need to clone Arc and convert strings to owned for the 'static lifetime requirement
-    let is_monitored = |_repo: &Arc<RepositoryWrapper>, _db: Option<&KeyValueDB>, addr: &str, chain: &str| -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>> {
-        // Clone what we need to move into the async block
-        let repo = repository.clone();
-        let addr = addr.to_string();
-        let chain = chain.to_string();
-
-        // We can't capture kv_db by reference because of lifetime issues
-        // Instead, we'll always use None and rely on the repository fallback
-        // This is acceptable since RocksDB is checked inside is_monitored_address via the repository
-        Box::pin(async move {
-            is_monitored_address(&repo, None, &addr, &chain).await
-        })
-    };
-
     match block_data {
         BlockData::Ethereum(block) => {
-            super::chains::analyze_ethereum_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_ethereum_block(block, address_index).await
         }
         BlockData::Bitcoin(block) => {
-            super::chains::analyze_bitcoin_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_bitcoin_block(block, address_index).await
         }
         BlockData::Tron(block) => {
-            super::chains::analyze_tron_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_tron_block(block, address_index).await
         }
         BlockData::Theta(block) => {
-            super::chains::analyze_theta_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_theta_block(block, address_index).await
         }
         BlockData::Icon(block) => {
-            super::chains::analyze_icon_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_icon_block(block, address_index).await
         }
         BlockData::Aion(block) => {
-            super::chains::analyze_aion_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_aion_block(block, address_index).await
         }
         BlockData::Algorand(block) => {
-            super::chains::analyze_algorand_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_algorand_block(block, address_index).await
         }
         BlockData::Gxchain(block) => {
-            super::chains::analyze_gxchain_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_gxchain_block(block, address_index).await
         }
         BlockData::Quark(block) => {
-            super::chains::analyze_quark_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_quark_block(block, address_index).await
         }
         BlockData::Terra(block) => {
-            super::chains::analyze_terra_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_terra_block(block, address_index).await
         }
         BlockData::Tezos(block) => {
-            super::chains::analyze_tezos_block(block, repository, kv_db, is_monitored).await
+            super::chains::analyze_tezos_block(block, address_index).await
         }
         BlockData::Wayki(block) => {
-            super::chains::analyze_wayki_block(block, repository, kv_db, is_monitored).await
-        }
-    }
-}
-
-/// Check if an address is monitored (RocksDB first, fallback to Repository)
-async fn is_monitored_address(
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    address: &str,
-    chain_name: &str,
-) -> Result<bool, String> {
-    // RocksDB 먼저 조회
-    #[cfg(feature = "rocksdb-backend")]
-    if let Some(kv_db) = kv_db {
-        if let Ok(is_monitored) = is_monitored_address_in_rocksdb(kv_db, address, chain_name) {
-            return Ok(is_monitored);
+            super::chains::analyze_wayki_block(block, address_index).await
         }
     }
-
-    #[cfg(not(feature = "rocksdb-backend"))]
-    let _ = kv_db;
-
-    // Repository fallback
-    repository.is_monitored_address(address, chain_name).await
-        .map_err(|e| format!("Failed to check if address is monitored: {}", e))
 }
 
-/// Process a deposit (save to DB and send SQS notification)
-async fn process_deposit(
-    repository: &Arc<RepositoryWrapper>,
+/// 입금 하나를 새 입금(Stage 1) 또는 confirmed 전이(Stage 2)로 commit에 추가
+///
+/// 알림은 commit_block에서 입금과 같은 트랜잭션으로 outbox에 기록되고, 실제 SQS 전송은 outbox dispatcher가 담당한다
+/// (알림이 설정되지 않았으면 기록하지 않음).
+async fn plan_deposit(
+    context: &AnalyzerContext,
     chain_name: &str,
     deposit: DepositInfo,
-    current_block: u64,
+    confirmations: u64,
     required_confirmations: u64,
-    sqs_notifier: Option<&SqsNotifier>,
-    #[cfg(feature = "rocksdb-backend")]
-    kv_db: Option<&KeyValueDB>,
+    commit: &mut BlockCommit,
 ) -> Result<(), String> {
-    let confirmations = current_block.saturating_sub(deposit.block_number) + 1;
-
-    // Get wallet_id and account_id from RocksDB cache
-    #[cfg(feature = "rocksdb-backend")]
-    let (wallet_id, account_id) = if let Some(db) = kv_db {
-        use crate::respository::get_address_metadata_from_rocksdb;
-        match get_address_metadata_from_rocksdb(db, &deposit.address, chain_name) {
-            Ok(Some(metadata)) => (metadata.wallet_id, metadata.account_id),
-            Ok(None) => return Err(format!("Address metadata not found in RocksDB for {}", deposit.address)),
-            Err(e) => return Err(format!("Failed to get address metadata from RocksDB: {}", e)),
-        }
-    } else {
-        return Err("RocksDB not available".to_string());
-    };
+    let notifications_enabled = context.publisher.is_some();
 
-    #[cfg(not(feature = "rocksdb-backend"))]
-    let (wallet_id, account_id) = repository
-        .get_address_metadata(&deposit.address, chain_name)
+    // wallet_id, account_id 조회 (그 사이 삭제된 주소는 건너뜀)
+    let Some(metadata) = context.address_index
+        .lookup(&deposit.address, chain_name)
         .await
         .map_err(|e| format!("Failed to get address metadata: {}", e))?
-        .ok_or_else(|| format!("Address metadata not found for {}", deposit.address))?;
+    else {
+        warn!("[Analyzer] Address metadata not found for {}, skipping deposit {}", deposit.address, deposit.tx_hash);
+        return Ok(());
+    };
+    let (wallet_id, account_id) = (metadata.wallet_id, metadata.account_id);
 
     info!(
-        "[DEPOSIT] Received {} {} at address {} (wallet: {}, account: {:?}, tx: {}, block: {}, confirmations: {})",
-        deposit.amount, chain_name, deposit.address, wallet_id, account_id, deposit.tx_hash, deposit.block_number, confirmations
+        "[DEPOSIT] Received {} {} at address {} (wallet: {}, account: {:?}, tx: {}:{}, block: {}, confirmations: {})",
+        deposit.amount, chain_name, deposit.address, wallet_id, account_id, deposit.tx_hash, deposit.output_index, deposit.block_number, confirmations
     );
 
-    // Check if deposit already exists in database
-    let already_exists = repository
-        .deposit_exists(&deposit.tx_hash, chain_name)
+    // 이미 저장된 입금이면 상태 전이만 처리 (orphaned 입금은 다시 detected로 저장)
+    let current = context.repository
+        .get_deposit_status(&deposit.tx_hash, deposit.output_index, chain_name)
         .await
-        .map_err(|e| format!("Failed to check deposit existence: {}", e))?;
-
-    if already_exists {
-        // Deposit already processed in Stage 1, only check Stage 2
-        if confirmations >= required_confirmations {
-            // Check if already confirmed to prevent duplicate notifications
-            let is_confirmed = repository
-                .is_deposit_confirmed(&deposit.tx_hash)
-                .await
-                .map_err(|e| format!("Failed to check confirmation status: {}", e))?;
-
-            if !is_confirmed {
-                info!("[DEPOSIT_CONFIRMED] {} confirmations reached (required: {}), sending confirmation", confirmations, required_confirmations);
-
-                // Update deposit confirmed status
-                repository.update_deposit_confirmed(&deposit.tx_hash)
-                    .await
-                    .map_err(|e| format!("Failed to update deposit confirmation: {}", e))?;
-
-                // Send SQS notification
-                if let Some(notifier) = sqs_notifier {
-                    if let Err(e) = notifier.send_deposit_confirmed(
-                        deposit.address.clone(),
-                        wallet_id.clone(),
-                        account_id.clone(),
-                        chain_name.to_uppercase(),
-                        deposit.tx_hash.clone(),
-                        deposit.amount.clone(),
-                        deposit.block_number,
-                        confirmations,
-                    ).await {
-                        error!("[DEPOSIT_CONFIRMED] Failed to send SQS: {}", e);
-                    } else {
-                        info!("[DEPOSIT_CONFIRMED] ✅ SQS notification sent");
-                    }
-                }
-            } else {
-                // Already confirmed, skip
-                return Ok(());
-            }
+        .map_err(|e| format!("Failed to get deposit status: {}", e))?;
+
+    if let Some((status, status_version)) = current.filter(|(status, _)| *status != DepositStatus::Orphaned) {
+        // Stage 2는 아직 확정되지 않은 입금만
+        if !status.is_pending() || confirmations < required_confirmations {
+            return Ok(());
         }
+
+        let event = DepositEvent::DepositConfirmed {
+            address: deposit.address.clone(),
+            wallet_id,
+            account_id,
+            chain: chain_name.to_uppercase(),
+            tx_hash: deposit.tx_hash.clone(),
+            output_index: deposit.output_index,
+            amount: deposit.amount.clone(),
+            asset: deposit.asset.clone(),
+            block_number: deposit.block_number,
+            block_hash: deposit.block_hash.clone(),
+            confirmations,
+        };
+
+        // 상태 전이 (+ outbox), ConfirmationChecker가 먼저 확정했으면 적용되지 않음
+        info!("[DEPOSIT_CONFIRMED] {} confirmations reached (required: {}), queueing confirmation", confirmations, required_confirmations);
+        commit.transitions.push(DepositTransition {
+            tx_hash: deposit.tx_hash,
+            output_index: deposit.output_index,
+            transition: StatusTransition {
+                from: status,
+                to: DepositStatus::Confirmed,
+                expected_version: status_version,
+                confirmations,
+            },
+            notification: notifications_enabled.then_some(event),
+        });
         return Ok(());
     }
 
-    // New deposit - Stage 1: DEPOSIT_DETECTED (1 confirmation)
-    if confirmations == 1 {
-        info!("[DEPOSIT_DETECTED] {} confirmations reached for tx {}", confirmations, deposit.tx_hash);
-
-        // Save to DB with status PENDING
-        repository.save_deposit_event(
-            &deposit.address,
-            &wallet_id,
-            account_id.as_deref(),
-            chain_name,
-            &deposit.tx_hash,
-            deposit.block_number,
-            &deposit.amount,
-            deposit.amount_decimal,
-        )
-            .await
-            .map_err(|e| format!("Failed to save deposit event: {}", e))?;
-
-        // Send SQS notification
-        if let Some(notifier) = sqs_notifier {
-            if let Err(e) = notifier.send_deposit_detected(
-                deposit.address.clone(),
-                wallet_id.clone(),
-                account_id.clone(),
-                chain_name.to_uppercase(),
-                deposit.tx_hash.clone(),
-                deposit.amount.clone(),
-                deposit.block_number,
-            ).await {
-                error!("[DEPOSIT_DETECTED] Failed to send SQS: {}", e);
-            } else {
-                info!("[DEPOSIT_DETECTED] ✅ SQS notification sent");
+    // New deposit - Stage 1: DEPOSIT_DETECTED
+    // catch-up 중에는 처음 발견한 시점에 이미 여러 confirmation이 있을 수 있음 (Stage 2는 ConfirmationChecker가 처리)
+    info!("[DEPOSIT_DETECTED] {} confirmations reached for tx {}", confirmations, deposit.tx_hash);
+
+    let event = DepositEvent::DepositDetected {
+        address: deposit.address.clone(),
+        wallet_id: wallet_id.clone(),
+        account_id: account_id.clone(),
+        chain: chain_name.to_uppercase(),
+        tx_hash: deposit.tx_hash.clone(),
+        output_index: deposit.output_index,
+        amount: deposit.amount.clone(),
+        asset: deposit.asset.clone(),
+        block_number: deposit.block_number,
+        block_hash: deposit.block_hash.clone(),
+        confirmations: 1,
+    };
+
+    // status detected로 저장 (+ status history, outbox)
+    commit.deposits.push(NewDeposit {
+        address: deposit.address,
+        wallet_id,
+        account_id,
+        tx_hash: deposit.tx_hash,
+        output_index: deposit.output_index,
+        block_number: deposit.block_number,
+        amount: deposit.amount,
+        amount_decimal: deposit.amount_decimal,
+        asset: deposit.asset,
+        block_hash: deposit.block_hash,
+        notification: notifications_enabled.then_some(event),
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coin::bitcoin::model::{BitcoinBlock, Output, Transaction};
+    use crate::respository::{AddressMetadata, MemoryRepository, MonitoredAddress};
+    use crate::types::{AppError, AssetInfo, BlockRef};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use tokio::sync::mpsc;
+
+    /// 조회한 주소를 기록하고 처음 `failures`번은 실패하는 주소 인덱스 (관리 대상 주소는 없음)
+    struct FlakyIndex {
+        failures: Mutex<usize>,
+        queried: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl AddressIndex for FlakyIndex {
+        async fn lookup(&self, _address: &str, _chain_name: &str) -> Result<Option<AddressMetadata>, AppError> {
+            Ok(None)
+        }
+
+        async fn lookup_batch(&self, addresses: &[String], _chain_name: &str) -> Result<HashMap<String, AddressMetadata>, AppError> {
+            self.queried.lock().unwrap().extend(addresses.iter().cloned());
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(AppError::Database("connection reset".to_string()));
             }
+            Ok(HashMap::new())
+        }
+
+        async fn insert(&self, entries: &[MonitoredAddress]) -> Result<usize, AppError> {
+            Ok(entries.len())
+        }
+
+        async fn remove(&self, _address: &str, _chain_name: &str) -> Result<bool, AppError> {
+            Ok(false)
+        }
+
+        async fn remove_wallet(&self, _wallet_id: &str) -> Result<usize, AppError> {
+            Ok(0)
+        }
+
+        async fn count(&self, _chain_name: &str) -> Result<usize, AppError> {
+            Ok(0)
+        }
+
+        async fn list(&self, _chain_name: Option<&str>) -> Result<Vec<MonitoredAddress>, AppError> {
+            Ok(Vec::new())
         }
     }
 
-    Ok(())
+    fn context(failures: usize) -> (Arc<AnalyzerContext>, Arc<FlakyIndex>) {
+        let index = Arc::new(FlakyIndex { failures: Mutex::new(failures), queried: Mutex::new(Vec::new()) });
+        let context = AnalyzerContext {
+            repository: Arc::new(RepositoryWrapper::Memory(Arc::new(MemoryRepository::new()))),
+            address_index: index.clone(),
+            publisher: None,
+            chain_configs: HashMap::new(),
+            token_registry: TokenRegistry::new(&[], &HashMap::new()),
+            head_tracker: Arc::new(ChainHeadTracker::new()),
+            fetchers: HashMap::new(),
+        };
+        (Arc::new(context), index)
+    }
+
+    /// `addr-{height}`로 보내는 출력 하나를 가진 BTC 블록
+    fn block(height: u64) -> ChainEvent {
+        let output = Output {
+            r#type: 0,
+            spent: false,
+            value: 1000,
+            spending_outpoints: Vec::new(),
+            n: 0,
+            tx_index: height,
+            script: String::new(),
+            addr: Some(format!("addr-{}", height)),
+        };
+        let tx = Transaction {
+            hash: format!("tx-{}", height),
+            ver: 1,
+            vin_sz: 0,
+            vout_sz: 1,
+            size: 0,
+            weight: 0,
+            fee: None,
+            relayed_by: String::new(),
+            lock_time: 0,
+            tx_index: height,
+            double_spend: false,
+            time: 0,
+            block_index: height,
+            block_height: height,
+            inputs: Vec::new(),
+            out: vec![output],
+        };
+        let block = BitcoinBlock {
+            hash: format!("0x{:x}", height),
+            ver: 1,
+            prev_block: format!("0x{:x}", height - 1),
+            mrkl_root: String::new(),
+            time: 0,
+            bits: 0,
+            next_block: None,
+            fee: 0,
+            nonce: 0,
+            n_tx: 1,
+            size: 0,
+            block_index: height,
+            main_chain: true,
+            height,
+            weight: 0,
+            tx: vec![tx],
+        };
+        ChainEvent::Block { block_number: height, block: BlockData::Bitcoin(block) }
+    }
+
+    fn new_deposit(tx_hash: &str) -> NewDeposit {
+        NewDeposit {
+            address: "addr-11".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            account_id: None,
+            tx_hash: tx_hash.to_string(),
+            output_index: 0,
+            block_number: 11,
+            amount: "1000".to_string(),
+            amount_decimal: None,
+            asset: AssetInfo::native("BTC", 8),
+            block_hash: Some("0xb".to_string()),
+            notification: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_commit_leaves_cursor_and_deposits_unchanged() {
+        let (context, _) = context(0);
+        context.repository.update_last_processed_block("BTC", 10).await.unwrap();
+        let commit = BlockCommit {
+            chain_name: "BTC".to_string(),
+            block_number: 11,
+            deposits: vec![new_deposit("tx-new")],
+            // 허용되지 않는 전이 - commit 전체가 실패해야 한다
+            transitions: vec![DepositTransition {
+                tx_hash: "tx-old".to_string(),
+                output_index: 0,
+                transition: StatusTransition {
+                    from: DepositStatus::Confirmed,
+                    to: DepositStatus::Detected,
+                    expected_version: 1,
+                    confirmations: 0,
+                },
+                notification: None,
+            }],
+            failed_deposits: Vec::new(),
+            cursor: Some(CursorUpdate { block_ref: Some(BlockRef::new("0xb", "0xa")), prune_below: 0 }),
+        };
+
+        assert!(commit_block(&context, &commit).await.is_err());
+        assert_eq!(context.repository.get_last_processed_block("BTC").await.unwrap(), 10);
+        assert_eq!(context.repository.get_deposit_status("tx-new", 0, "BTC").await.unwrap(), None);
+        assert!(context.repository.get_recent_block_hashes("BTC", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn blocks_after_failed_block_are_dropped_until_it_is_retried() {
+        let (context, index) = context(1);
+        context.repository.update_last_processed_block("BTC", 10).await.unwrap();
+        let (sender, receiver) = mpsc::channel(10);
+        let (retry, mut retry_requests) = mpsc::unbounded_channel();
+
+        // 11은 실패, 12는 11이 다시 올 때까지 버려지고, 재전송된 11과 12는 commit된다
+        for height in [11, 12, 11, 12] {
+            sender.send(block(height)).await.unwrap();
+        }
+        drop(sender);
+        run_analyzer("BTC".to_string(), receiver, retry, context.clone(), CancellationToken::new()).await;
+
+        assert_eq!(retry_requests.try_recv().ok(), Some(11));
+        assert!(retry_requests.try_recv().is_err());
+        assert_eq!(*index.queried.lock().unwrap(), ["addr-11", "addr-11", "addr-12"]);
+        assert_eq!(context.repository.get_last_processed_block("BTC").await.unwrap(), 12);
+    }
+
+    #[test]
+    fn retry_requests_the_earliest_failed_block() {
+        let (retry, mut retry_requests) = mpsc::unbounded_channel();
+        let mut retrying = None;
+
+        request_retry("BTC", &retry, &mut retrying, 12);
+        request_retry("BTC", &retry, &mut retrying, 14);
+        request_retry("BTC", &retry, &mut retrying, 11);
+
+        assert_eq!(retrying, Some(11));
+        assert_eq!(retry_requests.try_recv().ok(), Some(12));
+        assert_eq!(retry_requests.try_recv().ok(), Some(12));
+        assert_eq!(retry_requests.try_recv().ok(), Some(11));
+    }
 }
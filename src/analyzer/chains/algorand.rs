@@ -1,39 +1,33 @@
 use super::types::DepositInfo;
-use super::utils::microalgo_to_algo;
-use crate::respository::RepositoryWrapper;
-use std::sync::Arc;
+use crate::types::AssetInfo;
+use super::utils::{microalgo_to_algo, monitored_addresses};
+use crate::respository::AddressIndex;
+use std::collections::HashSet;
 use log::info;
 
-#[cfg(feature = "rocksdb-backend")]
-use rocksdb::DB as RocksDB;
-#[cfg(feature = "rocksdb-backend")]
-pub type KeyValueDB = RocksDB;
-
-#[cfg(not(feature = "rocksdb-backend"))]
-pub type KeyValueDB = ();
-
 /// Algorand 블록 분석
-pub async fn analyze_algorand_block<F>(
+pub async fn analyze_algorand_block(
     block: crate::coin::algorand::model::AlgorandBlock,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    is_monitored: F,
-) -> Result<(String, u64, Vec<DepositInfo>), String>
-where
-    F: Fn(&Arc<RepositoryWrapper>, Option<&KeyValueDB>, &str, &str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>>,
-{
+    address_index: &dyn AddressIndex,
+) -> Result<(String, u64, Vec<DepositInfo>), String> {
     let chain_name = "ALGORAND";
     let block_number = block.round;
 
     info!("[Analyzer] ALGORAND Block #{} received", block_number);
 
+    let candidates: HashSet<String> = block.txns.transactions.iter()
+        .filter_map(|tx| tx.payment.as_ref().map(|payment| payment.to.clone()))
+        .collect();
+    let monitored = monitored_addresses(address_index, candidates, chain_name).await?;
+
     let mut deposits = Vec::new();
 
+    // 블록에 포함된 Algorand 트랜잭션은 모두 적용된 것 (실패한 트랜잭션은 블록에 포함되지 않음)
     for tx in &block.txns.transactions {
         if let Some(payment) = &tx.payment {
             let to_address = &payment.to;
 
-            if is_monitored(repository, kv_db, to_address, chain_name).await? {
+            if monitored.contains(to_address) {
                 let amount_decimal = microalgo_to_algo(payment.amount);
 
                 info!("[Analyzer] ✅ ALGORAND 입금 감지! 블록: {} | 주소: {} | 금액: {} ALGO",
@@ -42,9 +36,11 @@ where
                 deposits.push(DepositInfo::new(
                     to_address.clone(),
                     tx.tx.clone(),
+                    0,
                     block_number,
                     payment.amount.to_string(),
                     Some(amount_decimal),
+                    AssetInfo::native("ALGO", 6),
                 ));
             }
         }
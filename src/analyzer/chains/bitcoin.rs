@@ -1,38 +1,31 @@
 use super::types::DepositInfo;
-use super::utils::satoshi_to_btc;
-use crate::respository::RepositoryWrapper;
-use std::sync::Arc;
+use crate::types::AssetInfo;
+use super::utils::{satoshi_to_btc, monitored_addresses};
+use crate::respository::AddressIndex;
+use std::collections::HashSet;
 use log::info;
 
-#[cfg(feature = "rocksdb-backend")]
-use rocksdb::DB as RocksDB;
-#[cfg(feature = "rocksdb-backend")]
-pub type KeyValueDB = RocksDB;
-
-#[cfg(not(feature = "rocksdb-backend"))]
-pub type KeyValueDB = ();
-
 /// Bitcoin 블록 분석
-pub async fn analyze_bitcoin_block<F>(
+pub async fn analyze_bitcoin_block(
     block: crate::coin::bitcoin::model::BitcoinBlock,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    is_monitored: F,
-) -> Result<(String, u64, Vec<DepositInfo>), String>
-where
-    F: Fn(&Arc<RepositoryWrapper>, Option<&KeyValueDB>, &str, &str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>>,
-{
+    address_index: &dyn AddressIndex,
+) -> Result<(String, u64, Vec<DepositInfo>), String> {
     let chain_name = "BTC";
     let block_number = block.height;
 
     info!("[Analyzer] Bitcoin Block #{} received", block_number);
 
+    let candidates: HashSet<String> = block.tx.iter()
+        .flat_map(|tx| tx.out.iter().filter_map(|output| output.addr.clone()))
+        .collect();
+    let monitored = monitored_addresses(address_index, candidates, chain_name).await?;
+
     let mut deposits = Vec::new();
 
     for tx in &block.tx {
-        for output in &tx.out {
+        for (output_index, output) in tx.out.iter().enumerate() {
             if let Some(address) = &output.addr {
-                if is_monitored(repository, kv_db, address, chain_name).await? {
+                if monitored.contains(address) {
                     let amount_decimal = satoshi_to_btc(output.value);
 
                     info!("[Analyzer] ✅ BTC 입금 감지! 블록: {} | 주소: {} | 금액: {} BTC",
@@ -41,9 +34,11 @@ where
                     deposits.push(DepositInfo::new(
                         address.clone(),
                         tx.hash.clone(),
+                        output_index as u32,
                         block_number,
                         output.value.to_string(),
                         Some(amount_decimal),
+                        AssetInfo::native("BTC", 8),
                     ));
                 }
             }
@@ -0,0 +1,172 @@
+use crate::coin::ethereum::client::ERC20_TRANSFER_TOPIC;
+
+/// ERC-20 transfer(address,uint256) selector
+const TRANSFER_SELECTOR: &str = "a9059cbb";
+/// ERC-20 transferFrom(address,address,uint256) selector
+const TRANSFER_FROM_SELECTOR: &str = "23b872dd";
+
+/// 디코딩된 ERC-20 전송
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc20Transfer {
+    pub token_address: String,
+    pub to: String,
+    pub amount_hex: String,
+}
+
+/// Transfer(address,address,uint256) 로그 디코딩
+/// ERC-721 Transfer는 tokenId가 indexed라 topic이 4개이므로 제외된다
+pub fn decode_transfer_log(token_address: &str, topics: &[String], data: &str) -> Option<Erc20Transfer> {
+    if topics.len() != 3 || !topics[0].eq_ignore_ascii_case(ERC20_TRANSFER_TOPIC) {
+        return None;
+    }
+
+    Some(Erc20Transfer {
+        token_address: token_address.to_lowercase(),
+        to: word_to_address(strip_0x(&topics[2]))?,
+        amount_hex: word_to_amount(strip_0x(data).get(..64)?)?,
+    })
+}
+
+/// transfer / transferFrom calldata 디코딩 (로그를 조회할 수 없는 체인용)
+pub fn decode_transfer_calldata(token_address: &str, input: &str) -> Option<Erc20Transfer> {
+    let input = strip_0x(input);
+    let selector = input.get(..8)?.to_lowercase();
+    let args = &input[8..];
+
+    // 인자 인덱스 (to, amount)
+    let (to_index, amount_index) = match selector.as_str() {
+        TRANSFER_SELECTOR => (0, 1),
+        TRANSFER_FROM_SELECTOR => (1, 2),
+        _ => return None,
+    };
+
+    Some(Erc20Transfer {
+        token_address: token_address.to_lowercase(),
+        to: word_to_address(abi_word(args, to_index)?)?,
+        amount_hex: word_to_amount(abi_word(args, amount_index)?)?,
+    })
+}
+
+fn strip_0x(value: &str) -> &str {
+    value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")).unwrap_or(value)
+}
+
+/// ABI 인코딩된 n번째 32바이트 word (hex 64자)
+fn abi_word(args: &str, index: usize) -> Option<&str> {
+    args.get(index * 64..(index + 1) * 64)
+}
+
+/// 32바이트 word의 하위 20바이트를 주소로 변환
+fn word_to_address(word: &str) -> Option<String> {
+    if word.len() != 64 || !word.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(format!("0x{}", word[24..].to_lowercase()))
+}
+
+/// 32바이트 word를 "0x..." 금액 문자열로 변환 (앞자리 0 제거)
+fn word_to_amount(word: &str) -> Option<String> {
+    if word.len() != 64 || !word.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let trimmed = word.trim_start_matches('0');
+    Some(format!("0x{}", if trimmed.is_empty() { "0" } else { trimmed }.to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const FROM_WORD: &str = "000000000000000000000000111111111111111111111111111111111111aaaa";
+    const TO_WORD: &str = "000000000000000000000000ABCDEFABCDEFABCDEFABCDEFABCDEFABCDEF0001";
+    const AMOUNT_WORD: &str = "00000000000000000000000000000000000000000000000000000000000f4240";
+
+    fn topics(count: usize) -> Vec<String> {
+        let all = [
+            ERC20_TRANSFER_TOPIC.to_string(),
+            format!("0x{}", FROM_WORD),
+            format!("0x{}", TO_WORD),
+            format!("0x{}", AMOUNT_WORD),
+        ];
+        all[..count].to_vec()
+    }
+
+    fn expected() -> Erc20Transfer {
+        Erc20Transfer {
+            token_address: TOKEN.to_lowercase(),
+            to: "0xabcdefabcdefabcdefabcdefabcdefabcdef0001".to_string(),
+            amount_hex: "0xf4240".to_string(),
+        }
+    }
+
+    #[test]
+    fn decodes_transfer_log() {
+        let data = format!("0x{}", AMOUNT_WORD);
+        assert_eq!(decode_transfer_log(TOKEN, &topics(3), &data), Some(expected()));
+    }
+
+    #[test]
+    fn ignores_erc721_and_other_events() {
+        let data = format!("0x{}", AMOUNT_WORD);
+        // ERC-721 Transfer: tokenId가 indexed라 topic 4개
+        assert_eq!(decode_transfer_log(TOKEN, &topics(4), &data), None);
+
+        let mut approval = topics(3);
+        approval[0] = "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925".to_string();
+        assert_eq!(decode_transfer_log(TOKEN, &approval, &data), None);
+    }
+
+    #[test]
+    fn rejects_short_or_malformed_log() {
+        assert_eq!(decode_transfer_log(TOKEN, &topics(3), "0x"), None);
+        assert_eq!(decode_transfer_log(TOKEN, &topics(3), &format!("0x{}", &AMOUNT_WORD[..63])), None);
+        assert_eq!(decode_transfer_log(TOKEN, &topics(3), &format!("0x{}zz", &AMOUNT_WORD[..62])), None);
+
+        let mut short_topic = topics(3);
+        short_topic[2] = "0xabcdef".to_string();
+        assert_eq!(decode_transfer_log(TOKEN, &short_topic, &format!("0x{}", AMOUNT_WORD)), None);
+    }
+
+    #[test]
+    fn zero_amount_is_normalized() {
+        let data = format!("0x{}", "0".repeat(64));
+        let transfer = decode_transfer_log(TOKEN, &topics(3), &data).unwrap();
+        assert_eq!(transfer.amount_hex, "0x0");
+    }
+
+    #[test]
+    fn decodes_transfer_calldata() {
+        let input = format!("0x{}{}{}", TRANSFER_SELECTOR, TO_WORD, AMOUNT_WORD);
+        assert_eq!(decode_transfer_calldata(TOKEN, &input), Some(expected()));
+    }
+
+    #[test]
+    fn decodes_transfer_from_calldata() {
+        let input = format!("0x{}{}{}{}", TRANSFER_FROM_SELECTOR.to_uppercase(), FROM_WORD, TO_WORD, AMOUNT_WORD);
+        assert_eq!(decode_transfer_calldata(TOKEN, &input), Some(expected()));
+    }
+
+    #[test]
+    fn rejects_short_or_malformed_calldata() {
+        assert_eq!(decode_transfer_calldata(TOKEN, ""), None);
+        assert_eq!(decode_transfer_calldata(TOKEN, "0xa905"), None);
+        // native 전송 (input 없음)
+        assert_eq!(decode_transfer_calldata(TOKEN, "0x"), None);
+        // 알 수 없는 selector
+        assert_eq!(decode_transfer_calldata(TOKEN, &format!("0x095ea7b3{}{}", TO_WORD, AMOUNT_WORD)), None);
+        // amount word 잘림
+        assert_eq!(decode_transfer_calldata(TOKEN, &format!("0x{}{}{}", TRANSFER_SELECTOR, TO_WORD, &AMOUNT_WORD[..40])), None);
+        // transferFrom인데 인자가 2개뿐
+        assert_eq!(decode_transfer_calldata(TOKEN, &format!("0x{}{}{}", TRANSFER_FROM_SELECTOR, TO_WORD, AMOUNT_WORD)), None);
+        // hex가 아닌 문자
+        assert_eq!(decode_transfer_calldata(TOKEN, &format!("0x{}{}{}", TRANSFER_SELECTOR, "g".repeat(64), AMOUNT_WORD)), None);
+    }
+
+    #[test]
+    fn non_ascii_input_does_not_panic() {
+        assert_eq!(decode_transfer_calldata(TOKEN, "0xa9059cb€"), None);
+        assert_eq!(decode_transfer_calldata(TOKEN, "0xa9059cbb€"), None);
+        assert_eq!(decode_transfer_log(TOKEN, &topics(3), "0x€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€€"), None);
+    }
+}
@@ -1,68 +1,101 @@
+use super::erc20::{decode_transfer_calldata, decode_transfer_log, Erc20Transfer};
 use super::types::DepositInfo;
-use super::utils::parse_wei_to_decimal;
-use crate::respository::RepositoryWrapper;
-use std::sync::Arc;
+use super::utils::{parse_wei_to_decimal, monitored_addresses};
+use crate::types::AssetInfo;
+use crate::respository::AddressIndex;
+use std::collections::{HashMap, HashSet};
 use log::info;
 
-#[cfg(feature = "rocksdb-backend")]
-use rocksdb::DB as RocksDB;
-#[cfg(feature = "rocksdb-backend")]
-pub type KeyValueDB = RocksDB;
-
-#[cfg(not(feature = "rocksdb-backend"))]
-pub type KeyValueDB = ();
-
 /// EVM 호환 블록체인 분석 (Ethereum, AION, QUARK, THETA)
-pub async fn analyze_evm_block<F>(
+///
+/// native coin 전송은 `tx.to`/`tx.value`로, ERC-20 전송은 Transfer 로그로 감지한다.
+/// 로그를 조회하지 못한 체인(`logs == None`)은 transfer/transferFrom calldata로 대신 감지한다.
+///
+/// output index: native 전송은 0, 토큰 전송은 log index + 1 (calldata로 감지한 전송은 1)
+pub async fn analyze_evm_block(
     chain_name: &str,
     block_number: u64,
     transactions: Vec<EVMTransaction>,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    is_monitored: F,
-) -> Result<(String, u64, Vec<DepositInfo>), String>
-where
-    F: Fn(&Arc<RepositoryWrapper>, Option<&KeyValueDB>, &str, &str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>>,
-{
+    logs: Option<Vec<EVMLog>>,
+    native_asset: AssetInfo,
+    address_index: &dyn AddressIndex,
+) -> Result<(String, u64, Vec<DepositInfo>), String> {
     info!("[Analyzer] {} Block #{} received (transactions: {})", chain_name, block_number, transactions.len());
 
+    // ERC-20 전송 (tx_hash, output index, transfer, 실행 성공 여부)
+    // revert된 트랜잭션은 로그를 남기지 않으므로 로그 기반 전송은 항상 성공
+    let token_transfers: Vec<(String, u32, Erc20Transfer, Option<bool>)> = match &logs {
+        Some(logs) => logs.iter()
+            .filter_map(|log| {
+                decode_transfer_log(&log.address, &log.topics, &log.data)
+                    .map(|transfer| (log.tx_hash.clone(), log.log_index + 1, transfer, Some(true)))
+            })
+            .collect(),
+        None => transactions.iter()
+            .filter_map(|tx| {
+                let token_address = tx.to.as_deref()?;
+                decode_transfer_calldata(token_address, tx.input.as_deref()?)
+                    .map(|transfer| (tx.hash.clone().unwrap_or_default(), 1, transfer, tx.success))
+            })
+            .collect(),
+    };
+
+    // 네이티브 전송 수신 주소와 토큰 전송 수신 주소를 한 번에 조회
+    let candidates: HashSet<String> = transactions.iter()
+        .filter_map(|tx| tx.to.clone())
+        .chain(token_transfers.iter().map(|(_, _, transfer, _)| transfer.to.clone()))
+        .collect();
+    let monitored = monitored_addresses(address_index, candidates, chain_name).await?;
+
     let mut deposits = Vec::new();
     let mut checked_count = 0;
 
-    for (i, tx) in transactions.iter().enumerate() {
+    for tx in transactions.iter() {
         if let Some(to_address) = &tx.to {
             checked_count += 1;
 
-            // Check RocksDB directly
-            #[cfg(feature = "rocksdb-backend")]
-            let is_addr_monitored = if let Some(db) = kv_db {
-                use crate::respository::is_monitored_address_in_rocksdb;
-                is_monitored_address_in_rocksdb(db, to_address, chain_name).unwrap_or(false)
-            } else {
-                false
-            };
-
-            #[cfg(not(feature = "rocksdb-backend"))]
-            let is_addr_monitored = is_monitored(repository, kv_db, to_address, chain_name).await?;
-
-            if is_addr_monitored {
+            if monitored.contains(to_address) {
                 let amount_hex = tx.value.as_deref().unwrap_or("0x0");
                 let amount_decimal = parse_wei_to_decimal(amount_hex).ok();
 
                 info!("[Analyzer] ✅ {} 입금 감지! 블록: {} | 주소: {} | 금액: {:?}",
                     chain_name, block_number, to_address, amount_decimal);
 
-                deposits.push(DepositInfo::new(
+                let deposit = DepositInfo::new(
                     to_address.clone(),
                     tx.hash.as_deref().unwrap_or("").to_string(),
+                    0,
                     block_number,
                     amount_hex.to_string(),
                     amount_decimal,
-                ));
+                    native_asset.clone(),
+                );
+                deposits.push(with_receipt_status(deposit, tx.success));
             }
         }
     }
 
+    for (tx_hash, output_index, transfer, success) in token_transfers {
+        if !monitored.contains(&transfer.to) {
+            continue;
+        }
+
+        // symbol/decimals는 토큰 레지스트리에서 채워진다
+        info!("[Analyzer] ✅ {} 토큰 전송 감지! 블록: {} | 주소: {} | 토큰: {} | 금액: {}",
+            chain_name, block_number, transfer.to, transfer.token_address, transfer.amount_hex);
+
+        let deposit = DepositInfo::new(
+            transfer.to,
+            tx_hash,
+            output_index,
+            block_number,
+            transfer.amount_hex,
+            None,
+            AssetInfo::token("UNKNOWN", &transfer.token_address, None),
+        );
+        deposits.push(with_receipt_status(deposit, success));
+    }
+
     if checked_count > 0 && deposits.is_empty() {
         info!("[Analyzer] {} 블록 #{}: {}개 트랜잭션 확인, 관리 주소 없음", chain_name, block_number, checked_count);
     }
@@ -70,23 +103,37 @@ where
     Ok((chain_name.to_string(), block_number, deposits))
 }
 
+/// receipt status가 실패(0x0)인 트랜잭션 표시 (status를 모르면 그대로)
+fn with_receipt_status(deposit: DepositInfo, success: Option<bool>) -> DepositInfo {
+    match success {
+        Some(false) => deposit.failed("transaction reverted (receipt status 0x0)"),
+        _ => deposit,
+    }
+}
+
 /// EVM 트랜잭션 구조체
 pub struct EVMTransaction {
     pub to: Option<String>,
     pub value: Option<String>,
     pub hash: Option<String>,
+    pub input: Option<String>,
+    pub success: Option<bool>, // receipt status (None이면 확인 불가, 성공으로 간주)
+}
+
+/// EVM 로그 구조체 (ERC-20 Transfer 감지용)
+pub struct EVMLog {
+    pub address: String,
+    pub topics: Vec<String>,
+    pub data: String,
+    pub tx_hash: String,
+    pub log_index: u32,
 }
 
 /// Ethereum 블록 분석
-pub async fn analyze_ethereum_block<F>(
+pub async fn analyze_ethereum_block(
     block: crate::coin::ethereum::model::EthereumBlock,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    is_monitored: F,
-) -> Result<(String, u64, Vec<DepositInfo>), String>
-where
-    F: Fn(&Arc<RepositoryWrapper>, Option<&KeyValueDB>, &str, &str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>>,
-{
+    address_index: &dyn AddressIndex,
+) -> Result<(String, u64, Vec<DepositInfo>), String> {
     let chain_name = "SEPOLIA";
     let result = block.result.ok_or("Missing 'result' in EthereumBlock")?;
 
@@ -97,25 +144,58 @@ where
         number_hex.parse::<u64>()
     }.map_err(|e| format!("Failed to parse ETH block number: {}", e))?;
 
+    // tx_hash -> 실행 성공 여부
+    let receipts_fetched = block.receipts.is_some();
+    let statuses: HashMap<String, bool> = block.receipts.unwrap_or_default().into_iter()
+        .filter_map(|receipt| {
+            let success = receipt.status? != "0x0";
+            Some((receipt.transaction_hash.to_lowercase(), success))
+        })
+        .collect();
+
     let transactions: Vec<EVMTransaction> = result.transactions.iter().map(|tx| EVMTransaction {
         to: tx.to.clone(),
         value: tx.value.clone(),
         hash: tx.hash.clone(),
+        input: tx.input.clone(),
+        success: tx.hash.as_ref().and_then(|hash| statuses.get(&hash.to_lowercase()).copied()),
     }).collect();
 
-    analyze_evm_block(chain_name, block_number, transactions, repository, kv_db, is_monitored).await
+    let logs = block.logs.map(|logs| logs.into_iter()
+        .enumerate()
+        .filter(|(_, log)| log.removed != Some(true))
+        .map(|(position, log)| EVMLog {
+            // logIndex가 없으면 응답 내 위치로 대신한다
+            log_index: log.log_index.as_deref()
+                .and_then(|index| u32::from_str_radix(index.trim_start_matches("0x"), 16).ok())
+                .unwrap_or(position as u32),
+            address: log.address,
+            topics: log.topics,
+            data: log.data,
+            tx_hash: log.transaction_hash.unwrap_or_default(),
+        })
+        .collect());
+
+    let (chain_name, block_number, deposits) =
+        analyze_evm_block(chain_name, block_number, transactions, logs, AssetInfo::native("ETH", 18), address_index).await?;
+
+    // receipt를 블록 단위로 가져오지 못했으면 native 입금만 트랜잭션별로 확인 (로그 기반 토큰 전송은 항상 성공)
+    let deposits = if receipts_fetched {
+        deposits
+    } else {
+        deposits.into_iter()
+            .map(|deposit| if deposit.output_index == 0 { deposit.with_unknown_status() } else { deposit })
+            .collect()
+    };
+
+    Ok((chain_name, block_number, deposits))
 }
 
 /// AION 블록 분석
-pub async fn analyze_aion_block<F>(
+pub async fn analyze_aion_block(
     block: crate::coin::aion::model::AionBlock,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    is_monitored: F,
-) -> Result<(String, u64, Vec<DepositInfo>), String>
-where
-    F: Fn(&Arc<RepositoryWrapper>, Option<&KeyValueDB>, &str, &str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>>,
-{
+    address_index: &dyn AddressIndex,
+) -> Result<(String, u64, Vec<DepositInfo>), String> {
     let chain_name = "AION";
     let result = block.result.ok_or("Missing 'result' in AionBlock")?;
 
@@ -130,21 +210,18 @@ where
         to: tx.to.clone(),
         value: tx.value.clone(),
         hash: tx.hash.clone(),
+        input: tx.input.clone(),
+        success: None,
     }).collect();
 
-    analyze_evm_block(chain_name, block_number, transactions, repository, kv_db, is_monitored).await
+    analyze_evm_block(chain_name, block_number, transactions, None, AssetInfo::native("AION", 18), address_index).await
 }
 
 /// QUARK 블록 분석
-pub async fn analyze_quark_block<F>(
+pub async fn analyze_quark_block(
     block: crate::coin::quark::model::QuarkBlock,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    is_monitored: F,
-) -> Result<(String, u64, Vec<DepositInfo>), String>
-where
-    F: Fn(&Arc<RepositoryWrapper>, Option<&KeyValueDB>, &str, &str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>>,
-{
+    address_index: &dyn AddressIndex,
+) -> Result<(String, u64, Vec<DepositInfo>), String> {
     let chain_name = "QUARK";
     let result = block.result.ok_or("Missing 'result' in QuarkBlock")?;
 
@@ -159,21 +236,18 @@ where
         to: tx.to.clone(),
         value: tx.value.clone(),
         hash: tx.hash.clone(),
+        input: None,
+        success: None,
     }).collect();
 
-    analyze_evm_block(chain_name, block_number, transactions, repository, kv_db, is_monitored).await
+    analyze_evm_block(chain_name, block_number, transactions, None, AssetInfo::native("QKC", 18), address_index).await
 }
 
 /// THETA 블록 분석
-pub async fn analyze_theta_block<F>(
+pub async fn analyze_theta_block(
     block: crate::coin::theta::model::ThetaBlock,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    is_monitored: F,
-) -> Result<(String, u64, Vec<DepositInfo>), String>
-where
-    F: Fn(&Arc<RepositoryWrapper>, Option<&KeyValueDB>, &str, &str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>>,
-{
+    address_index: &dyn AddressIndex,
+) -> Result<(String, u64, Vec<DepositInfo>), String> {
     let chain_name = "THETA";
     let block_number = block.height.parse::<u64>()
         .map_err(|e| format!("Failed to parse THETA block number: {}", e))?;
@@ -189,8 +263,10 @@ where
             to: tx.to.clone(),
             value: Some(value),
             hash: Some(tx.hash.clone()),
+            input: tx.input.clone(),
+            success: None,
         }
     }).collect();
 
-    analyze_evm_block(chain_name, block_number, transactions, repository, kv_db, is_monitored).await
+    analyze_evm_block(chain_name, block_number, transactions, None, AssetInfo::native("THETA", 18), address_index).await
 }
@@ -1,6 +1,7 @@
 pub mod types;
 pub mod utils;
 pub mod evm;
+pub mod erc20;
 pub mod bitcoin;
 pub mod tron;
 pub mod icon;
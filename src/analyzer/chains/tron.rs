@@ -1,58 +1,108 @@
+use super::erc20::decode_transfer_calldata;
 use super::types::DepositInfo;
-use super::utils::sun_to_trx;
-use crate::respository::RepositoryWrapper;
-use std::sync::Arc;
+use crate::coin::tron::address::{decode_asset_name, to_base58_address};
+use crate::coin::tron::model::{TronContract, TronTransaction};
+use crate::types::AssetInfo;
+use super::utils::{sun_to_trx, monitored_addresses};
+use crate::respository::AddressIndex;
+use std::collections::HashSet;
 use log::info;
 
-#[cfg(feature = "rocksdb-backend")]
-use rocksdb::DB as RocksDB;
-#[cfg(feature = "rocksdb-backend")]
-pub type KeyValueDB = RocksDB;
-
-#[cfg(not(feature = "rocksdb-backend"))]
-pub type KeyValueDB = ();
-
 /// TRON 블록 분석
-pub async fn analyze_tron_block<F>(
+///
+/// - TransferContract: TRX 전송
+/// - TransferAssetContract: TRC-10 전송 (asset_name = 토큰 ID)
+/// - TriggerSmartContract: TRC-20 transfer(address,uint256) 호출
+pub async fn analyze_tron_block(
     block: crate::coin::tron::model::TronBlock,
-    repository: &Arc<RepositoryWrapper>,
-    kv_db: Option<&KeyValueDB>,
-    is_monitored: F,
-) -> Result<(String, u64, Vec<DepositInfo>), String>
-where
-    F: Fn(&Arc<RepositoryWrapper>, Option<&KeyValueDB>, &str, &str) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<bool, String>> + Send>>,
-{
+    address_index: &dyn AddressIndex,
+) -> Result<(String, u64, Vec<DepositInfo>), String> {
     let chain_name = "TRON";
     let block_number = block.block_header.raw_data.number;
 
     info!("[Analyzer] TRON Block #{} received", block_number);
 
+    // (트랜잭션, 컨트랙트 위치, 수신 주소, 금액, 자산)
+    let transfers: Vec<(&TronTransaction, u32, String, String, AssetInfo)> = block.transactions.iter()
+        .flat_map(|tx| {
+            tx.raw_data.contract.iter()
+                .enumerate()
+                .filter_map(|(index, contract)| decode_transfer(contract).map(|transfer| (index as u32, transfer)))
+                .map(move |(index, (to_address, amount, asset))| (tx, index, to_address, amount, asset))
+        })
+        .collect();
+
+    let candidates: HashSet<String> = transfers.iter().map(|(_, _, to_address, _, _)| to_address.clone())
+        .collect();
+    let monitored = monitored_addresses(address_index, candidates, chain_name).await?;
+
     let mut deposits = Vec::new();
 
-    for tx in &block.transactions {
-        // TRX 전송 트랜잭션 처리
-        if let Some(contract) = tx.raw_data.contract.first() {
-            if contract.contract_type == "TransferContract" {
-                if let Some(to_address) = &contract.parameter.value.to_address {
-                    if is_monitored(repository, kv_db, to_address, chain_name).await? {
-                        let amount = contract.parameter.value.amount.unwrap_or(0);
-                        let amount_decimal = sun_to_trx(amount as u64);
-
-                        info!("[Analyzer] ✅ TRON 입금 감지! 블록: {} | 주소: {} | 금액: {} TRX",
-                            block_number, to_address, amount_decimal);
-
-                        deposits.push(DepositInfo::new(
-                            to_address.clone(),
-                            tx.tx_id.clone(),
-                            block_number,
-                            amount.to_string(),
-                            Some(amount_decimal),
-                        ));
-                    }
-                }
-            }
+    for (tx, output_index, to_address, amount, asset) in transfers {
+        if !monitored.contains(&to_address) {
+            continue;
         }
+
+        // TRX 이외 자산의 금액은 토큰 레지스트리에서 decimals 기준으로 변환된다
+        let amount_decimal = if asset.token_address.is_none() {
+            amount.parse::<u64>().ok().map(sun_to_trx)
+        } else {
+            None
+        };
+
+        info!("[Analyzer] ✅ TRON 입금 감지! 블록: {} | 주소: {} | 금액: {} {} ({:?})",
+            block_number, to_address, amount, asset.symbol, asset.token_address);
+
+        let deposit = DepositInfo::new(
+            to_address,
+            tx.tx_id.clone(),
+            output_index,
+            block_number,
+            amount,
+            amount_decimal,
+            asset,
+        );
+
+        // 실패한 트랜잭션 (REVERT, OUT_OF_ENERGY 등)은 잔액 변동이 없으므로 입금이 아님
+        deposits.push(match failure_reason(tx) {
+            Some(reason) => deposit.failed(reason),
+            None => deposit,
+        });
     }
 
     Ok((chain_name.to_string(), block_number, deposits))
 }
+
+/// 트랜잭션 실행 결과가 SUCCESS가 아니면 사유 반환 (ret이 없으면 성공으로 간주)
+fn failure_reason(tx: &TronTransaction) -> Option<String> {
+    tx.ret.first()
+        .filter(|ret| ret.contract_ret != "SUCCESS")
+        .map(|ret| format!("contractRet {}", ret.contract_ret))
+}
+
+/// 컨트랙트에서 (수신 주소, 원시 금액, 자산) 추출
+fn decode_transfer(contract: &TronContract) -> Option<(String, String, AssetInfo)> {
+    let value = &contract.parameter.value;
+
+    match contract.contract_type.as_str() {
+        "TransferContract" => Some((
+            to_base58_address(value.to_address.as_deref()?)?,
+            value.amount.unwrap_or(0).to_string(),
+            AssetInfo::native("TRX", 6),
+        )),
+        "TransferAssetContract" => Some((
+            to_base58_address(value.to_address.as_deref()?)?,
+            value.amount.unwrap_or(0).to_string(),
+            AssetInfo::token("UNKNOWN", &decode_asset_name(value.asset_name.as_deref()?), None),
+        )),
+        "TriggerSmartContract" => {
+            let transfer = decode_transfer_calldata(value.contract_address.as_deref()?, value.data.as_deref()?)?;
+            Some((
+                to_base58_address(&transfer.to)?,
+                transfer.amount_hex,
+                AssetInfo::token("UNKNOWN", &to_base58_address(&transfer.token_address)?, None),
+            ))
+        }
+        _ => None,
+    }
+}
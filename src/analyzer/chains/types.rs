@@ -1,3 +1,4 @@
+use crate::types::AssetInfo;
 use rust_decimal::Decimal;
 
 /// 입금 정보 구조체
@@ -5,25 +6,49 @@ use rust_decimal::Decimal;
 pub struct DepositInfo {
     pub address: String,
     pub tx_hash: String,
+    pub output_index: u32, // 트랜잭션 내 입금 위치 (UTXO output / 이벤트 로그 / 컨트랙트 순서)
     pub block_number: u64,
     pub amount: String,
     pub amount_decimal: Option<Decimal>,
+    pub asset: AssetInfo,
+    pub failure: Option<String>, // 실패한 트랜잭션이면 사유 (입금 대신 감사 테이블에 기록)
+    pub block_hash: Option<String>, // 입금이 포함된 블록 해시 (Analyzer가 채움)
+    pub status_unknown: bool, // 실행 결과를 블록과 함께 가져오지 못함 (Analyzer가 fetcher로 확인)
 }
 
 impl DepositInfo {
     pub fn new(
         address: String,
         tx_hash: String,
+        output_index: u32,
         block_number: u64,
         amount: String,
         amount_decimal: Option<Decimal>,
+        asset: AssetInfo,
     ) -> Self {
         Self {
             address,
             tx_hash,
+            output_index,
             block_number,
             amount,
             amount_decimal,
+            asset,
+            failure: None,
+            block_hash: None,
+            status_unknown: false,
         }
     }
+
+    /// 실패한 트랜잭션으로 표시
+    pub fn failed(mut self, reason: impl Into<String>) -> Self {
+        self.failure = Some(reason.into());
+        self
+    }
+
+    /// 실행 결과를 아직 확인하지 않은 트랜잭션으로 표시
+    pub fn with_unknown_status(mut self) -> Self {
+        self.status_unknown = true;
+        self
+    }
 }
@@ -1,4 +1,6 @@
+use crate::respository::AddressIndex;
 use rust_decimal::Decimal;
+use std::collections::HashSet;
 
 /// Wei(Hex)를 Decimal로 변환 (Ethereum, AION, QUARK, THETA 등)
 pub fn parse_wei_to_decimal(wei_hex: &str) -> Result<Decimal, String> {
@@ -36,3 +38,52 @@ pub fn loop_to_icx(loop_value: u64) -> Decimal {
 pub fn microalgo_to_algo(microalgo: u64) -> Decimal {
     Decimal::from(microalgo) / Decimal::from(1_000_000u64)
 }
+
+/// 토큰 최소 단위(hex)를 decimals 기준 Decimal로 변환 (ERC-20 등)
+pub fn parse_token_amount(raw_hex: &str, decimals: u32) -> Result<Decimal, String> {
+    let raw = parse_hex_u128(raw_hex)?;
+    let raw = i128::try_from(raw).map_err(|e| format!("Token amount too large: {}", e))?;
+
+    Decimal::try_from_i128_with_scale(raw, decimals)
+        .map_err(|e| format!("Failed to scale token amount: {}", e))
+}
+
+/// 체인 원시 금액(hex "0x..." 또는 10진수 문자열)을 decimals 기준 Decimal로 변환
+pub fn parse_raw_amount(raw: &str, decimals: u32) -> Result<Decimal, String> {
+    if raw.starts_with("0x") || raw.starts_with("0X") {
+        return parse_token_amount(raw, decimals);
+    }
+
+    let raw = raw.parse::<i128>()
+        .map_err(|e| format!("Failed to parse amount: {}", e))?;
+
+    Decimal::try_from_i128_with_scale(raw, decimals)
+        .map_err(|e| format!("Failed to scale amount: {}", e))
+}
+
+/// Hex 문자열(최대 uint256, 앞자리 0 허용)을 u128로 변환
+pub fn parse_hex_u128(value_hex: &str) -> Result<u128, String> {
+    let digits = value_hex.strip_prefix("0x")
+        .or_else(|| value_hex.strip_prefix("0X"))
+        .unwrap_or(value_hex)
+        .trim_start_matches('0');
+
+    if digits.is_empty() {
+        return Ok(0);
+    }
+
+    u128::from_str_radix(digits, 16)
+        .map_err(|e| format!("Failed to parse hex amount: {}", e))
+}
+
+/// 블록의 후보 주소 중 관리 대상 주소 (HashSet으로 중복 제거 후 한 번에 조회)
+pub async fn monitored_addresses(address_index: &dyn AddressIndex, candidates: HashSet<String>, chain_name: &str) -> Result<HashSet<String>, String> {
+    if candidates.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let candidates: Vec<String> = candidates.into_iter().collect();
+    address_index.lookup_batch(&candidates, chain_name).await
+        .map(|found| found.into_keys().collect())
+        .map_err(|e| format!("Failed to look up monitored addresses: {}", e))
+}
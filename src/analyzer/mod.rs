@@ -7,6 +7,7 @@
 
 pub mod analyzer;
 pub mod chains;
+pub mod token_registry;
 
-pub use analyzer::run_analyzer;
-pub use analyzer::KeyValueDB;
+pub use analyzer::{run_analyzer, AnalyzerContext};
+pub use token_registry::TokenRegistry;
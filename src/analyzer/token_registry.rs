@@ -0,0 +1,200 @@
+use super::chains::utils::parse_raw_amount;
+use super::chains::DepositInfo;
+use crate::config::TokenConfig;
+use crate::types::AssetInfo;
+use log::{debug, info, warn};
+use std::collections::HashMap;
+
+/// `[[tokens]]` 설정 기반 입금 자산 레지스트리
+///
+/// 등록되지 않았거나 비활성화된 토큰의 입금은 무시한다 (spam 토큰 방지).
+/// native coin은 설정이 있을 때만 decimals/min_deposit을 적용하고, 없으면 analyzer 기본값을 유지한다.
+#[derive(Debug, Clone, Default)]
+pub struct TokenRegistry {
+    // (CHAIN, contract 소문자) -> 설정, native coin은 contract = ""
+    entries: HashMap<(String, String), TokenConfig>,
+}
+
+impl TokenRegistry {
+    /// `chain_names`: 설정 이름(소문자) -> fetcher 체인 이름 (`fetcher::factory::chain_names`)
+    ///
+    /// `[[tokens]] chain`은 설정 이름이지만 analyzer는 fetcher 체인 이름으로 조회하므로 등록 시 바꾼다.
+    pub fn new(tokens: &[TokenConfig], chain_names: &HashMap<String, &'static str>) -> Self {
+        let entries = tokens.iter()
+            .filter_map(|token| match chain_names.get(&token.chain.to_lowercase()) {
+                Some(chain_name) => Some((Self::key(chain_name, token.contract.as_deref()), token.clone())),
+                None => {
+                    warn!("[TokenRegistry] {} refers to unknown chain {}, ignored", token.symbol, token.chain);
+                    None
+                }
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    /// 입금의 자산 정보와 금액을 레지스트리 기준으로 확정
+    /// 무시해야 하는 입금이면 None
+    pub fn resolve(&self, chain_name: &str, mut deposit: DepositInfo) -> Option<DepositInfo> {
+        let token_address = deposit.asset.token_address.clone();
+
+        let token = match self.entries.get(&Self::key(chain_name, token_address.as_deref())) {
+            Some(token) => token,
+            None if token_address.is_none() => return Some(deposit),
+            None => {
+                debug!(
+                    "[TokenRegistry] {} unregistered token {} ignored (tx: {})",
+                    chain_name, token_address.unwrap_or_default(), deposit.tx_hash
+                );
+                return None;
+            }
+        };
+
+        if !token.enabled {
+            info!("[TokenRegistry] {} {} is disabled, deposit {} ignored", chain_name, token.symbol, deposit.tx_hash);
+            return None;
+        }
+
+        deposit.asset = match &token_address {
+            Some(address) => AssetInfo::token(&token.symbol, address, Some(token.decimals)),
+            None => AssetInfo::native(&token.symbol, token.decimals),
+        };
+        deposit.amount_decimal = match parse_raw_amount(&deposit.amount, token.decimals) {
+            Ok(amount) => Some(amount),
+            Err(e) => {
+                warn!("[TokenRegistry] Failed to scale {} amount {} (tx: {}): {}", token.symbol, deposit.amount, deposit.tx_hash, e);
+                None
+            }
+        };
+
+        if let (Some(min_deposit), Some(amount)) = (token.min_deposit, deposit.amount_decimal) {
+            if amount < min_deposit {
+                info!(
+                    "[TokenRegistry] {} deposit {} {} below min_deposit {}, ignored (tx: {})",
+                    chain_name, amount, token.symbol, min_deposit, deposit.tx_hash
+                );
+                return None;
+            }
+        }
+
+        Some(deposit)
+    }
+
+    fn key(chain_name: &str, contract: Option<&str>) -> (String, String) {
+        (chain_name.to_uppercase(), contract.unwrap_or_default().to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ChainConfig;
+    use crate::fetcher::factory::chain_names;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    const USDT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+    fn chain(symbol: &str) -> ChainConfig {
+        ChainConfig {
+            api: "http://localhost:8545".to_string(),
+            symbol: symbol.to_string(),
+            start_block: 0,
+            interval_secs: 1,
+            required_confirmations: 12,
+            reorg_depth: 0,
+            catchup_concurrency: 1,
+            catchup_window: 1,
+            analyzer_queue_size: 1,
+            rpc_method: None,
+            rpc_params_format: None,
+        }
+    }
+
+    fn token(chain: &str, contract: Option<&str>, symbol: &str, decimals: u32) -> TokenConfig {
+        TokenConfig {
+            chain: chain.to_string(),
+            contract: contract.map(str::to_string),
+            symbol: symbol.to_string(),
+            decimals,
+            min_deposit: None,
+            enabled: true,
+        }
+    }
+
+    fn registry(tokens: &[TokenConfig]) -> TokenRegistry {
+        // README 예시와 같이 설정 이름은 "ethereum"
+        let chains = vec![("ethereum".to_string(), chain("eth")), ("tron".to_string(), chain("trx"))];
+        TokenRegistry::new(tokens, &chain_names(&chains))
+    }
+
+    fn deposit(token_address: Option<&str>, amount: &str) -> DepositInfo {
+        let asset = match token_address {
+            Some(address) => AssetInfo::token("UNKNOWN", &address.to_lowercase(), None),
+            None => AssetInfo::native("ETH", 18),
+        };
+        DepositInfo::new("0xdeposit".to_string(), "0xtx".to_string(), 0, 100, amount.to_string(), None, asset)
+    }
+
+    #[test]
+    fn resolves_token_configured_by_section_name() {
+        // analyzer는 설정 이름("ethereum")이 아닌 fetcher 체인 이름으로 조회한다
+        let registry = registry(&[token("Ethereum", Some(USDT), "USDT", 6)]);
+        let analyzer_chain = chain_names(&[("ethereum".to_string(), chain("eth"))])["ethereum"];
+
+        let resolved = registry.resolve(analyzer_chain, deposit(Some(USDT), "0xf4240")).unwrap();
+        assert_eq!(resolved.asset.symbol, "USDT");
+        assert_eq!(resolved.asset.decimals, Some(6));
+        assert_eq!(resolved.amount_decimal, Some(Decimal::from(1)));
+
+        // 다른 체인의 같은 컨트랙트는 등록되지 않은 토큰
+        assert!(registry.resolve("TRON", deposit(Some(USDT), "0xf4240")).is_none());
+    }
+
+    #[test]
+    fn ignores_unregistered_token_but_keeps_native_coin() {
+        let registry = registry(&[]);
+        assert!(registry.resolve("SEPOLIA", deposit(Some(USDT), "0xf4240")).is_none());
+
+        let native = registry.resolve("SEPOLIA", deposit(None, "1000")).unwrap();
+        assert_eq!(native.asset.symbol, "ETH");
+    }
+
+    #[test]
+    fn skips_tokens_of_unknown_chains() {
+        let registry = registry(&[token("solana", Some(USDT), "USDT", 6)]);
+        assert!(registry.entries.is_empty());
+    }
+
+    #[test]
+    fn applies_min_deposit() {
+        let mut usdt = token("ethereum", Some(USDT), "USDT", 6);
+        usdt.min_deposit = Some(Decimal::from_str("1").unwrap());
+        let registry = registry(&[usdt]);
+
+        // 0.5 USDT
+        assert!(registry.resolve("SEPOLIA", deposit(Some(USDT), "0x7a120")).is_none());
+        // 정확히 min_deposit이면 통과
+        assert!(registry.resolve("SEPOLIA", deposit(Some(USDT), "0xf4240")).is_some());
+    }
+
+    #[test]
+    fn ignores_disabled_token() {
+        let mut usdt = token("ethereum", Some(USDT), "USDT", 6);
+        usdt.enabled = false;
+        let registry = registry(&[usdt]);
+
+        assert!(registry.resolve("SEPOLIA", deposit(Some(USDT), "0xf4240")).is_none());
+    }
+
+    #[test]
+    fn native_coin_settings_override_defaults() {
+        let mut eth = token("ethereum", None, "SEPOLIAETH", 18);
+        eth.min_deposit = Some(Decimal::from_str("0.01").unwrap());
+        let registry = registry(&[eth]);
+
+        let resolved = registry.resolve("SEPOLIA", deposit(None, "0x2386f26fc10000")).unwrap();
+        assert_eq!(resolved.asset.symbol, "SEPOLIAETH");
+        assert!(registry.resolve("SEPOLIA", deposit(None, "0x1")).is_none());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::respository::{AddressMetadata, MonitoredAddress, Repository};
+use crate::tasks::BackfillRequest;
+use crate::types::{BackfillJob, DepositFilter, DepositRecord, DepositStatusChange};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use super::server::{ApiError, ApiState};
+
+// 한 번에 조회할 수 있는 최대 입금 수
+const MAX_DEPOSIT_LIMIT: usize = 1000;
+
+/// 입금 + 상태 변경 이력
+#[derive(Serialize)]
+pub struct DepositDetail {
+    #[serde(flatten)]
+    pub deposit: DepositRecord,
+    pub history: Vec<DepositStatusChange>,
+}
+
+/// 체인별 스캔 진행 상황
+#[derive(Serialize)]
+pub struct ChainProgress {
+    pub chain: String,
+    pub last_processed_block: u64,
+    pub chain_head: Option<u64>,
+    pub lag: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct AddressLookup {
+    pub chain: String,
+    pub address: String,
+    #[serde(flatten)]
+    pub metadata: AddressMetadata,
+}
+
+#[derive(Deserialize)]
+pub struct AddAddressRequest {
+    pub chain: String,
+    pub address: String,
+    pub wallet_id: String,
+    #[serde(default)]
+    pub account_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ChainQuery {
+    pub chain: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct StartBackfillRequest {
+    pub chain: String,
+    pub from_block: u64,
+    #[serde(default)]
+    pub to_block: Option<u64>,
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BackfillListQuery {
+    #[serde(default)]
+    pub unfinished: bool,
+}
+
+/// GET /api/v1/deposits?wallet_id=&account_id=&address=&chain=&status=&limit=&offset=
+pub async fn list_deposits(
+    State(state): State<ApiState>,
+    Query(mut filter): Query<DepositFilter>,
+) -> Result<Json<Vec<DepositRecord>>, ApiError> {
+    filter.limit = filter.limit.min(MAX_DEPOSIT_LIMIT);
+
+    let deposits = state.repository.list_deposits(&filter).await
+        .map_err(|e| ApiError::internal(format!("Failed to list deposits: {}", e)))?;
+    Ok(Json(deposits))
+}
+
+/// GET /api/v1/deposits/{tx_hash}?chain= - 트랜잭션의 모든 입금 output과 상태 이력
+pub async fn get_deposit(
+    State(state): State<ApiState>,
+    Path(tx_hash): Path<String>,
+    Query(query): Query<ChainQuery>,
+) -> Result<Json<Vec<DepositDetail>>, ApiError> {
+    let filter = DepositFilter {
+        chain_name: query.chain,
+        tx_hash: Some(tx_hash.clone()),
+        limit: MAX_DEPOSIT_LIMIT,
+        ..DepositFilter::default()
+    };
+
+    let deposits = state.repository.list_deposits(&filter).await
+        .map_err(|e| ApiError::internal(format!("Failed to get deposit: {}", e)))?;
+    if deposits.is_empty() {
+        return Err(ApiError::not_found(format!("Deposit {} not found", tx_hash)));
+    }
+
+    let mut details = Vec::with_capacity(deposits.len());
+    for deposit in deposits {
+        let history = state.repository
+            .get_deposit_status_history(&deposit.tx_hash, deposit.output_index, &deposit.chain)
+            .await
+            .map_err(|e| ApiError::internal(format!("Failed to get deposit history: {}", e)))?;
+        details.push(DepositDetail { deposit, history });
+    }
+
+    Ok(Json(details))
+}
+
+/// GET /api/v1/chains - blockchain_state 기준 체인별 진행 상황
+pub async fn list_chains(State(state): State<ApiState>) -> Result<Json<Vec<ChainProgress>>, ApiError> {
+    let blocks = state.repository.list_last_processed_blocks().await
+        .map_err(|e| ApiError::internal(format!("Failed to list chains: {}", e)))?;
+
+    let mut chains = Vec::with_capacity(blocks.len());
+    for (chain, last_processed_block) in blocks {
+        let chain_head = state.head_tracker.head(&chain).await;
+        chains.push(ChainProgress {
+            lag: chain_head.map(|head| head.saturating_sub(last_processed_block)),
+            chain,
+            last_processed_block,
+            chain_head,
+        });
+    }
+
+    Ok(Json(chains))
+}
+
+/// GET /api/v1/addresses/{chain}/{address} - 관리 대상 주소 조회
+pub async fn lookup_address(
+    State(state): State<ApiState>,
+    Path((chain, address)): Path<(String, String)>,
+) -> Result<Json<AddressLookup>, ApiError> {
+    let metadata = state.address_index.lookup(&address, &chain).await
+        .map_err(|e| ApiError::internal(format!("Failed to look up address: {}", e)))?
+        .ok_or_else(|| ApiError::not_found(format!("{} is not monitored on {}", address, chain)))?;
+
+    Ok(Json(AddressLookup { chain, address, metadata }))
+}
+
+/// POST /api/v1/addresses - 관리 대상 주소 추가 (admin)
+pub async fn add_address(
+    State(state): State<ApiState>,
+    Json(request): Json<AddAddressRequest>,
+) -> Result<(StatusCode, Json<AddressLookup>), ApiError> {
+    if request.address.trim().is_empty() || request.chain.trim().is_empty() || request.wallet_id.trim().is_empty() {
+        return Err(ApiError::bad_request("chain, address and wallet_id are required"));
+    }
+
+    let metadata = AddressMetadata {
+        wallet_id: request.wallet_id,
+        account_id: request.account_id,
+    };
+    let entry = MonitoredAddress {
+        address: request.address.clone(),
+        chain_name: request.chain.clone(),
+        metadata: metadata.clone(),
+    };
+
+    state.address_index.insert(&[entry]).await
+        .map_err(|e| ApiError::internal(format!("Failed to add address: {}", e)))?;
+
+    Ok((StatusCode::CREATED, Json(AddressLookup { chain: request.chain, address: request.address, metadata })))
+}
+
+/// DELETE /api/v1/addresses/{chain}/{address} - 관리 대상 주소 삭제 (admin)
+pub async fn remove_address(
+    State(state): State<ApiState>,
+    Path((chain, address)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    let removed = state.address_index.remove(&address, &chain).await
+        .map_err(|e| ApiError::internal(format!("Failed to remove address: {}", e)))?;
+
+    if removed {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found(format!("{} is not monitored on {}", address, chain)))
+    }
+}
+
+/// GET /api/v1/backfills?unfinished= - backfill 작업 목록 (id 오름차순)
+pub async fn list_backfills(
+    State(state): State<ApiState>,
+    Query(query): Query<BackfillListQuery>,
+) -> Result<Json<Vec<BackfillJob>>, ApiError> {
+    let jobs = state.repository.list_backfill_jobs(query.unfinished).await
+        .map_err(|e| ApiError::internal(format!("Failed to list backfill jobs: {}", e)))?;
+    Ok(Json(jobs))
+}
+
+/// GET /api/v1/backfills/{id}
+pub async fn get_backfill(
+    State(state): State<ApiState>,
+    Path(id): Path<i64>,
+) -> Result<Json<BackfillJob>, ApiError> {
+    let job = state.repository.get_backfill_job(id).await
+        .map_err(|e| ApiError::internal(format!("Failed to get backfill job: {}", e)))?
+        .ok_or_else(|| ApiError::not_found(format!("Backfill job {} not found", id)))?;
+    Ok(Json(job))
+}
+
+/// POST /api/v1/backfills - 이미 처리한 블록 구간 backfill (admin)
+///
+/// to_block을 생략하면 현재 last_processed_block까지. 작업은 저장된 뒤 체인별 순서대로 실행된다.
+pub async fn start_backfill(
+    State(state): State<ApiState>,
+    Json(request): Json<StartBackfillRequest>,
+) -> Result<(StatusCode, Json<BackfillJob>), ApiError> {
+    let (chain_name, to_block) = validate_backfill(&state, &request.chain, request.from_block, request.to_block).await?;
+
+    let job = state.backfill
+        .submit(BackfillRequest {
+            chain_name,
+            from_block: request.from_block,
+            to_block: Some(to_block),
+            address: request.address,
+        })
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// backfill 구간 확인 - 체인 심볼과 to_block 반환
+async fn validate_backfill(state: &ApiState, chain: &str, from_block: u64, to_block: Option<u64>) -> Result<(String, u64), ApiError> {
+    let chain_name = chain.to_uppercase();
+    if !state.backfill.supports(&chain_name) {
+        return Err(ApiError::bad_request(format!("Unknown chain: {}", chain)));
+    }
+
+    let last_processed_block = state.repository.get_last_processed_block(&chain_name).await
+        .map_err(|e| ApiError::internal(format!("Failed to get last processed block: {}", e)))?;
+    let to_block = to_block.unwrap_or(last_processed_block);
+    if to_block > last_processed_block {
+        return Err(ApiError::bad_request(format!(
+            "to_block {} is beyond last processed block {} for {}",
+            to_block, last_processed_block, chain_name
+        )));
+    }
+    if from_block > to_block {
+        return Err(ApiError::bad_request(format!("from_block {} is after to_block {}", from_block, to_block)));
+    }
+
+    let blocks = to_block - from_block + 1;
+    if blocks > state.settings.max_backfill_blocks {
+        return Err(ApiError::bad_request(format!(
+            "Backfill of {} blocks exceeds max_backfill_blocks ({})",
+            blocks, state.settings.max_backfill_blocks
+        )));
+    }
+
+    Ok((chain_name, to_block))
+}
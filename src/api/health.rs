@@ -0,0 +1,140 @@
+use crate::notification::EventPublisher;
+use crate::respository::{AddressIndex, Repository, RepositoryWrapper};
+use crate::types::AppError;
+use log::warn;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::{interval, timeout, Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+// blockchain_state 진행 상황 조회 주기
+const PROGRESS_SAMPLE_INTERVAL_SECS: u64 = 5;
+
+/// 컴포넌트별 상태
+#[derive(Debug, Serialize)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// `/readyz` 응답
+#[derive(Debug, Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub components: Vec<ComponentHealth>,
+}
+
+/// Readiness 판단 - 저장소, 주소 인덱스, 알림 sink 연결과 체인별 진행 정체 여부
+///
+/// 체인의 last_processed_block이 `interval_secs * stall_threshold_multiplier` 동안 바뀌지 않으면
+/// 정체로 본다 (같은 블록을 계속 재시도하는 fetcher 포함).
+pub struct HealthChecker {
+    repository: Arc<RepositoryWrapper>,
+    address_index: Arc<dyn AddressIndex>,
+    publisher: Option<Arc<EventPublisher>>,
+    // chain_name -> 정체로 판단하는 시간
+    stall_timeouts: HashMap<String, Duration>,
+    // chain_name -> (last_processed_block, 마지막으로 바뀐 시각)
+    progress: RwLock<HashMap<String, (u64, Instant)>>,
+    check_timeout: Duration,
+}
+
+impl HealthChecker {
+    pub fn new(
+        repository: Arc<RepositoryWrapper>,
+        address_index: Arc<dyn AddressIndex>,
+        publisher: Option<Arc<EventPublisher>>,
+        stall_timeouts: HashMap<String, Duration>,
+        check_timeout: Duration,
+    ) -> Self {
+        Self {
+            repository,
+            address_index,
+            publisher,
+            stall_timeouts: stall_timeouts.into_iter().map(|(chain, t)| (chain.to_uppercase(), t)).collect(),
+            progress: RwLock::new(HashMap::new()),
+            check_timeout,
+        }
+    }
+
+    /// blockchain_state 조회 - 블록 번호가 바뀐 체인만 시각 갱신
+    async fn sample_progress(&self) -> Result<(), AppError> {
+        let blocks = self.repository.list_last_processed_blocks().await?;
+        let now = Instant::now();
+
+        let mut progress = self.progress.write().await;
+        for (chain, block_number) in blocks {
+            let chain = chain.to_uppercase();
+            if progress.get(&chain).is_none_or(|(last, _)| *last != block_number) {
+                progress.insert(chain, (block_number, now));
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn readiness(&self) -> ReadinessReport {
+        let mut components = vec![
+            self.check("database", async { self.repository.health_check().await.map_err(|e| e.to_string()) }).await,
+            self.check("address_index", async { self.address_index.health_check().await.map_err(|e| e.to_string()) }).await,
+        ];
+        if let Some(publisher) = &self.publisher {
+            components.push(self.check("notifier", publisher.health_check()).await);
+        }
+
+        let progress = self.progress.read().await;
+        let mut chains: Vec<_> = self.stall_timeouts.iter().collect();
+        chains.sort_by(|a, b| a.0.cmp(b.0));
+        for (chain, stall_timeout) in chains {
+            let (healthy, detail) = match progress.get(chain) {
+                Some((block_number, since)) => {
+                    let idle = since.elapsed();
+                    (
+                        idle <= *stall_timeout,
+                        format!(
+                            "last_processed_block {} unchanged for {}s (stall after {}s)",
+                            block_number, idle.as_secs(), stall_timeout.as_secs()
+                        ),
+                    )
+                }
+                None => (false, "no progress recorded in blockchain_state".to_string()),
+            };
+            components.push(ComponentHealth { name: format!("chain:{}", chain), healthy, detail: Some(detail) });
+        }
+
+        ReadinessReport {
+            ready: components.iter().all(|c| c.healthy),
+            components,
+        }
+    }
+
+    async fn check(&self, name: &str, probe: impl Future<Output = Result<(), String>>) -> ComponentHealth {
+        let result = match timeout(self.check_timeout, probe).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("timed out after {}s", self.check_timeout.as_secs())),
+        };
+        ComponentHealth {
+            name: name.to_string(),
+            healthy: result.is_ok(),
+            detail: result.err(),
+        }
+    }
+}
+
+/// 체인별 진행 상황을 주기적으로 기록 (probe 호출 빈도와 관계없이 정체 시간 계산)
+pub async fn run_progress_sampler(checker: Arc<HealthChecker>, shutdown: CancellationToken) {
+    let mut tick = interval(Duration::from_secs(PROGRESS_SAMPLE_INTERVAL_SECS));
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {}
+            _ = shutdown.cancelled() => break,
+        }
+        if let Err(e) = checker.sample_progress().await {
+            warn!("[Health] Failed to sample scanner progress: {}", e);
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod server;
+pub mod handlers;
+pub mod monitoring;
+pub mod health;
+
+pub use server::{ApiState, run_api_server};
+pub use monitoring::run_monitoring_server;
+pub use health::{HealthChecker, run_progress_sampler};
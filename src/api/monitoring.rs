@@ -0,0 +1,53 @@
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use log::{error, info};
+use serde_json::json;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use super::health::HealthChecker;
+
+/// 모니터링 서버 실행 (`GET /metrics`, `/healthz`, `/readyz`, 인증 없음)
+pub async fn run_monitoring_server(listen_addr: String, health: Arc<HealthChecker>, shutdown: CancellationToken) {
+    let app = Router::new()
+        .route("/metrics", get(metrics))
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(health);
+
+    let listener = match tokio::net::TcpListener::bind(&listen_addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("[Monitoring] Failed to bind {}: {}", listen_addr, e);
+            return;
+        }
+    };
+
+    info!("[Monitoring] Serving /metrics, /healthz, /readyz on {}", listen_addr);
+    if let Err(e) = axum::serve(listener, app).with_graceful_shutdown(shutdown.cancelled_owned()).await {
+        error!("[Monitoring] Server error: {}", e);
+    }
+    info!("[Monitoring] Stopped");
+}
+
+async fn metrics() -> impl IntoResponse {
+    match crate::metrics::render() {
+        Ok(body) => (StatusCode::OK, [(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, [(header::CONTENT_TYPE, "text/plain")], e),
+    }
+}
+
+/// 프로세스가 요청에 응답할 수 있으면 항상 200
+async fn healthz() -> impl IntoResponse {
+    Json(json!({ "status": "ok" }))
+}
+
+/// 모든 컴포넌트가 정상이면 200, 아니면 503 (본문에 컴포넌트별 상태)
+async fn readyz(State(health): State<Arc<HealthChecker>>) -> impl IntoResponse {
+    let report = health.readiness().await;
+    let status = if report.ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(report))
+}
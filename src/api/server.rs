@@ -0,0 +1,130 @@
+use crate::config::ApiSettings;
+use crate::respository::{AddressIndex, RepositoryWrapper};
+use crate::tasks::{BackfillScheduler, ChainHeadTracker};
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use log::{error, info};
+use serde_json::json;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+use super::handlers;
+
+/// API 핸들러 공유 상태
+#[derive(Clone)]
+pub struct ApiState {
+    pub repository: Arc<RepositoryWrapper>,
+    pub address_index: Arc<dyn AddressIndex>,
+    pub head_tracker: Arc<ChainHeadTracker>,
+    pub backfill: Arc<BackfillScheduler>,
+    pub settings: Arc<ApiSettings>,
+}
+
+/// 조회/관리 HTTP API 실행
+///
+/// 조회 API는 `auth_token` 또는 `admin_token`, 관리 API는 `admin_token`으로만 호출할 수 있다.
+pub async fn run_api_server(state: ApiState, shutdown: CancellationToken) {
+    let listen_addr = state.settings.listen_addr.clone();
+
+    let read_routes = Router::new()
+        .route("/api/v1/deposits", get(handlers::list_deposits))
+        .route("/api/v1/deposits/:tx_hash", get(handlers::get_deposit))
+        .route("/api/v1/chains", get(handlers::list_chains))
+        .route("/api/v1/addresses/:chain/:address", get(handlers::lookup_address))
+        .route("/api/v1/backfills", get(handlers::list_backfills))
+        .route("/api/v1/backfills/:id", get(handlers::get_backfill))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_read_token));
+
+    let admin_routes = Router::new()
+        .route("/api/v1/addresses", post(handlers::add_address))
+        .route("/api/v1/addresses/:chain/:address", axum::routing::delete(handlers::remove_address))
+        .route("/api/v1/backfills", post(handlers::start_backfill))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_admin_token));
+
+    let app = read_routes.merge(admin_routes).with_state(state);
+
+    let listener = match tokio::net::TcpListener::bind(&listen_addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("[API] Failed to bind {}: {}", listen_addr, e);
+            return;
+        }
+    };
+
+    info!("[API] Listening on {}", listen_addr);
+    if let Err(e) = axum::serve(listener, app).with_graceful_shutdown(shutdown.cancelled_owned()).await {
+        error!("[API] Server error: {}", e);
+    }
+    info!("[API] Stopped");
+}
+
+/// API 오류 응답 (`{"error": "..."}`)
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self { status, message: message.into() }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+async fn require_read_token(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let token = bearer_token(&request);
+    let authorized = token.is_some_and(|token| {
+        constant_time_eq(token, &state.settings.auth_token)
+            || state.settings.admin_token.as_deref().is_some_and(|admin| constant_time_eq(token, admin))
+    });
+
+    if !authorized {
+        return ApiError::new(StatusCode::UNAUTHORIZED, "Missing or invalid token").into_response();
+    }
+    next.run(request).await
+}
+
+async fn require_admin_token(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let Some(admin_token) = state.settings.admin_token.as_deref() else {
+        return ApiError::new(StatusCode::FORBIDDEN, "Admin API is disabled (no admin_token configured)").into_response();
+    };
+
+    if !bearer_token(&request).is_some_and(|token| constant_time_eq(token, admin_token)) {
+        return ApiError::new(StatusCode::UNAUTHORIZED, "Missing or invalid admin token").into_response();
+    }
+    next.run(request).await
+}
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    request.headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+// 토큰 비교 시간으로 토큰 내용이 드러나지 않도록 모든 바이트 비교
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
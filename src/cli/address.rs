@@ -0,0 +1,142 @@
+use crate::config::Settings;
+use crate::respository::{open_address_index, AddressIndex, AddressMetadata, MonitoredAddress};
+use crate::tasks::CustomerAddressData;
+use crate::types::AppError;
+use clap::Subcommand;
+use log::info;
+use std::sync::Arc;
+
+use super::{open_repository, print_json};
+
+// import 시 한 번에 등록할 주소 수
+const IMPORT_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Subcommand)]
+pub enum AddressCommand {
+    /// Add a monitored address (updates wallet/account if it already exists)
+    Add {
+        #[arg(long)]
+        chain: String,
+        #[arg(long)]
+        address: String,
+        #[arg(long)]
+        wallet_id: String,
+        /// Omit for an omnibus (master) address
+        #[arg(long)]
+        account_id: Option<String>,
+    },
+    /// Stop monitoring an address
+    Remove {
+        #[arg(long)]
+        chain: String,
+        #[arg(long)]
+        address: String,
+    },
+    /// Show the wallet/account of a monitored address
+    Get {
+        #[arg(long)]
+        chain: String,
+        #[arg(long)]
+        address: String,
+    },
+    /// List monitored addresses
+    List {
+        #[arg(long)]
+        chain: Option<String>,
+    },
+    /// Export monitored addresses in the customer address cache file format
+    Export {
+        #[arg(long)]
+        chain: Option<String>,
+        /// Output file (stdout if omitted)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Import addresses from a customer address cache file
+    Import {
+        #[arg(long)]
+        input: String,
+    },
+}
+
+pub async fn execute(settings: &Settings, command: AddressCommand) -> Result<(), AppError> {
+    let repository = open_repository(settings).await?;
+    let address_index = open_address_index(settings, &repository, false)?;
+
+    match command {
+        AddressCommand::Add { chain, address, wallet_id, account_id } => {
+            let entry = MonitoredAddress {
+                address,
+                chain_name: chain,
+                metadata: AddressMetadata { wallet_id, account_id },
+            };
+            address_index.insert(std::slice::from_ref(&entry)).await?;
+            info!("Added {} on {}", entry.address, entry.chain_name);
+            print_json(&to_cache_entry(entry))
+        }
+        AddressCommand::Remove { chain, address } => {
+            if !address_index.remove(&address, &chain).await? {
+                return Err(AppError::Config(format!("{} is not monitored on {}", address, chain)));
+            }
+            info!("Removed {} on {}", address, chain);
+            Ok(())
+        }
+        AddressCommand::Get { chain, address } => {
+            let metadata = address_index.lookup(&address, &chain).await?
+                .ok_or_else(|| AppError::Config(format!("{} is not monitored on {}", address, chain)))?;
+            print_json(&to_cache_entry(MonitoredAddress { address, chain_name: chain, metadata }))
+        }
+        AddressCommand::List { chain } => {
+            let entries = list_entries(&address_index, chain.as_deref()).await?;
+            print_json(&entries)
+        }
+        AddressCommand::Export { chain, output } => {
+            let entries = list_entries(&address_index, chain.as_deref()).await?;
+            match output {
+                Some(path) => {
+                    let json = serde_json::to_string_pretty(&entries)
+                        .map_err(|e| AppError::Initialization(format!("Failed to serialize addresses: {}", e)))?;
+                    tokio::fs::write(&path, json).await
+                        .map_err(|e| AppError::Initialization(format!("Failed to write {}: {}", path, e)))?;
+                    info!("Exported {} address(es) to {}", entries.len(), path);
+                    Ok(())
+                }
+                None => print_json(&entries),
+            }
+        }
+        AddressCommand::Import { input } => {
+            let content = tokio::fs::read_to_string(&input).await
+                .map_err(|e| AppError::Initialization(format!("Failed to read {}: {}", input, e)))?;
+            let addresses: Vec<CustomerAddressData> = serde_json::from_str(&content)
+                .map_err(|e| AppError::Initialization(format!("Failed to parse JSON from {}: {}", input, e)))?;
+
+            let entries: Vec<MonitoredAddress> = addresses.into_iter()
+                .map(|addr| MonitoredAddress {
+                    address: addr.address,
+                    chain_name: addr.chain,
+                    metadata: AddressMetadata { wallet_id: addr.wallet_id, account_id: addr.account_id },
+                })
+                .collect();
+
+            let mut imported = 0;
+            for batch in entries.chunks(IMPORT_BATCH_SIZE) {
+                imported += address_index.insert(batch).await?;
+                info!("Imported {}/{} address(es)", imported, entries.len());
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn list_entries(address_index: &Arc<dyn AddressIndex>, chain: Option<&str>) -> Result<Vec<CustomerAddressData>, AppError> {
+    Ok(address_index.list(chain).await?.into_iter().map(to_cache_entry).collect())
+}
+
+fn to_cache_entry(entry: MonitoredAddress) -> CustomerAddressData {
+    CustomerAddressData {
+        address: entry.address,
+        chain: entry.chain_name,
+        wallet_id: entry.metadata.wallet_id,
+        account_id: entry.metadata.account_id,
+    }
+}
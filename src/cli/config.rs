@@ -0,0 +1,133 @@
+use crate::config::Settings;
+use crate::fetcher::factory::{build_fetcher, chain_names};
+use crate::notification::envelope::EnvelopeSigner;
+use crate::types::AppError;
+use clap::Subcommand;
+use log::info;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Check the configuration without connecting to any service
+    Validate,
+}
+
+pub fn execute(settings: &Settings, command: ConfigCommand) -> Result<(), AppError> {
+    match command {
+        ConfigCommand::Validate => {
+            let problems = validate(settings);
+            if problems.is_empty() {
+                info!("Configuration OK ({} chain(s))", settings.blockchain.chains.len());
+                return Ok(());
+            }
+
+            for problem in &problems {
+                println!("- {}", problem);
+            }
+            Err(AppError::Config(format!("{} configuration problem(s) found", problems.len())))
+        }
+    }
+}
+
+/// 파싱은 성공했지만 실행 시 실패하거나 무시될 설정
+fn validate(settings: &Settings) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if settings.blockchain.chains.is_empty() {
+        problems.push("No [blockchain.<name>] configured".to_string());
+    }
+
+    // symbol -> 설정 이름
+    let mut symbols: HashMap<String, String> = HashMap::new();
+    let mut chains: Vec<_> = settings.get_chain_configs();
+    chains.sort_by(|a, b| a.0.cmp(&b.0));
+    for (name, chain) in &chains {
+        if build_fetcher(name, chain).is_none() {
+            problems.push(format!("blockchain.{}: unsupported chain", name));
+        }
+        if chain.api.trim().is_empty() {
+            problems.push(format!("blockchain.{}: api is empty", name));
+        }
+        if chain.interval_secs == 0 {
+            problems.push(format!("blockchain.{}: interval_secs must be greater than 0", name));
+        }
+        if chain.catchup_concurrency == 0 {
+            problems.push(format!("blockchain.{}: catchup_concurrency must be at least 1", name));
+        }
+        if let Some(other) = symbols.insert(chain.symbol.to_uppercase(), name.clone()) {
+            problems.push(format!("blockchain.{}: symbol {} is also used by blockchain.{}", name, chain.symbol, other));
+        }
+    }
+
+    // analyzer와 같은 방식으로 설정 이름을 fetcher 체인 이름으로 바꿔서 확인
+    let chain_names = chain_names(&chains);
+    for token in &settings.tokens {
+        if !chain_names.contains_key(&token.chain.to_lowercase()) {
+            problems.push(format!("tokens: {} refers to unknown chain {}", token.symbol, token.chain));
+        }
+    }
+
+    let repository = &settings.repository;
+    if !["rocksdb", "postgres"].iter().any(|backend| repository.address_index.eq_ignore_ascii_case(backend)) {
+        problems.push(format!("repository.address_index: unknown backend {}", repository.address_index));
+    }
+    if let Some(rocksdb_path) = repository.rocksdb_path.as_deref().filter(|_| !repository.memory_db) {
+        if repository.address_index.eq_ignore_ascii_case("postgres") {
+            problems.push("repository.address_index = \"postgres\" cannot be used with repository.rocksdb_path".to_string());
+        } else if rocksdb_path == repository.leveldb_path {
+            problems.push("repository.rocksdb_path must differ from leveldb_path (RocksDB address index)".to_string());
+        }
+    }
+    if repository.address_bloom_filter && !(repository.bloom_false_positive_rate > 0.0 && repository.bloom_false_positive_rate < 1.0) {
+        problems.push("repository.bloom_false_positive_rate must be between 0 and 1".to_string());
+    }
+
+    if let Some(notification) = &settings.notification {
+        if notification.all_sinks().is_empty() {
+            problems.push("notification: no sinks configured".to_string());
+        }
+        if notification.outbox_max_attempts == 0 {
+            problems.push("notification.outbox_max_attempts must be greater than 0".to_string());
+        }
+        if let Some(signing) = &notification.signing {
+            if let Err(e) = EnvelopeSigner::from_settings(signing) {
+                problems.push(format!("notification.signing: {}", e));
+            }
+        }
+    }
+
+    if let Some(api) = &settings.api {
+        if api.listen_addr.parse::<SocketAddr>().is_err() {
+            problems.push(format!("api.listen_addr: invalid address {}", api.listen_addr));
+        }
+        if api.auth_token.trim().is_empty() {
+            problems.push("api.auth_token is empty".to_string());
+        }
+        if api.admin_token.as_deref().is_some_and(|token| token.trim().is_empty()) {
+            problems.push("api.admin_token is empty (omit it to disable the admin API)".to_string());
+        }
+    }
+
+    if settings.backfill.blocks_per_second <= 0.0 {
+        problems.push("backfill.blocks_per_second must be greater than 0".to_string());
+    }
+    if settings.backfill.poll_interval_secs == 0 {
+        problems.push("backfill.poll_interval_secs must be greater than 0".to_string());
+    }
+
+    if settings.shutdown.timeout_secs == 0 {
+        problems.push("shutdown.timeout_secs must be greater than 0".to_string());
+    }
+
+    if let Some(monitoring) = &settings.monitoring {
+        if monitoring.listen_addr.parse::<SocketAddr>().is_err() {
+            problems.push(format!("monitoring.listen_addr: invalid address {}", monitoring.listen_addr));
+        }
+        if monitoring.stall_threshold_multiplier == 0 {
+            problems.push("monitoring.stall_threshold_multiplier must be greater than 0".to_string());
+        }
+    }
+
+    problems
+}
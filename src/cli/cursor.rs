@@ -0,0 +1,60 @@
+use crate::config::Settings;
+use crate::respository::Repository;
+use crate::types::AppError;
+use clap::Subcommand;
+use log::{info, warn};
+use serde::Serialize;
+
+use super::{open_repository, print_json};
+
+#[derive(Debug, Subcommand)]
+pub enum CursorCommand {
+    /// Show the last processed block (all chains if --chain is omitted)
+    Get {
+        /// Chain symbol (e.g. SEPOLIA, BTC)
+        #[arg(long)]
+        chain: Option<String>,
+    },
+    /// Move the last processed block; the scanner resumes from block + 1 (stop the scanner first)
+    Set {
+        /// Chain symbol (e.g. SEPOLIA, BTC)
+        #[arg(long)]
+        chain: String,
+        #[arg(long)]
+        block: u64,
+    },
+}
+
+#[derive(Serialize)]
+struct Cursor {
+    chain: String,
+    last_processed_block: u64,
+}
+
+pub async fn execute(settings: &Settings, command: CursorCommand) -> Result<(), AppError> {
+    let repository = open_repository(settings).await?;
+
+    match command {
+        CursorCommand::Get { chain: Some(chain) } => {
+            let chain = chain.to_uppercase();
+            let last_processed_block = repository.get_last_processed_block(&chain).await?;
+            print_json(&Cursor { chain, last_processed_block })
+        }
+        CursorCommand::Get { chain: None } => {
+            let cursors: Vec<Cursor> = repository.list_last_processed_blocks().await?
+                .into_iter()
+                .map(|(chain, last_processed_block)| Cursor { chain, last_processed_block })
+                .collect();
+            print_json(&cursors)
+        }
+        CursorCommand::Set { chain, block } => {
+            let chain = chain.to_uppercase();
+            let previous = repository.get_last_processed_block(&chain).await?;
+            // 실행 중인 스캐너는 메모리의 진행 위치로 계속 덮어쓴다
+            warn!("The scanner must be stopped while moving the cursor, otherwise it overwrites the new value");
+            repository.update_last_processed_block(&chain, block).await?;
+            info!("{} last processed block moved from {} to {}", chain, previous, block);
+            print_json(&Cursor { chain, last_processed_block: block })
+        }
+    }
+}
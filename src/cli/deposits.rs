@@ -0,0 +1,172 @@
+use crate::api::handlers::DepositDetail;
+use crate::config::Settings;
+use crate::notification::DepositEvent;
+use crate::respository::{Repository, RepositoryWrapper};
+use crate::types::{AppError, DepositFilter, DepositRecord, DepositStatus};
+use clap::Subcommand;
+use log::{info, warn};
+
+use super::{open_repository, print_json};
+
+// 트랜잭션 하나에서 조회할 최대 입금 output 수
+const MAX_TX_OUTPUTS: usize = 1000;
+
+#[derive(Debug, Subcommand)]
+pub enum DepositsCommand {
+    /// List deposits, newest block first
+    List {
+        #[arg(long)]
+        wallet_id: Option<String>,
+        #[arg(long)]
+        account_id: Option<String>,
+        #[arg(long)]
+        address: Option<String>,
+        #[arg(long)]
+        chain: Option<String>,
+        /// detected, confirming, confirmed, orphaned, failed, flagged_for_review
+        #[arg(long)]
+        status: Option<DepositStatus>,
+        #[arg(long, default_value_t = 100)]
+        limit: usize,
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+    },
+    /// Show every deposit output of a transaction with its status history
+    Show {
+        tx_hash: String,
+        #[arg(long)]
+        chain: Option<String>,
+    },
+    /// Queue the notification for a deposit's current status again (delivered by the running scanner's outbox)
+    Resend {
+        tx_hash: String,
+        #[arg(long)]
+        chain: Option<String>,
+        /// Only this output (all outputs of the transaction if omitted)
+        #[arg(long)]
+        output_index: Option<u32>,
+    },
+}
+
+pub async fn execute(settings: &Settings, command: DepositsCommand) -> Result<(), AppError> {
+    let repository = open_repository(settings).await?;
+
+    match command {
+        DepositsCommand::List { wallet_id, account_id, address, chain, status, limit, offset } => {
+            let filter = DepositFilter {
+                wallet_id,
+                account_id,
+                address,
+                chain_name: chain,
+                tx_hash: None,
+                status,
+                limit,
+                offset,
+            };
+            print_json(&repository.list_deposits(&filter).await?)
+        }
+        DepositsCommand::Show { tx_hash, chain } => {
+            let deposits = find_deposits(&repository, &tx_hash, chain).await?;
+
+            let mut details = Vec::with_capacity(deposits.len());
+            for deposit in deposits {
+                let history = repository
+                    .get_deposit_status_history(&deposit.tx_hash, deposit.output_index, &deposit.chain)
+                    .await?;
+                details.push(DepositDetail { deposit, history });
+            }
+            print_json(&details)
+        }
+        DepositsCommand::Resend { tx_hash, chain, output_index } => {
+            if settings.notification.is_none() {
+                warn!("[notification] is not configured - queued events stay in the outbox until a notifier is set up");
+            }
+
+            let deposits: Vec<DepositRecord> = find_deposits(&repository, &tx_hash, chain).await?
+                .into_iter()
+                .filter(|deposit| output_index.is_none_or(|index| deposit.output_index == index))
+                .collect();
+            if deposits.is_empty() {
+                return Err(AppError::Config(format!("Deposit {} has no output {:?}", tx_hash, output_index)));
+            }
+
+            for deposit in deposits {
+                let Some(event) = deposit_event(&deposit) else {
+                    warn!(
+                        "Skipping {} output {} ({}): no notification for this status",
+                        deposit.tx_hash, deposit.output_index, deposit.status.as_str()
+                    );
+                    continue;
+                };
+                repository.enqueue_notification(&event).await?;
+                info!("Queued {} for {} output {}", event.event_type(), deposit.tx_hash, deposit.output_index);
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn find_deposits(
+    repository: &RepositoryWrapper,
+    tx_hash: &str,
+    chain: Option<String>,
+) -> Result<Vec<DepositRecord>, AppError> {
+    let filter = DepositFilter {
+        chain_name: chain,
+        tx_hash: Some(tx_hash.to_string()),
+        limit: MAX_TX_OUTPUTS,
+        ..DepositFilter::default()
+    };
+
+    let deposits = repository.list_deposits(&filter).await?;
+    if deposits.is_empty() {
+        return Err(AppError::Config(format!("Deposit {} not found", tx_hash)));
+    }
+    Ok(deposits)
+}
+
+/// 현재 상태에 해당하는 알림 (failed / flagged_for_review는 알림 없음)
+fn deposit_event(deposit: &DepositRecord) -> Option<DepositEvent> {
+    let deposit = deposit.clone();
+    match deposit.status {
+        DepositStatus::Detected | DepositStatus::Confirming => Some(DepositEvent::DepositDetected {
+            address: deposit.address,
+            wallet_id: deposit.wallet_id,
+            account_id: deposit.account_id,
+            chain: deposit.chain,
+            tx_hash: deposit.tx_hash,
+            output_index: deposit.output_index,
+            amount: deposit.amount,
+            asset: deposit.asset,
+            block_number: deposit.block_number,
+            block_hash: deposit.block_hash.clone(),
+            confirmations: deposit.confirmations,
+        }),
+        DepositStatus::Confirmed => Some(DepositEvent::DepositConfirmed {
+            address: deposit.address,
+            wallet_id: deposit.wallet_id,
+            account_id: deposit.account_id,
+            chain: deposit.chain,
+            tx_hash: deposit.tx_hash,
+            output_index: deposit.output_index,
+            amount: deposit.amount,
+            asset: deposit.asset,
+            block_number: deposit.block_number,
+            block_hash: deposit.block_hash.clone(),
+            confirmations: deposit.confirmations,
+        }),
+        DepositStatus::Orphaned => Some(DepositEvent::DepositOrphaned {
+            address: deposit.address,
+            wallet_id: deposit.wallet_id,
+            account_id: deposit.account_id,
+            chain: deposit.chain,
+            tx_hash: deposit.tx_hash,
+            output_index: deposit.output_index,
+            amount: deposit.amount,
+            asset: deposit.asset,
+            block_number: deposit.block_number,
+            block_hash: deposit.block_hash.clone(),
+        }),
+        DepositStatus::Failed | DepositStatus::FlaggedForReview => None,
+    }
+}
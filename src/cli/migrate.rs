@@ -0,0 +1,24 @@
+use crate::config::Settings;
+use crate::types::AppError;
+use log::info;
+
+/// `xscanner migrate [--dry-run]` - 스키마 마이그레이션 적용 (dry-run이면 적용할 목록만 출력)
+pub async fn execute(settings: &Settings, dry_run: bool) -> Result<(), AppError> {
+    if settings.repository.memory_db {
+        return Err(AppError::Config("migrate requires PostgreSQL (memory_db = false)".to_string()));
+    }
+    if settings.repository.rocksdb_path.is_some() {
+        return Err(AppError::Config("migrate requires PostgreSQL (rocksdb_path is set)".to_string()));
+    }
+
+    let pool = crate::respository::connect_db(&settings.repository.postgresql_url).await
+        .map_err(|e| AppError::Database(format!("Failed to connect to PostgreSQL: {}", e)))?;
+    let migrations = crate::respository::migrations::run_migrations(&pool, dry_run).await?;
+
+    if dry_run {
+        info!("{} migration(s) pending", migrations.len());
+    } else {
+        info!("{} migration(s) applied", migrations.len());
+    }
+    Ok(())
+}
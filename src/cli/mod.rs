@@ -0,0 +1,101 @@
+//! `xscanner` 명령줄 - 서브커맨드 없이 실행하면 `run` (스캐너 실행)
+
+mod address;
+mod config;
+mod cursor;
+mod deposits;
+mod migrate;
+mod rescan;
+
+pub use address::AddressCommand;
+pub use config::ConfigCommand;
+pub use cursor::CursorCommand;
+pub use deposits::DepositsCommand;
+
+use crate::config::Settings;
+use crate::respository::RepositoryWrapper;
+use crate::types::AppError;
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Multi-chain deposit scanner")]
+pub struct Cli {
+    /// Path to the configuration file (APP_* environment variables still override it)
+    #[arg(long, global = true, default_value = "./config.toml")]
+    pub config: String,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the scanner (default when no subcommand is given)
+    Run,
+    /// Apply pending schema migrations and exit
+    Migrate {
+        /// Only list the migrations that would be applied
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Queue a backfill of an already processed block range (run by the scanner)
+    Rescan {
+        /// Chain symbol (e.g. SEPOLIA, BTC)
+        #[arg(long)]
+        chain: String,
+        #[arg(long)]
+        from: u64,
+        /// Last block to rescan (defaults to the last processed block)
+        #[arg(long)]
+        to: Option<u64>,
+        /// Only process deposits to this address
+        #[arg(long)]
+        address: Option<String>,
+        /// Wait until the scanner finishes the job
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Manage monitored addresses
+    #[command(subcommand)]
+    Address(AddressCommand),
+    /// Inspect or move a chain's last processed block
+    #[command(subcommand)]
+    Cursor(CursorCommand),
+    /// Query stored deposits and resend their notifications
+    #[command(subcommand)]
+    Deposits(DepositsCommand),
+    /// Configuration checks
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+/// `run` 이외의 서브커맨드 실행
+pub async fn execute(command: Command, settings: &Settings) -> Result<(), AppError> {
+    match command {
+        Command::Run => unreachable!("`run` is handled by main"),
+        Command::Migrate { dry_run } => migrate::execute(settings, dry_run).await,
+        Command::Rescan { chain, from, to, address, wait } => rescan::execute(settings, &chain, from, to, address, wait).await,
+        Command::Address(command) => address::execute(settings, command).await,
+        Command::Cursor(command) => cursor::execute(settings, command).await,
+        Command::Deposits(command) => deposits::execute(settings, command).await,
+        Command::Config(command) => config::execute(settings, command),
+    }
+}
+
+/// 스캐너와 같은 저장소 열기 - memory_db는 프로세스마다 비어 있으므로 CLI에서는 사용할 수 없다
+async fn open_repository(settings: &Settings) -> Result<Arc<RepositoryWrapper>, AppError> {
+    if settings.repository.memory_db {
+        return Err(AppError::Config("This command requires PostgreSQL (memory_db = false)".to_string()));
+    }
+    Ok(Arc::new(RepositoryWrapper::from_settings(settings).await?))
+}
+
+/// 결과를 stdout에 JSON으로 출력 (로그는 stderr)
+fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<(), AppError> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| AppError::Initialization(format!("Failed to serialize output: {}", e)))?;
+    println!("{}", json);
+    Ok(())
+}
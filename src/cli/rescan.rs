@@ -0,0 +1,56 @@
+use crate::config::Settings;
+use crate::fetcher::factory::build_fetcher;
+use crate::respository::Repository;
+use crate::tasks::{create_backfill_job, BackfillRequest};
+use crate::types::{AppError, BackfillStatus};
+use log::info;
+use tokio::time::{sleep, Duration};
+
+use super::{open_repository, print_json};
+
+// --wait 진행 상황 확인 주기
+const PROGRESS_POLL_INTERVAL_SECS: u64 = 2;
+
+/// `xscanner rescan --chain --from [--to]` - backfill 작업 등록
+///
+/// 작업은 실행 중인 스캐너의 BackfillScheduler가 poll_interval_secs 안에 가져가 실행한다.
+pub async fn execute(
+    settings: &Settings,
+    chain: &str,
+    from_block: u64,
+    to_block: Option<u64>,
+    address: Option<String>,
+    wait: bool,
+) -> Result<(), AppError> {
+    let chain_name = chain.to_uppercase();
+    let known = settings.get_chain_configs().iter()
+        .filter_map(|(name, chain_config)| build_fetcher(name, chain_config))
+        .any(|fetcher| fetcher.chain_name().eq_ignore_ascii_case(&chain_name));
+    if !known {
+        return Err(AppError::Config(format!("Unknown chain: {}", chain)));
+    }
+
+    let repository = open_repository(settings).await?;
+    let request = BackfillRequest { chain_name, from_block, to_block, address };
+    let job = create_backfill_job(&repository, request).await.map_err(AppError::Config)?;
+    if !wait {
+        return print_json(&job);
+    }
+
+    info!("Waiting for the scanner to run backfill job {}", job.id);
+    let job = loop {
+        sleep(Duration::from_secs(PROGRESS_POLL_INTERVAL_SECS)).await;
+        let Some(current) = repository.get_backfill_job(job.id).await? else {
+            return Err(AppError::Config(format!("Backfill job {} disappeared", job.id)));
+        };
+        if !current.status.is_unfinished() {
+            break current;
+        }
+    };
+
+    print_json(&job)?;
+    match job.status {
+        BackfillStatus::Failed => Err(AppError::Block(job.error.unwrap_or_else(|| "backfill failed".to_string()))),
+        _ => Ok(()),
+    }
+}
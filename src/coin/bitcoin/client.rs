@@ -28,10 +28,24 @@ impl BitcoinClient {
   }
   
   pub async fn fetch_block_by_number(&self, block_number: u64) -> Result<BitcoinBlock, reqwest::Error> {
+    let _timer = crate::metrics::rpc_timer("bitcoin", "block");
     let url = format!("{}{}", self.api_url, block_number);
     self.fetch_json_url(&url).await
   }
   
+  /// 최신 블록 높이 조회 (blockchain.info /latestblock)
+  pub async fn fetch_tip_height(&self) -> Result<u64, Box<dyn std::error::Error>> {
+    let _timer = crate::metrics::rpc_timer("bitcoin", "latestblock");
+    #[derive(Deserialize)]
+    struct LatestBlock {
+      height: u64,
+    }
+
+    let url = reqwest::Url::parse(&self.api_url)?.join("/latestblock")?;
+    let latest: LatestBlock = self.fetch_json_url(url.as_str()).await?;
+    Ok(latest.height)
+  }
+
   async fn fetch_json_url<T>(&self, url: &str) -> Result<T, reqwest::Error>
   where
     T: DeserializeOwned,
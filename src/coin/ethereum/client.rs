@@ -8,12 +8,15 @@ use reqwest;
 use serde::Deserialize;
 use log::info;
 use crate::coin::coin_trait::BlockchainClient;
-use crate::coin::ethereum::model::EthereumBlock;
+use crate::coin::ethereum::model::{EthereumBlock, EthereumError, EthereumLogs, EthereumReceipt, EthereumReceipts, LogResult, ReceiptResult};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::json;
 use std::io::{Error as IoError, ErrorKind};
 
+/// keccak256("Transfer(address,address,uint256)")
+pub const ERC20_TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
 #[derive(Clone)]
 pub struct EthereumClient {
   client: Client,
@@ -29,6 +32,7 @@ impl EthereumClient {
   }
   
   pub async fn fetch_block_by_number(&self, block_number: u64) -> Result<EthereumBlock, Box<dyn std::error::Error>> {
+    let _timer = crate::metrics::rpc_timer("ethereum", "eth_getBlockByNumber");
     let block_number_hex = format!("0x{:X}", block_number);
     let payload = json!({
             "jsonrpc": "2.0",
@@ -55,6 +59,169 @@ impl EthereumClient {
 
     Ok(block)
   }
+
+  /// 블록 해시만 조회 (트랜잭션 본문 없이 eth_getBlockByNumber). 블록이 없으면 None
+  pub async fn fetch_block_hash(&self, block_number: u64) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let _timer = crate::metrics::rpc_timer("ethereum", "eth_getBlockByNumber");
+    let block_number_hex = format!("0x{:X}", block_number);
+    let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "eth_getBlockByNumber",
+            "params": [block_number_hex, false],
+            "id": 1
+        });
+
+    #[derive(Deserialize)]
+    struct BlockHeader {
+      hash: String,
+    }
+
+    #[derive(Deserialize)]
+    struct RpcResponse {
+      result: Option<BlockHeader>,
+      error: Option<EthereumError>,
+    }
+
+    let response: RpcResponse = self.client
+      .post(&self.api_url)
+      .json(&payload)
+      .send()
+      .await?
+      .json()
+      .await?;
+
+    if let Some(error) = response.error {
+      return Err(format!("eth_getBlockByNumber failed: {} (code {})", error.message, error.code).into());
+    }
+
+    Ok(response.result.map(|header| header.hash))
+  }
+
+  /// 최신 블록 번호 조회 (eth_blockNumber)
+  pub async fn fetch_latest_block_number(&self) -> Result<u64, Box<dyn std::error::Error>> {
+    let _timer = crate::metrics::rpc_timer("ethereum", "eth_blockNumber");
+    let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "eth_blockNumber",
+            "params": [],
+            "id": 1
+        });
+
+    #[derive(Deserialize)]
+    struct RpcResponse {
+      result: Option<String>,
+    }
+
+    let response: RpcResponse = self.client
+      .post(&self.api_url)
+      .json(&payload)
+      .send()
+      .await?
+      .json()
+      .await?;
+
+    let number_hex = response.result.ok_or("eth_blockNumber returned no result")?;
+    Ok(u64::from_str_radix(number_hex.trim_start_matches("0x"), 16)?)
+  }
+
+  /// ERC-20 Transfer(address,address,uint256) 로그 조회 (eth_getLogs)
+  pub async fn fetch_transfer_logs(&self, block_number: u64) -> Result<Vec<LogResult>, Box<dyn std::error::Error>> {
+    let _timer = crate::metrics::rpc_timer("ethereum", "eth_getLogs");
+    let block_number_hex = format!("0x{:X}", block_number);
+    let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "eth_getLogs",
+            "params": [{
+                "fromBlock": block_number_hex,
+                "toBlock": block_number_hex,
+                "topics": [ERC20_TRANSFER_TOPIC]
+            }],
+            "id": 1
+        });
+
+    let response = self.client
+      .post(&self.api_url)
+      .json(&payload)
+      .send()
+      .await?;
+
+    let response_text = response.text().await?;
+    log::debug!("[ETH] Raw eth_getLogs response: {}", response_text);
+
+    let logs: EthereumLogs = serde_json::from_str(&response_text)?;
+
+    if let Some(error) = logs.error {
+      return Err(format!("eth_getLogs failed: {} (code {})", error.message, error.code).into());
+    }
+
+    Ok(logs.result.unwrap_or_default())
+  }
+
+  /// 블록 내 모든 트랜잭션의 receipt 조회 (eth_getBlockReceipts)
+  ///
+  /// 노드가 eth_getBlockReceipts를 지원하지 않으면 None
+  pub async fn fetch_block_receipts(&self, block_number: u64) -> Result<Option<Vec<ReceiptResult>>, Box<dyn std::error::Error>> {
+    let _timer = crate::metrics::rpc_timer("ethereum", "eth_getBlockReceipts");
+    let block_number_hex = format!("0x{:X}", block_number);
+    let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "eth_getBlockReceipts",
+            "params": [block_number_hex],
+            "id": 1
+        });
+
+    let response = self.client
+      .post(&self.api_url)
+      .json(&payload)
+      .send()
+      .await?;
+
+    let response_text = response.text().await?;
+    log::debug!("[ETH] Raw eth_getBlockReceipts response: {}", response_text);
+
+    let receipts: EthereumReceipts = serde_json::from_str(&response_text)?;
+
+    if let Some(error) = receipts.error {
+      if is_method_unsupported(&error) {
+        return Ok(None);
+      }
+      return Err(format!("eth_getBlockReceipts failed: {} (code {})", error.message, error.code).into());
+    }
+
+    Ok(Some(receipts.result.unwrap_or_default()))
+  }
+
+  /// 트랜잭션 하나의 receipt 조회 (eth_getTransactionReceipt). 아직 receipt가 없으면 None
+  pub async fn fetch_transaction_receipt(&self, tx_hash: &str) -> Result<Option<ReceiptResult>, Box<dyn std::error::Error>> {
+    let _timer = crate::metrics::rpc_timer("ethereum", "eth_getTransactionReceipt");
+    let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "eth_getTransactionReceipt",
+            "params": [tx_hash],
+            "id": 1
+        });
+
+    let receipt: EthereumReceipt = self.client
+      .post(&self.api_url)
+      .json(&payload)
+      .send()
+      .await?
+      .json()
+      .await?;
+
+    if let Some(error) = receipt.error {
+      return Err(format!("eth_getTransactionReceipt failed: {} (code {})", error.message, error.code).into());
+    }
+
+    Ok(receipt.result)
+  }
+}
+
+/// 노드가 메서드를 지원하지 않는다는 JSON-RPC 에러인지 (-32601 또는 프로바이더별 메시지)
+fn is_method_unsupported(error: &EthereumError) -> bool {
+  let message = error.message.to_lowercase();
+  error.code == -32601
+    || ["not supported", "not available", "does not exist", "method not found"].iter().any(|m| message.contains(m))
 }
 
 #[async_trait]
@@ -75,5 +75,57 @@ pub struct EthereumBlock {
   pub id: usize,
   pub result: Option<EthereumResult>,
   pub error: Option<EthereumError>,
+  // eth_getLogs로 별도 조회한 ERC-20 Transfer 로그 (조회하지 않았으면 None)
+  #[serde(skip)]
+  pub logs: Option<Vec<LogResult>>,
+  // eth_getBlockReceipts로 별도 조회한 트랜잭션 실행 결과 (조회하지 않았으면 None)
+  #[serde(skip)]
+  pub receipts: Option<Vec<ReceiptResult>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EthereumLogs {
+  pub jsonrpc: String,
+  pub id: usize,
+  pub result: Option<Vec<LogResult>>,
+  pub error: Option<EthereumError>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogResult {
+  pub address: String, // 로그를 발생시킨 컨트랙트 (토큰 컨트랙트)
+  pub topics: Vec<String>,
+  pub data: String,
+  pub block_number: Option<String>,
+  pub transaction_hash: Option<String>,
+  pub log_index: Option<String>,
+  pub removed: Option<bool>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EthereumReceipts {
+  pub jsonrpc: String,
+  pub id: usize,
+  pub result: Option<Vec<ReceiptResult>>,
+  pub error: Option<EthereumError>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EthereumReceipt {
+  pub jsonrpc: String,
+  pub id: usize,
+  pub result: Option<ReceiptResult>,
+  pub error: Option<EthereumError>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptResult {
+  pub transaction_hash: String,
+  pub status: Option<String>, // "0x1" 성공, "0x0" 실패 (Byzantium 이전 블록은 없음)
 }
 
@@ -1,7 +1,7 @@
 use reqwest::Client;
 use serde_json::json;
 use crate::coin::coin_trait::BlockchainClient;
-use crate::coin::icon::model::IconBlock;
+use crate::coin::icon::model::{IconBlock, IconTransactionResult};
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::io::{Error as IoError, ErrorKind};
@@ -21,6 +21,7 @@ impl IconClient {
     }
     
     pub async fn fetch_block_by_number(&self, block_number: u64) -> Result<IconBlock, Box<dyn std::error::Error>> {
+        let _timer = crate::metrics::rpc_timer("icon", "icx_getBlockByHeight");
         // ICON RPC: icx_getBlockByHeight
         let payload = json!({
             "jsonrpc": "2.0",
@@ -50,6 +51,72 @@ impl IconClient {
             None => Err(Box::new(IoError::new(ErrorKind::Other, "ICON RPC returned no result")))
         }
     }
+
+    pub async fn fetch_latest_block_number(&self) -> Result<u64, Box<dyn std::error::Error>> {
+        let _timer = crate::metrics::rpc_timer("icon", "icx_getLastBlock");
+        // ICON RPC: icx_getLastBlock
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "icx_getLastBlock"
+        });
+
+        #[derive(Deserialize)]
+        struct LastBlock {
+            height: u64,
+        }
+
+        #[derive(Deserialize)]
+        struct RpcResponse {
+            result: Option<LastBlock>,
+        }
+
+        let response: RpcResponse = self.client
+            .post(&self.api_url)
+            .header("Content-Type", "application/json")
+            .json(&payload)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match response.result {
+            Some(block) => Ok(block.height),
+            None => Err(Box::new(IoError::other("ICON RPC returned no result")))
+        }
+    }
+
+    pub async fn fetch_transaction_result(&self, tx_hash: &str) -> Result<IconTransactionResult, Box<dyn std::error::Error>> {
+        let _timer = crate::metrics::rpc_timer("icon", "icx_getTransactionResult");
+        // ICON RPC: icx_getTransactionResult
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "icx_getTransactionResult",
+            "params": {
+                "txHash": tx_hash
+            }
+        });
+
+        #[derive(Deserialize)]
+        struct RpcResponse {
+            result: Option<IconTransactionResult>,
+        }
+
+        let response: RpcResponse = self.client
+            .post(&self.api_url)
+            .header("Content-Type", "application/json")
+            .json(&payload)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        match response.result {
+            Some(result) => Ok(result),
+            None => Err(Box::new(IoError::other("ICON RPC returned no transaction result")))
+        }
+    }
 }
 
 #[async_trait]
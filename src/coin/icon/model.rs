@@ -31,3 +31,28 @@ pub struct IconTransaction {
     pub data_type: Option<String>,
     pub data: Option<String>,
 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct IconTransactionResult {
+    pub status: String, // "0x1" 성공, "0x0" 실패
+    pub failure: Option<IconFailure>,
+}
+
+impl IconTransactionResult {
+    /// status가 0x1이 아니면 실패 사유
+    pub fn failure_reason(&self) -> Option<String> {
+        if self.status == "0x1" {
+            return None;
+        }
+        Some(match &self.failure {
+            Some(f) => format!("status {} ({}: {})", self.status, f.code, f.message),
+            None => format!("status {}", self.status),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct IconFailure {
+    pub code: String,
+    pub message: String,
+}
@@ -21,6 +21,7 @@ impl ThetaClient {
     }
     
     pub async fn fetch_block_by_number(&self, block_number: u64) -> Result<ThetaBlock, Box<dyn std::error::Error>> {
+        let _timer = crate::metrics::rpc_timer("theta", "eth_getBlockByNumber");
         // THETA RPC: eth_getBlockByNumber
         let block_number_hex = format!("0x{:X}", block_number);
         let payload = json!({
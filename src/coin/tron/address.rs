@@ -0,0 +1,81 @@
+/// TRON 메인넷 주소 prefix (0x41)
+const ADDRESS_PREFIX: u8 = 0x41;
+
+/// TRON hex 주소("41" + 20바이트 또는 20바이트)를 base58check 주소(T...)로 변환
+/// 이미 base58 주소이면 그대로 반환
+pub fn to_base58_address(address: &str) -> Option<String> {
+    if address.starts_with('T') {
+        return Some(address.to_string());
+    }
+
+    let hex_str = address.strip_prefix("0x").unwrap_or(address);
+    let mut bytes = hex::decode(hex_str).ok()?;
+    match bytes.len() {
+        21 if bytes[0] == ADDRESS_PREFIX => {}
+        20 => bytes.insert(0, ADDRESS_PREFIX),
+        _ => return None,
+    }
+
+    Some(bs58::encode(bytes).with_check().into_string())
+}
+
+/// TRC-10 asset_name 디코딩
+/// getblockbynum 응답은 hex 인코딩된 토큰 ID("31303030303031" -> "1000001")를 준다
+pub fn decode_asset_name(asset_name: &str) -> String {
+    hex::decode(asset_name)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_graphic()))
+        .unwrap_or_else(|| asset_name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // USDT TRC-20 컨트랙트
+    const USDT_HEX: &str = "41a614f803b6fd780986a42c78ec9c7f77e6ded13c";
+    const USDT_BASE58: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+
+    #[test]
+    fn converts_prefixed_hex_address() {
+        assert_eq!(to_base58_address(USDT_HEX).as_deref(), Some(USDT_BASE58));
+        assert_eq!(to_base58_address(&format!("0x{}", USDT_HEX)).as_deref(), Some(USDT_BASE58));
+    }
+
+    #[test]
+    fn adds_prefix_to_20_byte_address() {
+        // TRC-20 로그 topic은 0x41 없이 20바이트만 담는다
+        assert_eq!(to_base58_address(&USDT_HEX[2..]).as_deref(), Some(USDT_BASE58));
+    }
+
+    #[test]
+    fn keeps_base58_address() {
+        assert_eq!(to_base58_address(USDT_BASE58).as_deref(), Some(USDT_BASE58));
+    }
+
+    #[test]
+    fn rejects_invalid_hex_address() {
+        assert_eq!(to_base58_address(""), None);
+        assert_eq!(to_base58_address("41a614f8"), None);
+        assert_eq!(to_base58_address("zz"), None);
+        // 21바이트인데 prefix가 0x41이 아님
+        assert_eq!(to_base58_address(&format!("42{}", &USDT_HEX[2..])), None);
+    }
+
+    #[test]
+    fn decodes_hex_asset_name() {
+        assert_eq!(decode_asset_name("31303030303031"), "1000001");
+    }
+
+    #[test]
+    fn keeps_undecodable_asset_name() {
+        // 이미 디코딩된 ID
+        assert_eq!(decode_asset_name("1000001"), "1000001");
+        assert_eq!(decode_asset_name(""), "");
+        // 제어 문자로 디코딩되는 hex
+        assert_eq!(decode_asset_name("0001"), "0001");
+        // UTF-8이 아닌 바이트
+        assert_eq!(decode_asset_name("ff"), "ff");
+    }
+}
@@ -19,6 +19,7 @@ impl TronClient {
     }
     
     pub async fn fetch_block_by_number(&self, block_number: u64) -> Result<TronBlock, reqwest::Error> {
+        let _timer = crate::metrics::rpc_timer("tron", "getblockbynum");
         // TRON API: /wallet/getblockbynum
         let url = format!("{}/wallet/getblockbynum", self.api_url);
         let payload = json!({
@@ -36,6 +37,22 @@ impl TronClient {
         
         Ok(response)
     }
+
+    pub async fn fetch_latest_block_number(&self) -> Result<u64, reqwest::Error> {
+        let _timer = crate::metrics::rpc_timer("tron", "getnowblock");
+        // TRON API: /wallet/getnowblock
+        let url = format!("{}/wallet/getnowblock", self.api_url);
+
+        let response = self.client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .send()
+            .await?
+            .json::<TronBlock>()
+            .await?;
+
+        Ok(response.block_header.raw_data.number)
+    }
 }
 
 #[async_trait]
@@ -1,2 +1,3 @@
+pub mod address;
 pub mod client;
 pub mod model;
@@ -72,5 +72,6 @@ pub struct TronContractValue {
     pub owner_address: Option<String>,
     pub to_address: Option<String>,
     pub asset_name: Option<String>,
+    pub contract_address: Option<String>, // TriggerSmartContract 대상 컨트랙트
     pub data: Option<String>,
 }
@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -12,6 +13,16 @@ pub struct Settings {
   pub customer_sync: Option<CustomerSyncSettings>,
   #[serde(default)]
   pub confirmation_checker: Option<ConfirmationCheckerSettings>,
+  #[serde(default)]
+  pub api: Option<ApiSettings>,
+  #[serde(default)]
+  pub monitoring: Option<MonitoringSettings>,
+  #[serde(default)]
+  pub backfill: BackfillSettings,
+  #[serde(default)]
+  pub shutdown: ShutdownSettings,
+  #[serde(default)]
+  pub tokens: Vec<TokenConfig>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -36,16 +47,116 @@ pub struct ChainConfig {
   pub interval_secs: u64,
   #[serde(default = "default_required_confirmations")]
   pub required_confirmations: u64,
+  #[serde(default = "default_reorg_depth")]
+  pub reorg_depth: u64, // 추적할 최근 블록 해시 개수 (0이면 reorg 감지 비활성화)
+  #[serde(default = "default_catchup_concurrency")]
+  pub catchup_concurrency: usize, // 밀린 블록 동시 조회 수 (1이면 catch-up 비활성화)
+  #[serde(default = "default_catchup_window")]
+  pub catchup_window: u64, // catch-up 시 한 번에 가져올 최대 블록 수
+  #[serde(default = "default_analyzer_queue_size")]
+  pub analyzer_queue_size: usize, // 이 체인의 analyzer 채널 크기 (가득 차면 fetcher가 대기)
   #[serde(default)]
   pub rpc_method: Option<String>, // "eth_getBlockByNumber", "getblock" ?
   #[serde(default)]
   pub rpc_params_format: Option<String>, // "hex", "decimal", "string"
 }
 
+/// 입금 자산 설정 (`[[tokens]]`)
+/// `contract`가 없으면 해당 체인의 native coin 설정
+#[derive(Debug, Deserialize, Clone)]
+pub struct TokenConfig {
+  pub chain: String, // [blockchain.<name>] 의 name (대소문자 무시)
+  #[serde(default)]
+  pub contract: Option<String>, // 토큰 컨트랙트 주소 또는 asset id
+  pub symbol: String,
+  pub decimals: u32,
+  #[serde(default)]
+  pub min_deposit: Option<Decimal>, // 이 금액 미만 입금은 무시 (dust/spam)
+  #[serde(default = "default_token_enabled")]
+  pub enabled: bool,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct NotificationSettings {
-  pub sqs_queue_url: String,
+  // 기존 단일 SQS 설정 (sinks와 함께 쓰면 SQS sink가 하나 더 추가됨)
+  #[serde(default)]
+  pub sqs_queue_url: Option<String>,
+  #[serde(default = "default_aws_region")]
   pub aws_region: String,
+  #[serde(default)]
+  pub sinks: Vec<NotificationSinkSettings>,
+  // envelope의 scanner_instance (기본: HOSTNAME 환경 변수)
+  #[serde(default)]
+  pub instance_id: Option<String>,
+  #[serde(default)]
+  pub signing: Option<SigningSettings>,
+  #[serde(default = "default_outbox_poll_interval_secs")]
+  pub outbox_poll_interval_secs: u64,
+  #[serde(default = "default_batch_size")]
+  pub outbox_batch_size: usize,
+  #[serde(default = "default_outbox_retry_base_delay_secs")]
+  pub outbox_retry_base_delay_secs: u64,
+  #[serde(default = "default_outbox_max_retry_delay_secs")]
+  pub outbox_max_retry_delay_secs: u64,
+  // 이 횟수만큼 실패한 알림은 dead로 표시하고 더 이상 전송하지 않음
+  #[serde(default = "default_outbox_max_attempts")]
+  pub outbox_max_attempts: u32,
+}
+
+/// 입금 알림 sink (`[[notification.sinks]]`, type으로 구분)
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum NotificationSinkSettings {
+  Sqs {
+    queue_url: String,
+    #[serde(default = "default_aws_region")]
+    aws_region: String,
+  },
+  Webhook {
+    url: String,
+    secret: String, // HMAC-SHA256 서명 키
+    #[serde(default = "default_sink_timeout_secs")]
+    timeout_secs: u64,
+  },
+  Kafka {
+    brokers: String, // e.g., "localhost:9092,localhost:9093"
+    topic: String,
+    #[serde(default = "default_sink_timeout_secs")]
+    timeout_secs: u64,
+  },
+  File {
+    path: String, // JSONL
+  },
+}
+
+/// envelope 서명 설정 (`[notification.signing]`)
+#[derive(Debug, Deserialize, Clone)]
+pub struct SigningSettings {
+  pub algorithm: String, // "hmac-sha256" | "ed25519"
+  pub key: String, // hmac-sha256: secret, ed25519: 32바이트 seed (hex)
+  #[serde(default)]
+  pub key_id: Option<String>, // 키 교체 시 consumer가 검증 키를 고를 수 있도록
+}
+
+impl NotificationSettings {
+  pub fn scanner_instance(&self) -> String {
+    self.instance_id.clone()
+      .or_else(|| std::env::var("HOSTNAME").ok())
+      .unwrap_or_else(|| "xscanner".to_string())
+  }
+
+  /// 설정된 모든 sink (기존 sqs_queue_url 포함)
+  pub fn all_sinks(&self) -> Vec<NotificationSinkSettings> {
+    let mut sinks = Vec::new();
+    if let Some(queue_url) = &self.sqs_queue_url {
+      sinks.push(NotificationSinkSettings::Sqs {
+        queue_url: queue_url.clone(),
+        aws_region: self.aws_region.clone(),
+      });
+    }
+    sinks.extend(self.sinks.iter().cloned());
+    sinks
+  }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -59,6 +170,16 @@ pub struct CustomerSyncSettings {
   pub flush_interval_secs: u64,
   #[serde(default)]
   pub cache_file_path: Option<String>,
+  #[serde(default = "default_visibility_timeout_secs")]
+  pub visibility_timeout_secs: u64,
+  #[serde(default = "default_max_receive_count")]
+  pub max_receive_count: u32,
+  #[serde(default = "default_retry_delay_secs")]
+  pub retry_delay_secs: u64,
+  #[serde(default)]
+  pub dlq_url: Option<String>,
+  #[serde(default)]
+  pub poison_queue_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -69,6 +190,93 @@ pub struct ConfirmationCheckerSettings {
   pub check_interval_secs: u64,
 }
 
+/// 조회/관리용 HTTP API (`[api]`)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ApiSettings {
+  #[serde(default = "default_api_listen_addr")]
+  pub listen_addr: String,
+  /// 조회 API Bearer 토큰
+  pub auth_token: String,
+  /// 관리 API(주소 추가/삭제, 재스캔) Bearer 토큰 - 없으면 관리 API 비활성화
+  #[serde(default)]
+  pub admin_token: Option<String>,
+  /// backfill 요청 하나에 허용하는 최대 블록 수
+  #[serde(default = "default_max_backfill_blocks")]
+  pub max_backfill_blocks: u64,
+}
+
+/// `/metrics`, `/healthz`, `/readyz` 서버 (`[monitoring]`, 인증 없음 - 내부 네트워크에서만 노출)
+#[derive(Debug, Deserialize, Clone)]
+pub struct MonitoringSettings {
+  #[serde(default = "default_monitoring_listen_addr")]
+  pub listen_addr: String,
+  /// 관리 대상 주소 수(xscanner_monitored_addresses) 갱신 주기
+  #[serde(default = "default_address_metrics_interval_secs")]
+  pub address_metrics_interval_secs: u64,
+  /// last_processed_block이 interval_secs * 이 값 동안 바뀌지 않으면 /readyz 실패
+  #[serde(default = "default_stall_threshold_multiplier")]
+  pub stall_threshold_multiplier: u64,
+  /// /readyz 컴포넌트별 연결 확인 timeout
+  #[serde(default = "default_health_check_timeout_secs")]
+  pub health_check_timeout_secs: u64,
+}
+
+/// 블록 구간 backfill 실행 설정 (`[backfill]`, 생략 시 기본값)
+#[derive(Debug, Deserialize, Clone)]
+pub struct BackfillSettings {
+  /// 체인당 초당 조회할 최대 블록 수 (live fetcher와 별개)
+  #[serde(default = "default_backfill_blocks_per_second")]
+  pub blocks_per_second: f64,
+  /// 다른 프로세스(`xscanner rescan`)가 등록한 작업 확인 주기
+  #[serde(default = "default_backfill_poll_interval_secs")]
+  pub poll_interval_secs: u64,
+  /// 진행 위치(next_block) 저장 주기 (블록 수)
+  #[serde(default = "default_backfill_checkpoint_blocks")]
+  pub checkpoint_blocks: u64,
+}
+
+impl Default for BackfillSettings {
+  fn default() -> Self {
+    Self {
+      blocks_per_second: default_backfill_blocks_per_second(),
+      poll_interval_secs: default_backfill_poll_interval_secs(),
+      checkpoint_blocks: default_backfill_checkpoint_blocks(),
+    }
+  }
+}
+
+fn default_backfill_blocks_per_second() -> f64 {
+  5.0
+}
+
+fn default_backfill_poll_interval_secs() -> u64 {
+  10
+}
+
+fn default_backfill_checkpoint_blocks() -> u64 {
+  10
+}
+
+/// 종료 설정 (`[shutdown]`, 생략 시 기본값)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ShutdownSettings {
+  /// 종료 신호 후 진행 중인 블록 commit, 주소 버퍼 flush, 알림 전송을 기다리는 최대 시간
+  #[serde(default = "default_shutdown_timeout_secs")]
+  pub timeout_secs: u64,
+}
+
+impl Default for ShutdownSettings {
+  fn default() -> Self {
+    Self {
+      timeout_secs: default_shutdown_timeout_secs(),
+    }
+  }
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+  30
+}
+
 fn default_aws_region() -> String {
   "ap-northeast-2".to_string()
 }
@@ -81,10 +289,62 @@ fn default_flush_interval_secs() -> u64 {
   5
 }
 
+fn default_sink_timeout_secs() -> u64 {
+  10
+}
+
+fn default_outbox_poll_interval_secs() -> u64 {
+  5
+}
+
+fn default_outbox_retry_base_delay_secs() -> u64 {
+  5
+}
+
+fn default_outbox_max_retry_delay_secs() -> u64 {
+  600
+}
+
+fn default_outbox_max_attempts() -> u32 {
+  20
+}
+
+fn default_visibility_timeout_secs() -> u64 {
+  60
+}
+
+fn default_max_receive_count() -> u32 {
+  5
+}
+
+fn default_retry_delay_secs() -> u64 {
+  30
+}
+
 fn default_required_confirmations() -> u64 {
   12 // Default to Ethereum's 12 confirmations
 }
 
+fn default_reorg_depth() -> u64 {
+  64
+}
+
+fn default_catchup_concurrency() -> usize {
+  4
+}
+
+fn default_catchup_window() -> u64 {
+  32
+}
+
+fn default_analyzer_queue_size() -> usize {
+  128
+}
+
+fn default_token_enabled() -> bool {
+  true
+}
+
 fn default_confirmation_checker_enabled() -> bool {
   true
 }
@@ -93,26 +353,83 @@ fn default_check_interval_secs() -> u64 {
   30
 }
 
+fn default_api_listen_addr() -> String {
+  "0.0.0.0:8080".to_string()
+}
+
+fn default_max_backfill_blocks() -> u64 {
+  10_000
+}
+
+fn default_monitoring_listen_addr() -> String {
+  "0.0.0.0:9100".to_string()
+}
+
+fn default_address_metrics_interval_secs() -> u64 {
+  60
+}
+
+fn default_stall_threshold_multiplier() -> u64 {
+  20
+}
+
+fn default_health_check_timeout_secs() -> u64 {
+  5
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct RepositorySettings {
   #[serde(default = "default_memory_db")]
   pub memory_db: bool,
   pub postgresql_url: String,
+  /// 설정하면 입금 / 진행 위치 / outbox / backfill 작업을 PostgreSQL 대신 이 경로의 RocksDB에 저장 (memory_db = true면 무시)
+  #[serde(default)]
+  pub rocksdb_path: Option<String>,
   pub leveldb_path: String,
   pub customer_address_file: String,
+  /// 관리 대상 주소 인덱스 백엔드 ("rocksdb" | "postgres"), memory_db = true면 무시하고 메모리 사용
+  #[serde(default = "default_address_index")]
+  pub address_index: String,
+  /// RocksDB 주소 인덱스 앞에 Bloom filter 사용 (시작 시 RocksDB 캐시로 생성)
+  #[serde(default)]
+  pub address_bloom_filter: bool,
+  /// Bloom filter 예상 주소 수 (초과하면 false positive 비율 증가)
+  #[serde(default = "default_bloom_capacity")]
+  pub bloom_capacity: usize,
+  #[serde(default = "default_bloom_false_positive_rate")]
+  pub bloom_false_positive_rate: f64,
+  /// 시작 시 스키마 마이그레이션 자동 적용 (false면 `xscanner migrate`로 먼저 적용해야 시작됨)
+  #[serde(default = "default_auto_migrate")]
+  pub auto_migrate: bool,
 }
 
 fn default_memory_db() -> bool {
   false
 }
 
+fn default_auto_migrate() -> bool {
+  true
+}
+
+fn default_address_index() -> String {
+  "rocksdb".to_string()
+}
+
+fn default_bloom_capacity() -> usize {
+  1_000_000
+}
+
+fn default_bloom_false_positive_rate() -> f64 {
+  0.001
+}
+
 impl Settings {
-  pub fn new() -> Result<Self, config::ConfigError> {
-    let default_config_path = "./config.toml";
+  /// 설정 파일 로드 (`--config`, 기본 ./config.toml) - 환경변수(APP_*)가 파일 값을 덮어쓴다
+  pub fn from_path(config_path: &str) -> Result<Self, config::ConfigError> {
     let env_prefix = "APP"; // Environment variable prefix (e.g., APP_BLOCKCHAIN__ETHEREUM__API=...)
     
     let builder = config::Config::builder()
-      .add_source(config::File::with_name(default_config_path).required(true))
+      .add_source(config::File::with_name(config_path).required(true))
       // E.g., `APP_BLOCKCHAIN__ETHEREUM__API=http://...` would override config file value
       .add_source(config::Environment::with_prefix(env_prefix).separator("__"))
       ;
@@ -23,6 +23,12 @@ impl BlockFetcher for BitcoinFetcher {
     let block = self.client.fetch_block_by_number(block_number).await?;
     Ok(BlockData::Bitcoin(block))
   }
+
+  async fn fetch_latest_block_number(&self) -> Result<Option<u64>, AppError> {
+    let tip = self.client.fetch_tip_height().await
+      .map_err(|e| AppError::Client(format!("Failed to fetch BTC tip height: {}", e)))?;
+    Ok(Some(tip))
+  }
   
   // fn extract_block_number(&self, block: &BlockData) -> Result<u64, AppError> {
   //   if let BlockData::Bitcoin(btc_block) = block {
@@ -10,19 +10,67 @@ use crate::types::{BlockData, AppError};
 use crate::fetcher::fetcher::BlockFetcher;
 
 use async_trait::async_trait;
+use log::warn;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 pub struct EthereumFetcher {
   pub client: Arc<EthereumClient>,
+  // 노드가 eth_getBlockReceipts를 지원하지 않으면 false (이후 블록은 receipt 없이 조회)
+  block_receipts_supported: AtomicBool,
+}
+
+impl EthereumFetcher {
+  pub fn new(client: Arc<EthereumClient>) -> Self {
+    Self { client, block_receipts_supported: AtomicBool::new(true) }
+  }
 }
 
 #[async_trait]
 impl BlockFetcher for EthereumFetcher {
   async fn fetch_block(&self, block_number: u64) -> Result<BlockData, AppError> {
-    let block = self.client.fetch_block_by_number(block_number).await
+    let mut block = self.client.fetch_block_by_number(block_number).await
       .map_err(|e| AppError::Client(format!("Failed to fetch ETH block: {}", e)))?;
+
+    // ERC-20 입금 감지를 위해 같은 블록의 Transfer 로그도 함께 조회
+    let logs = self.client.fetch_transfer_logs(block_number).await
+      .map_err(|e| AppError::Client(format!("Failed to fetch ETH transfer logs: {}", e)))?;
+    block.logs = Some(logs);
+
+    // 실패한(revert) 트랜잭션을 입금에서 제외하기 위해 receipt status 조회
+    // 가져오지 못하면 receipts = None으로 두고, Analyzer가 관리 주소로 온 트랜잭션만 따로 확인한다
+    if self.block_receipts_supported.load(Ordering::Relaxed) {
+      match self.client.fetch_block_receipts(block_number).await {
+        Ok(Some(receipts)) => block.receipts = Some(receipts),
+        Ok(None) => {
+          warn!("[SEPOLIA] eth_getBlockReceipts is not supported by the node, falling back to per-transaction receipts");
+          self.block_receipts_supported.store(false, Ordering::Relaxed);
+        }
+        Err(e) => warn!("[SEPOLIA] Failed to fetch receipts for block {}, falling back to per-transaction receipts: {}", block_number, e),
+      }
+    }
+
     Ok(BlockData::Ethereum(block))
   }
+
+  async fn fetch_latest_block_number(&self) -> Result<Option<u64>, AppError> {
+    let latest = self.client.fetch_latest_block_number().await
+      .map_err(|e| AppError::Client(format!("Failed to fetch ETH block number: {}", e)))?;
+    Ok(Some(latest))
+  }
+
+  async fn fetch_block_hash(&self, block_number: u64) -> Result<Option<String>, AppError> {
+    self.client.fetch_block_hash(block_number).await
+      .map_err(|e| AppError::Client(format!("Failed to fetch ETH block hash: {}", e)))
+  }
+
+  async fn fetch_transaction_failure(&self, tx_hash: &str) -> Result<Option<String>, AppError> {
+    let receipt = self.client.fetch_transaction_receipt(tx_hash).await
+      .map_err(|e| AppError::Client(format!("Failed to fetch ETH receipt {}: {}", tx_hash, e)))?;
+    Ok(receipt
+      .filter(|r| r.status.as_deref() == Some("0x0"))
+      .map(|_| "transaction reverted (receipt status 0x0)".to_string()))
+  }
   
   // fn extract_block_number(&self, block: &BlockData) -> Result<u64, AppError> {
   //   if let BlockData::Ethereum(eth_block) = block {
@@ -0,0 +1,42 @@
+use crate::coin::bitcoin::client::BitcoinClient;
+use crate::coin::ethereum::client::EthereumClient;
+use crate::coin::icon::client::IconClient;
+use crate::coin::theta::client::ThetaClient;
+use crate::coin::tron::client::TronClient;
+use crate::config::ChainConfig;
+use crate::fetcher::bitcoin_fetcher::BitcoinFetcher;
+use crate::fetcher::ethereum_fetcher::EthereumFetcher;
+use crate::fetcher::fetcher::BlockFetcher;
+use crate::fetcher::icon_fetcher::IconFetcher;
+use crate::fetcher::theta_fetcher::ThetaFetcher;
+use crate::fetcher::tron_fetcher::TronFetcher;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 설정의 체인 이름으로 fetcher 생성 (지원하지 않는 체인이면 None)
+pub fn build_fetcher(chain_name: &str, chain_config: &ChainConfig) -> Option<Arc<dyn BlockFetcher>> {
+  let fetcher: Arc<dyn BlockFetcher> = match chain_name.to_lowercase().as_str() {
+    "ethereum" | "eth" | "sepolia" => {
+      Arc::new(EthereumFetcher::new(Arc::new(EthereumClient::new(chain_config.api.clone()))))
+    }
+    "bitcoin" | "btc" => {
+      Arc::new(BitcoinFetcher { client: Arc::new(BitcoinClient::new(chain_config.api.clone())) })
+    }
+    "tron" => Arc::new(TronFetcher { client: Arc::new(TronClient::new(chain_config.api.clone())) }),
+    "theta" => Arc::new(ThetaFetcher { client: Arc::new(ThetaClient::new(chain_config.api.clone())) }),
+    "icon" => Arc::new(IconFetcher { client: Arc::new(IconClient::new(chain_config.api.clone())) }),
+    _ => return None,
+  };
+  Some(fetcher)
+}
+
+/// 설정 이름(소문자) -> fetcher 체인 이름
+///
+/// analyzer는 blockchain_state, 토큰 레지스트리 등을 fetcher의 `chain_name()`으로 조회하므로
+/// 설정 이름(`[blockchain.<name>]`, `[[tokens]] chain`)은 이 이름으로 바꿔서 써야 한다.
+pub fn chain_names(chains: &[(String, ChainConfig)]) -> HashMap<String, &'static str> {
+  chains.iter()
+    .filter_map(|(name, config)| build_fetcher(name, config).map(|fetcher| (name.to_lowercase(), fetcher.chain_name())))
+    .collect()
+}
@@ -11,6 +11,19 @@ use crate::types::{BlockData, AppError};
 #[async_trait]
 pub trait BlockFetcher: Send + Sync {
   async fn fetch_block(&self, block_number: u64) -> Result<BlockData, AppError>;
+  /// 체인의 최신 블록 번호 (catch-up 판단용). 지원하지 않는 체인은 None
+  async fn fetch_latest_block_number(&self) -> Result<Option<u64>, AppError> {
+    Ok(None)
+  }
+  /// block_number의 블록 해시 (canonical 확인용, 해시 정보가 없는 체인은 None)
+  /// 기본 구현은 블록 전체를 조회하므로 가벼운 조회가 가능한 체인은 재정의한다
+  async fn fetch_block_hash(&self, block_number: u64) -> Result<Option<String>, AppError> {
+    Ok(self.fetch_block(block_number).await?.block_ref().map(|block| block.hash))
+  }
+  /// 블록과 함께 실행 결과를 가져오지 못한 트랜잭션의 실패 사유 (성공했거나 확인할 수 없으면 None)
+  async fn fetch_transaction_failure(&self, _tx_hash: &str) -> Result<Option<String>, AppError> {
+    Ok(None)
+  }
   // fn extract_block_number(&self, block: &BlockData) -> Result<u64, AppError>;
   fn chain_name(&self) -> &'static str;
 }
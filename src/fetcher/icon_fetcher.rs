@@ -11,10 +11,23 @@ pub struct IconFetcher {
 #[async_trait]
 impl BlockFetcher for IconFetcher {
     async fn fetch_block(&self, block_number: u64) -> Result<BlockData, AppError> {
+        // 트랜잭션 실행 결과는 Analyzer가 관리 주소로 온 트랜잭션만 fetch_transaction_failure로 확인
         let block = self.client.fetch_block_by_number(block_number).await
             .map_err(|e| AppError::Client(format!("Failed to fetch ICON block: {}", e)))?;
         Ok(BlockData::Icon(block))
     }
+
+    async fn fetch_transaction_failure(&self, tx_hash: &str) -> Result<Option<String>, AppError> {
+        let result = self.client.fetch_transaction_result(tx_hash).await
+            .map_err(|e| AppError::Client(format!("Failed to fetch ICON transaction result {}: {}", tx_hash, e)))?;
+        Ok(result.failure_reason())
+    }
+
+    async fn fetch_latest_block_number(&self) -> Result<Option<u64>, AppError> {
+        let latest = self.client.fetch_latest_block_number().await
+            .map_err(|e| AppError::Client(format!("Failed to fetch ICON last block: {}", e)))?;
+        Ok(Some(latest))
+    }
     
     fn chain_name(&self) -> &'static str {
         "ICON"
@@ -1,5 +1,7 @@
 pub mod runner;
+pub mod factory;
 pub mod fetcher;
+pub mod reorg;
 pub mod bitcoin_fetcher;
 pub mod ethereum_fetcher;
 pub mod tron_fetcher;
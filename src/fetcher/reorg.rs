@@ -0,0 +1,234 @@
+// filename : reorg
+// author : HAMA
+// date: 2025. 12. 15.
+// description: Chain reorganization detection for the block fetcher
+
+use crate::fetcher::fetcher::BlockFetcher;
+use crate::types::AppError;
+
+use log::{error, info, warn};
+use std::collections::VecDeque;
+
+/// 최근 전송한 블록 해시를 기억하고 parent hash 불일치(reorg)를 감지
+pub struct ReorgDetector {
+  max_depth: usize,
+  // (block_number, block_hash) - 오래된 순
+  recent: VecDeque<(u64, String)>,
+}
+
+impl ReorgDetector {
+  /// `seed`: 저장소에 보관된 최근 블록 해시 (재시작 시 복원용)
+  pub fn new(max_depth: u64, seed: Vec<(u64, String)>) -> Self {
+    let mut detector = Self {
+      max_depth: max_depth as usize,
+      recent: VecDeque::with_capacity(max_depth as usize),
+    };
+    for (block_number, hash) in seed {
+      detector.record(block_number, &hash);
+    }
+    detector
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    self.max_depth > 0
+  }
+
+  /// 새 블록의 parent hash가 직전 블록 해시와 일치하는지 확인
+  /// 직전 블록 정보가 없으면 (최초 실행 등) 일치하는 것으로 간주
+  pub fn is_parent_consistent(&self, block_number: u64, parent_hash: &str) -> bool {
+    match self.hash_of(block_number.saturating_sub(1)) {
+      Some(expected) => expected.eq_ignore_ascii_case(parent_hash),
+      None => true,
+    }
+  }
+
+  /// 처리된 블록 해시 기록
+  pub fn record(&mut self, block_number: u64, hash: &str) {
+    if !self.is_enabled() {
+      return;
+    }
+    // 재스캔 등으로 같은 번호 이상의 블록이 이미 있으면 제거
+    while self.recent.back().is_some_and(|(n, _)| *n >= block_number) {
+      self.recent.pop_back();
+    }
+    self.recent.push_back((block_number, hash.to_lowercase()));
+    while self.recent.len() > self.max_depth {
+      self.recent.pop_front();
+    }
+  }
+
+  /// canonical 체인과 비교하여 공통 조상 블록 번호를 찾음
+  ///
+  /// 기록된 블록을 최신 순으로 다시 가져와 해시를 비교하며, 일치하지 않는 블록은 버린다.
+  /// 추적 범위(max_depth) 안에서 공통 조상을 찾지 못하면 추적 중인 가장 오래된 블록 직전으로 되돌린다.
+  pub async fn find_common_ancestor<F: BlockFetcher + ?Sized>(&mut self, fetcher: &F) -> Result<u64, AppError> {
+    let oldest = match self.recent.front() {
+      Some((n, _)) => *n,
+      None => return Err(AppError::Block("No block hashes tracked for reorg resolution".to_string())),
+    };
+
+    while let Some((block_number, stored_hash)) = self.recent.back().cloned() {
+      let canonical_hash = fetcher
+        .fetch_block_hash(block_number)
+        .await?
+        .ok_or_else(|| AppError::Block(format!("Block {} has no hash information", block_number)))?;
+
+      if canonical_hash.eq_ignore_ascii_case(&stored_hash) {
+        info!(
+          "[{} Reorg] Common ancestor found at block #{} ({})",
+          fetcher.chain_name(), block_number, canonical_hash
+        );
+        return Ok(block_number);
+      }
+
+      warn!(
+        "[{} Reorg] Block #{} orphaned (stored: {}, canonical: {})",
+        fetcher.chain_name(), block_number, stored_hash, canonical_hash
+      );
+      self.recent.pop_back();
+    }
+
+    let fallback = oldest.saturating_sub(1);
+    error!(
+      "[{} Reorg] Reorg deeper than tracked window ({} blocks), rolling back to block #{}",
+      fetcher.chain_name(), self.max_depth, fallback
+    );
+    Ok(fallback)
+  }
+
+  fn hash_of(&self, block_number: u64) -> Option<&str> {
+    self.recent
+      .iter()
+      .rev()
+      .find(|(n, _)| *n == block_number)
+      .map(|(_, h)| h.as_str())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::coin::bitcoin::model::BitcoinBlock;
+  use crate::types::BlockData;
+  use async_trait::async_trait;
+  use std::collections::HashMap;
+
+  /// 블록 번호별 canonical 해시를 돌려주는 fetcher
+  struct CanonicalChain {
+    hashes: HashMap<u64, String>,
+  }
+
+  impl CanonicalChain {
+    fn new(blocks: &[(u64, &str)]) -> Self {
+      Self { hashes: blocks.iter().map(|(n, h)| (*n, h.to_string())).collect() }
+    }
+  }
+
+  #[async_trait]
+  impl BlockFetcher for CanonicalChain {
+    async fn fetch_block(&self, block_number: u64) -> Result<BlockData, AppError> {
+      let hash = self.hashes
+        .get(&block_number)
+        .ok_or_else(|| AppError::Block(format!("Block {} not found", block_number)))?;
+      Ok(BlockData::Bitcoin(BitcoinBlock {
+        hash: hash.clone(),
+        ver: 1,
+        prev_block: String::new(),
+        mrkl_root: String::new(),
+        time: 0,
+        bits: 0,
+        next_block: None,
+        fee: 0,
+        nonce: 0,
+        n_tx: 0,
+        size: 0,
+        block_index: block_number,
+        main_chain: true,
+        height: block_number,
+        weight: 0,
+        tx: Vec::new(),
+      }))
+    }
+
+    fn chain_name(&self) -> &'static str {
+      "TEST"
+    }
+  }
+
+  fn tracked(max_depth: u64, blocks: &[(u64, &str)]) -> ReorgDetector {
+    ReorgDetector::new(max_depth, blocks.iter().map(|(n, h)| (*n, h.to_string())).collect())
+  }
+
+  #[test]
+  fn parent_matches_previous_block() {
+    let detector = tracked(10, &[(100, "0xaa"), (101, "0xbb")]);
+    assert!(detector.is_parent_consistent(102, "0xbb"));
+    assert!(detector.is_parent_consistent(102, "0xBB"));
+    assert!(!detector.is_parent_consistent(102, "0xcc"));
+  }
+
+  #[test]
+  fn unknown_previous_block_is_consistent() {
+    let detector = tracked(10, &[(100, "0xaa")]);
+    // 직전 블록(104)을 추적하지 않음
+    assert!(detector.is_parent_consistent(105, "0xcc"));
+    assert!(tracked(10, &[]).is_parent_consistent(0, "0xcc"));
+  }
+
+  #[test]
+  fn disabled_detector_accepts_everything() {
+    let detector = tracked(0, &[(100, "0xaa")]);
+    assert!(!detector.is_enabled());
+    assert!(detector.is_parent_consistent(101, "0xcc"));
+  }
+
+  #[test]
+  fn record_replaces_rescanned_blocks_and_trims_window() {
+    let mut detector = tracked(3, &[(100, "0xaa"), (101, "0xbb"), (102, "0xcc")]);
+    detector.record(101, "0xB2");
+    assert!(detector.is_parent_consistent(102, "0xb2"));
+    // 102는 재스캔으로 제거됨
+    assert!(detector.is_parent_consistent(103, "0xanything"));
+
+    detector.record(102, "0xc2");
+    detector.record(103, "0xd2");
+    // 100은 max_depth(3)를 넘어 밀려남
+    assert!(detector.is_parent_consistent(101, "0xanything"));
+    assert!(!detector.is_parent_consistent(104, "0xanything"));
+  }
+
+  #[tokio::test]
+  async fn finds_common_ancestor_and_drops_orphans() {
+    let mut detector = tracked(10, &[(100, "0xaa"), (101, "0xbb"), (102, "0xcc")]);
+    let chain = CanonicalChain::new(&[(100, "0xAA"), (101, "0xbb"), (102, "0xc2")]);
+
+    assert_eq!(detector.find_common_ancestor(&chain).await.unwrap(), 101);
+    // 고아가 된 102는 더 이상 추적하지 않음
+    assert!(detector.is_parent_consistent(103, "0xanything"));
+    assert!(detector.is_parent_consistent(102, "0xbb"));
+  }
+
+  #[tokio::test]
+  async fn falls_back_before_oldest_when_reorg_is_deeper_than_window() {
+    let mut detector = tracked(10, &[(100, "0xaa"), (101, "0xbb")]);
+    let chain = CanonicalChain::new(&[(100, "0xa2"), (101, "0xb2")]);
+
+    assert_eq!(detector.find_common_ancestor(&chain).await.unwrap(), 99);
+  }
+
+  #[tokio::test]
+  async fn fails_without_tracked_blocks() {
+    let mut detector = tracked(10, &[]);
+    let chain = CanonicalChain::new(&[]);
+
+    assert!(detector.find_common_ancestor(&chain).await.is_err());
+  }
+
+  #[tokio::test]
+  async fn propagates_fetch_errors() {
+    let mut detector = tracked(10, &[(100, "0xaa"), (101, "0xbb")]);
+    let chain = CanonicalChain::new(&[(100, "0xaa")]);
+
+    assert!(detector.find_common_ancestor(&chain).await.is_err());
+  }
+}
@@ -6,28 +6,86 @@
 **/
 
 use crate::fetcher::fetcher::BlockFetcher;
-use crate::types::BlockSender;
+use crate::fetcher::reorg::ReorgDetector;
+use crate::types::{AppError, BlockData, BlockSender, ChainEvent, RetryReceiver};
 
 use log::{info, warn, error};
 use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tokio::time::{interval, Duration};
+use tokio_util::sync::CancellationToken;
 
-pub async fn run_fetcher<F: BlockFetcher + 'static>(
+/// 밀린 블록 catch-up 설정
+#[derive(Debug, Clone, Copy)]
+pub struct CatchupConfig {
+  pub concurrency: usize, // 동시에 조회할 블록 수
+  pub window: u64,        // 한 번에 가져올 최대 블록 수
+}
+
+impl CatchupConfig {
+  pub fn is_enabled(&self) -> bool {
+    self.concurrency > 1 && self.window > 1
+  }
+}
+
+/// fetcher와 그 체인의 analyzer worker를 잇는 채널 (블록 전달 / commit 실패한 블록 재요청)
+pub struct AnalyzerLink {
+  pub sender: BlockSender,
+  pub retry: RetryReceiver,
+}
+
+/// 블록을 가져와 analyzer로 전달
+///
+/// analyzer가 블록 commit에 실패하면 `link.retry`로 그 블록 번호를 보내고, fetcher는 그 블록부터 다시 전달한다.
+/// `shutdown`이 취소되면 새 블록을 가져오지 않고 종료한다 (이미 전달한 블록은 analyzer가 처리).
+pub async fn run_fetcher<F: BlockFetcher + ?Sized + 'static>(
   fetcher: Arc<F>,
-  sender: BlockSender,
+  mut link: AnalyzerLink,
   mut current_block_number: u64,
   interval_duration: Duration,
+  mut reorg_detector: ReorgDetector,
+  catchup: CatchupConfig,
+  shutdown: CancellationToken,
 ) {
   let mut tick = interval(interval_duration);
   info!(
-        "[{} Fetcher] Starting from block {} with interval {:?}",
+        "[{} Fetcher] Starting from block {} with interval {:?} (catch-up: {:?})",
         fetcher.chain_name(),
         current_block_number,
-        interval_duration
+        interval_duration,
+        catchup
     );
   
   loop {
-    tick.tick().await;
+    tokio::select! {
+      _ = tick.tick() => {}
+      _ = shutdown.cancelled() => break,
+    }
+    current_block_number = take_retry(fetcher.as_ref(), &mut link.retry, current_block_number);
+
+    // 체인 head보다 뒤처져 있으면 window 단위로 동시에 가져와 순서대로 전달
+    if catchup.is_enabled() {
+      if let Some(head) = fetch_head(fetcher.as_ref()).await {
+        if head > current_block_number {
+          info!(
+            "[{} Fetcher] 🚀 Catch-up 시작: #{} -> #{} ({} blocks behind)",
+            fetcher.chain_name(), current_block_number, head, head - current_block_number
+          );
+          while head > current_block_number && !shutdown.is_cancelled() {
+            let window_end = head.min(current_block_number + catchup.window - 1);
+            let next = fetch_window(&fetcher, &mut link, &shutdown, &mut reorg_detector, current_block_number, window_end, catchup.concurrency).await;
+            // 실패 또는 reorg로 진행하지 못하면 다음 tick에 다시 시도
+            let progressed = next > current_block_number;
+            current_block_number = next;
+            if !progressed {
+              break;
+            }
+          }
+          continue;
+        }
+      }
+    }
+
     let block_to_fetch = current_block_number;
 
     info!("===============================================");
@@ -36,19 +94,11 @@ pub async fn run_fetcher<F: BlockFetcher + 'static>(
     match fetcher.fetch_block(block_to_fetch).await {
       Ok(block_data) => {
         info!("[{} Fetcher] ✅ 블록 #{} 가져오기 성공!", fetcher.chain_name(), block_to_fetch);
-        if let Err(e) = sender.send(block_data).await {
-          error!(
-                  "[{} Fetcher] Failed to send block {}: {}",
-                  fetcher.chain_name(),
-                  block_to_fetch,
-                  e
-              );
-        } else {
-          info!("[{} Fetcher] 블록 #{} Analyzer로 전송 완료", fetcher.chain_name(), block_to_fetch);
-        }
-        current_block_number += 1;
+        crate::metrics::inc_blocks_fetched(fetcher.chain_name());
+        current_block_number = deliver_block(fetcher.as_ref(), &link.sender, &mut reorg_detector, block_to_fetch, block_data).await;
       }
       Err(e) => {
+        crate::metrics::inc_fetch_error(fetcher.chain_name(), &e);
         let retry_delay = interval_duration / 2;
         warn!(
           "[{} Fetcher] ⏳ 블록 #{} 가져오기 실패: {} | {:?} 후 재시도...",
@@ -58,10 +108,298 @@ pub async fn run_fetcher<F: BlockFetcher + 'static>(
           retry_delay
         );
         // 블록 번호를 증가시키지 않고 interval의 절반 시간 후 재시도
-        tokio::time::sleep(retry_delay).await;
+        tokio::select! {
+          _ = tokio::time::sleep(retry_delay) => {}
+          _ = shutdown.cancelled() => break,
+        }
       }
     }
   }
-  
-  warn!("[{} Fetcher] Loop exited.", fetcher.chain_name());
+
+  info!("[{} Fetcher] Stopped at block {}", fetcher.chain_name(), current_block_number);
+}
+
+/// analyzer가 다시 요청한 블록이 있으면 그 블록부터 (여러 개면 가장 앞 블록)
+fn take_retry<F: BlockFetcher + ?Sized>(fetcher: &F, retry: &mut RetryReceiver, current_block_number: u64) -> u64 {
+  let mut next = current_block_number;
+  while let Ok(block_number) = retry.try_recv() {
+    next = next.min(block_number);
+  }
+  if next != current_block_number {
+    warn!(
+      "[{} Fetcher] 🔁 Analyzer가 블록 #{} commit에 실패해 #{}부터 다시 전달",
+      fetcher.chain_name(), next, next
+    );
+  }
+  next
+}
+
+/// 체인 최신 블록 번호 조회 (실패하거나 지원하지 않으면 None)
+async fn fetch_head<F: BlockFetcher + ?Sized>(fetcher: &F) -> Option<u64> {
+  match fetcher.fetch_latest_block_number().await {
+    Ok(head) => head,
+    Err(e) => {
+      warn!("[{} Fetcher] 최신 블록 번호 조회 실패: {}", fetcher.chain_name(), e);
+      None
+    }
+  }
+}
+
+/// from..=to 블록을 최대 concurrency개씩 동시에 가져와 번호 순서대로 전달
+/// 다음에 가져올 블록 번호를 반환 (조회 실패/reorg/analyzer 재요청/종료 시 그 지점에서 중단)
+async fn fetch_window<F: BlockFetcher + ?Sized + 'static>(
+  fetcher: &Arc<F>,
+  link: &mut AnalyzerLink,
+  shutdown: &CancellationToken,
+  reorg_detector: &mut ReorgDetector,
+  from: u64,
+  to: u64,
+  concurrency: usize,
+) -> u64 {
+  let semaphore = Arc::new(Semaphore::new(concurrency));
+  let mut handles = (from..=to)
+    .map(|block_number| {
+      let fetcher = fetcher.clone();
+      let semaphore = semaphore.clone();
+      tokio::spawn(async move {
+        let _permit = semaphore.acquire_owned().await;
+        fetcher.fetch_block(block_number).await
+      })
+    })
+    .collect::<Vec<_>>()
+    .into_iter();
+
+  let mut next = from;
+  for block_number in from..=to {
+    let rewind = take_retry(fetcher.as_ref(), &mut link.retry, block_number);
+    if rewind != block_number || shutdown.is_cancelled() {
+      next = rewind;
+      break;
+    }
+    let Some(handle) = handles.next() else { break };
+    let result = handle.await
+      .unwrap_or_else(|e| Err(AppError::Block(format!("Fetch task failed: {}", e))));
+
+    match result {
+      Ok(block_data) => {
+        crate::metrics::inc_blocks_fetched(fetcher.chain_name());
+        next = deliver_block(fetcher.as_ref(), &link.sender, reorg_detector, block_number, block_data).await;
+        if next != block_number + 1 {
+          break;
+        }
+      }
+      Err(e) => {
+        crate::metrics::inc_fetch_error(fetcher.chain_name(), &e);
+        warn!("[{} Fetcher] ⏳ Catch-up 블록 #{} 가져오기 실패: {}", fetcher.chain_name(), block_number, e);
+        break;
+      }
+    }
+  }
+
+  // 중단된 경우 남은 조회 작업 취소
+  handles.for_each(|handle| handle.abort());
+  next
+}
+
+/// reorg 검사 후 블록을 Analyzer로 전달하고 다음에 가져올 블록 번호를 반환
+async fn deliver_block<F: BlockFetcher + ?Sized>(
+  fetcher: &F,
+  sender: &BlockSender,
+  reorg_detector: &mut ReorgDetector,
+  block_number: u64,
+  block_data: BlockData,
+) -> u64 {
+  // Reorg 감지: parent hash가 직전에 보낸 블록 해시와 다르면 공통 조상까지 되돌림
+  if let Some(block_ref) = block_data.block_ref() {
+    if !reorg_detector.is_parent_consistent(block_number, &block_ref.parent_hash) {
+      warn!(
+        "[{} Fetcher] ⚠️ Reorg 감지! 블록 #{} parent hash {} 불일치",
+        fetcher.chain_name(),
+        block_number,
+        block_ref.parent_hash
+      );
+
+      return match reorg_detector.find_common_ancestor(fetcher).await {
+        Ok(common_ancestor) => {
+          let event = ChainEvent::Reorg {
+            chain_name: fetcher.chain_name().to_string(),
+            common_ancestor,
+          };
+          if let Err(e) = sender.send(event).await {
+            error!("[{} Fetcher] Failed to send reorg event: {}", fetcher.chain_name(), e);
+            return block_number;
+          }
+          // canonical 체인 재스캔
+          common_ancestor + 1
+        }
+        Err(e) => {
+          warn!("[{} Fetcher] Reorg 해결 실패: {} | 다음 tick에 재시도", fetcher.chain_name(), e);
+          block_number
+        }
+      };
+    }
+    reorg_detector.record(block_number, &block_ref.hash);
+  }
+
+  if let Err(e) = sender.send(ChainEvent::Block { block_number, block: block_data }).await {
+    error!(
+            "[{} Fetcher] Failed to send block {}: {}",
+            fetcher.chain_name(),
+            block_number,
+            e
+        );
+  } else {
+    crate::metrics::set_analyzer_backlog(fetcher.chain_name(), sender.max_capacity() - sender.capacity());
+    info!("[{} Fetcher] 블록 #{} Analyzer로 전송 완료", fetcher.chain_name(), block_number);
+  }
+  block_number + 1
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::coin::bitcoin::model::BitcoinBlock;
+  use async_trait::async_trait;
+  use std::sync::Mutex;
+  use tokio::sync::mpsc;
+
+  /// 번호가 큰 블록일수록 빨리 응답하는 fetcher (조회 완료 순서가 블록 순서와 반대)
+  struct ReversedChain {
+    head: u64,
+    failing: Option<u64>,
+    started: Mutex<Vec<u64>>,
+    completed: Mutex<Vec<u64>>,
+  }
+
+  impl ReversedChain {
+    fn new(head: u64, failing: Option<u64>) -> Arc<Self> {
+      Arc::new(Self { head, failing, started: Mutex::new(Vec::new()), completed: Mutex::new(Vec::new()) })
+    }
+  }
+
+  #[async_trait]
+  impl BlockFetcher for ReversedChain {
+    async fn fetch_block(&self, block_number: u64) -> Result<BlockData, AppError> {
+      self.started.lock().unwrap().push(block_number);
+      if self.failing == Some(block_number) {
+        return Err(AppError::Block(format!("Block {} not found", block_number)));
+      }
+      tokio::time::sleep(Duration::from_millis((self.head - block_number + 1) * 10)).await;
+      self.completed.lock().unwrap().push(block_number);
+      Ok(BlockData::Bitcoin(BitcoinBlock {
+        hash: format!("0x{:x}", block_number),
+        ver: 1,
+        prev_block: format!("0x{:x}", block_number - 1),
+        mrkl_root: String::new(),
+        time: 0,
+        bits: 0,
+        next_block: None,
+        fee: 0,
+        nonce: 0,
+        n_tx: 0,
+        size: 0,
+        block_index: block_number,
+        main_chain: true,
+        height: block_number,
+        weight: 0,
+        tx: Vec::new(),
+      }))
+    }
+
+    fn chain_name(&self) -> &'static str {
+      "TEST"
+    }
+  }
+
+  struct Harness {
+    link: AnalyzerLink,
+    blocks: mpsc::Receiver<ChainEvent>,
+    retry: mpsc::UnboundedSender<u64>,
+  }
+
+  impl Harness {
+    fn new() -> Self {
+      let (sender, blocks) = mpsc::channel(100);
+      let (retry, retry_receiver) = mpsc::unbounded_channel();
+      Self { link: AnalyzerLink { sender, retry: retry_receiver }, blocks, retry }
+    }
+
+    fn delivered(&mut self) -> Vec<u64> {
+      let mut delivered = Vec::new();
+      while let Ok(event) = self.blocks.try_recv() {
+        if let ChainEvent::Block { block_number, .. } = event {
+          delivered.push(block_number);
+        }
+      }
+      delivered
+    }
+  }
+
+  #[tokio::test]
+  async fn window_delivers_blocks_in_order() {
+    let fetcher = ReversedChain::new(15, None);
+    let mut harness = Harness::new();
+    let mut detector = ReorgDetector::new(10, Vec::new());
+
+    let next = fetch_window(&fetcher, &mut harness.link, &CancellationToken::new(), &mut detector, 10, 15, 6).await;
+
+    assert_eq!(next, 16);
+    assert_eq!(*fetcher.completed.lock().unwrap(), [15, 14, 13, 12, 11, 10]);
+    assert_eq!(harness.delivered(), [10, 11, 12, 13, 14, 15]);
+  }
+
+  #[tokio::test]
+  async fn failed_block_stops_window_and_aborts_remaining_fetches() {
+    let fetcher = ReversedChain::new(19, Some(12));
+    let mut harness = Harness::new();
+    let mut detector = ReorgDetector::new(10, Vec::new());
+
+    let next = fetch_window(&fetcher, &mut harness.link, &CancellationToken::new(), &mut detector, 10, 19, 2).await;
+
+    assert_eq!(next, 12);
+    assert_eq!(harness.delivered(), [10, 11]);
+    // 중단 이후에는 남은 블록을 조회하지 않는다
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert!(fetcher.started.lock().unwrap().len() < 10);
+  }
+
+  #[tokio::test]
+  async fn analyzer_retry_rewinds_before_delivering() {
+    let fetcher = ReversedChain::new(15, None);
+    let mut harness = Harness::new();
+    let mut detector = ReorgDetector::new(10, Vec::new());
+    harness.retry.send(8).unwrap();
+    harness.retry.send(9).unwrap();
+
+    let next = fetch_window(&fetcher, &mut harness.link, &CancellationToken::new(), &mut detector, 10, 15, 6).await;
+
+    // 재요청된 블록 중 가장 앞 블록부터 다시 전달
+    assert_eq!(next, 8);
+    assert!(harness.delivered().is_empty());
+    assert!(harness.link.retry.try_recv().is_err());
+  }
+
+  #[tokio::test]
+  async fn shutdown_stops_window() {
+    let fetcher = ReversedChain::new(15, None);
+    let mut harness = Harness::new();
+    let mut detector = ReorgDetector::new(10, Vec::new());
+    let shutdown = CancellationToken::new();
+    shutdown.cancel();
+
+    let next = fetch_window(&fetcher, &mut harness.link, &shutdown, &mut detector, 10, 15, 6).await;
+
+    assert_eq!(next, 10);
+    assert!(harness.delivered().is_empty());
+  }
+
+  #[test]
+  fn take_retry_keeps_current_block_without_requests() {
+    let fetcher = ReversedChain::new(15, None);
+    let mut harness = Harness::new();
+    assert_eq!(take_retry(fetcher.as_ref(), &mut harness.link.retry, 10), 10);
+    harness.retry.send(12).unwrap();
+    harness.retry.send(7).unwrap();
+    assert_eq!(take_retry(fetcher.as_ref(), &mut harness.link.retry, 10), 7);
+  }
 }
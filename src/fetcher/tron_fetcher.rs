@@ -14,6 +14,11 @@ impl BlockFetcher for TronFetcher {
         let block = self.client.fetch_block_by_number(block_number).await?;
         Ok(BlockData::Tron(block))
     }
+
+    async fn fetch_latest_block_number(&self) -> Result<Option<u64>, AppError> {
+        let latest = self.client.fetch_latest_block_number().await?;
+        Ok(Some(latest))
+    }
     
     fn chain_name(&self) -> &'static str {
         "TRON"
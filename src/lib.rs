@@ -6,6 +6,8 @@
 **/
 
 pub mod coin;
+pub mod config;
 pub mod fetcher;
 pub mod types;
-pub mod notification;
\ No newline at end of file
+pub mod notification;
+pub mod metrics;
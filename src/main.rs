@@ -14,97 +14,85 @@ mod analyzer;
 mod shutdown;
 mod tasks;
 mod notification;
+mod api;
+mod metrics;
+mod cli;
 
-use crate::coin::bitcoin::client::BitcoinClient;
-use crate::coin::ethereum::client::EthereumClient;
-use crate::coin::tron::client::TronClient;
-use crate::coin::theta::client::ThetaClient;
-use crate::coin::icon::client::IconClient;
+use crate::cli::{Cli, Command};
 use crate::config::Settings;
-use crate::types::{AppError, BlockData};
-use crate::fetcher::bitcoin_fetcher::BitcoinFetcher;
-use crate::fetcher::ethereum_fetcher::EthereumFetcher;
-use crate::fetcher::tron_fetcher::TronFetcher;
-use crate::fetcher::theta_fetcher::ThetaFetcher;
-use crate::fetcher::icon_fetcher::IconFetcher;
+use crate::types::{AppError, ChainEvent};
+use crate::fetcher::factory::{build_fetcher, chain_names};
+use crate::fetcher::reorg::ReorgDetector;
+use crate::fetcher::runner::{AnalyzerLink, CatchupConfig};
 
-use crate::respository::{
-    Repository,
-    RepositoryWrapper,
-};
-use crate::analyzer::analyzer::KeyValueDB;
-use crate::notification::sqs_client::SqsNotifier;
-
-#[cfg(feature = "leveldb-backend")]
-use crate::respository::open_leveldb;
-
-#[cfg(feature = "rocksdb-backend")]
-use crate::respository::open_rocksdb;
+use crate::respository::{open_address_index, Repository, RepositoryWrapper};
 use crate::shutdown::shutdown_signal;
 
-use log::{error, info, warn};
+use clap::Parser;
+use log::{info, warn};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 #[tokio::main]
 async fn main() -> Result<(), AppError> {
+    let cli = Cli::parse();
+
     // 1. Initialize logging
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
     info!("Application starting...");
     
     // 2. Load configuration
-    let settings = Settings::new().map_err(|e| AppError::Config(e.to_string()))?;
-    info!("Configuration loaded.");
-    
-    // 3. Initialize Repository based on configuration
-    let repository = Arc::new(RepositoryWrapper::from_settings(&settings).await?);
-    if settings.repository.memory_db {
-        info!("Using MemoryRepository (memory_db = true)");
-    } else {
-        info!("Using PostgreSQLRepository (memory_db = false)");
+    let settings = Settings::from_path(&cli.config).map_err(|e| AppError::Config(e.to_string()))?;
+    info!("Configuration loaded from {}.", cli.config);
+
+    // 서브커맨드가 없으면 스캐너 실행
+    match cli.command {
+        None | Some(Command::Run) => run_scanner(settings).await,
+        Some(command) => cli::execute(command, &settings).await,
     }
-    
-    // 4. Open KeyValueDB for fast address lookups (RocksDB or LevelDB)
-    let kv_db: Option<Arc<KeyValueDB>> = if !settings.repository.memory_db {
-        #[cfg(feature = "leveldb-backend")]
-        {
-            let db = Arc::new(open_leveldb(&settings.repository.leveldb_path)?);
-            info!("Opened LevelDB for customer address caching.");
-            info!("Note: Customer addresses will be loaded via SQS sync + optional cache file");
-            Some(db)
-        }
+}
 
+/// `xscanner run` - 설정된 모든 체인 스캔
+async fn run_scanner(settings: Settings) -> Result<(), AppError> {
+    // 3. Initialize Repository based on configuration
+    let repository = Arc::new(RepositoryWrapper::from_settings(&settings).await?);
+    match repository.as_ref() {
+        RepositoryWrapper::Memory(_) => info!("Using MemoryRepository (memory_db = true)"),
+        RepositoryWrapper::PostgreSQL(_) => info!("Using PostgreSQLRepository"),
         #[cfg(feature = "rocksdb-backend")]
-        {
-            let db = Arc::new(open_rocksdb(&settings.repository.leveldb_path)?);
-            info!("Opened RocksDB for customer address caching.");
-            info!("Note: Customer addresses will be loaded via SQS sync + optional cache file");
-            Some(db)
-        }
-
-        #[cfg(not(any(feature = "leveldb-backend", feature = "rocksdb-backend")))]
-        {
-            warn!("No KeyValueDB feature enabled. Using PostgreSQL only.");
-            None
-        }
-    } else {
-        None
-    };
+        RepositoryWrapper::RocksDB(_) => info!("Using RocksDBRepository ({})", settings.repository.rocksdb_path.as_deref().unwrap_or_default()),
+    }
     
-    // 5. Create channel for blocks
-    let (sender, receiver) = mpsc::channel::<BlockData>(128);
+    // 4. Open address index for monitored address lookups
+    let address_index = open_address_index(&settings, &repository, true)?;
     
+    // 종료 신호 시 취소 - fetcher, analyzer, confirmation checker, outbox, customer sync, HTTP 서버가 정리 후 종료
+    let shutdown = CancellationToken::new();
+    // fetcher / analyzer 외에 종료를 기다릴 작업
+    let mut worker_handles: Vec<JoinHandle<()>> = Vec::new();
+
     // 6. Dynamically spawn fetchers for all configured chains
     let mut fetcher_handles: Vec<JoinHandle<()>> = Vec::new();
     let chain_configs = settings.get_chain_configs();
     
     info!("Found {} blockchain(s) to monitor", chain_configs.len());
     
+    let mut head_tracker = crate::tasks::ChainHeadTracker::new();
+    let backfill_config = crate::tasks::BackfillConfig {
+        blocks_per_second: settings.backfill.blocks_per_second,
+        poll_interval_secs: settings.backfill.poll_interval_secs,
+        checkpoint_blocks: settings.backfill.checkpoint_blocks,
+    };
+    let mut backfill_scheduler = crate::tasks::BackfillScheduler::new(repository.clone(), backfill_config);
+    // 5. 체인별 analyzer 채널 (worker는 알림 설정 후 9단계에서 시작)
+    let mut analyzer_receivers = Vec::new();
+    let mut analyzer_fetchers = std::collections::HashMap::new();
+
     for (chain_name, chain_config) in chain_configs {
         let chain_symbol = chain_config.symbol.to_uppercase();
-        let sender_clone = sender.clone();
         
         // ???: ??? ?? ?? ?? (?? ??? ?)
         if let Err(e) = repository.init_last_processed_block(&chain_symbol, chain_config.start_block).await {
@@ -134,78 +122,86 @@ async fn main() -> Result<(), AppError> {
         info!("Initializing {} scanner from block {}", chain_name, start_block);
         
         let interval_duration = Duration::from_secs(chain_config.interval_secs);
+        let catchup = CatchupConfig {
+            concurrency: chain_config.catchup_concurrency,
+            window: chain_config.catchup_window,
+        };
 
-        // Spawn fetcher based on chain name
-        let handle = match chain_name.to_lowercase().as_str() {
-            "ethereum" | "eth" | "sepolia" => {
-                let client = Arc::new(EthereumClient::new(chain_config.api.clone()));
-                let fetcher = Arc::new(EthereumFetcher { client });
-                tokio::spawn(crate::fetcher::runner::run_fetcher(fetcher, sender_clone, start_block, interval_duration))
-            }
-            "bitcoin" | "btc" => {
-                let client = Arc::new(BitcoinClient::new(chain_config.api.clone()));
-                let fetcher = Arc::new(BitcoinFetcher { client });
-                tokio::spawn(crate::fetcher::runner::run_fetcher(fetcher, sender_clone, start_block, interval_duration))
-            }
-            "tron" => {
-                let client = Arc::new(TronClient::new(chain_config.api.clone()));
-                let fetcher = Arc::new(TronFetcher { client });
-                tokio::spawn(crate::fetcher::runner::run_fetcher(fetcher, sender_clone, start_block, interval_duration))
-            }
-            "theta" => {
-                let client = Arc::new(ThetaClient::new(chain_config.api.clone()));
-                let fetcher = Arc::new(ThetaFetcher { client });
-                tokio::spawn(crate::fetcher::runner::run_fetcher(fetcher, sender_clone, start_block, interval_duration))
-            }
-            "icon" => {
-                let client = Arc::new(IconClient::new(chain_config.api.clone()));
-                let fetcher = Arc::new(IconFetcher { client });
-                tokio::spawn(crate::fetcher::runner::run_fetcher(fetcher, sender_clone, start_block, interval_duration))
-            }
-            _ => {
-                warn!("Unknown blockchain: {}, skipping...", chain_name);
-                continue;
-            }
+        let Some(fetcher) = build_fetcher(&chain_name, &chain_config) else {
+            warn!("Unknown blockchain: {}, skipping...", chain_name);
+            continue;
         };
+        let (sender, receiver) = mpsc::channel::<ChainEvent>(chain_config.analyzer_queue_size.max(1));
+        // commit에 실패한 블록을 analyzer가 fetcher에게 다시 요청하는 채널
+        let (retry_sender, retry_receiver) = mpsc::unbounded_channel::<u64>();
+        analyzer_receivers.push((fetcher.chain_name().to_uppercase(), receiver, retry_sender));
+
+        analyzer_fetchers.insert(fetcher.chain_name().to_uppercase(), fetcher.clone());
+        head_tracker.register(fetcher.clone(), interval_duration);
+        backfill_scheduler.register(fetcher.clone(), sender.clone());
+        let detector = load_reorg_detector(&repository, fetcher.chain_name(), chain_config.reorg_depth).await;
+        let handle = tokio::spawn(crate::fetcher::runner::run_fetcher(fetcher, AnalyzerLink { sender, retry: retry_receiver }, start_block, interval_duration, detector, catchup, shutdown.clone()));
         
         fetcher_handles.push(handle);
     }
+
+    // 6.5. Track real chain heads for confirmation counting
+    let head_tracker = Arc::new(head_tracker);
+    worker_handles.extend(crate::tasks::run_chain_head_tracker(head_tracker.clone(), shutdown.clone()).await);
+
+    // 6.6. Run backfill jobs (재시작 전 미완료 작업 포함, live cursor와 별개)
+    let backfill_scheduler = Arc::new(backfill_scheduler);
+    worker_handles.push(tokio::spawn(crate::tasks::run_backfill_scheduler(backfill_scheduler.clone(), shutdown.clone())));
     
-    // 7. Initialize SQS Notifier (if configured)
-    let sqs_notifier = if let Some(notification_config) = &settings.notification {
-        match SqsNotifier::new(
-            notification_config.sqs_queue_url.clone(),
-            notification_config.aws_region.clone(),
-        ).await {
-            Ok(notifier) => {
-                info!("SQS Notifier initialized: {}", notification_config.sqs_queue_url);
-                Some(Arc::new(notifier))
+    // 7. Initialize notification sinks (if configured)
+    let publisher = if let Some(notification_config) = &settings.notification {
+        match crate::notification::build_publisher(notification_config).await {
+            Ok(Some(publisher)) => {
+                info!("Notifier initialized: {}", publisher.name());
+                Some(publisher)
+            }
+            Ok(None) => {
+                warn!("[notification] has no sinks configured, skipping");
+                None
             }
             Err(e) => {
-                warn!("Failed to initialize SQS Notifier: {}", e);
+                warn!("Failed to initialize notifier: {}", e);
                 None
             }
         }
     } else {
-        info!("SQS Notifier not configured, skipping");
+        info!("Notifier not configured, skipping");
         None
     };
 
+    // 7.5. Spawn notification outbox dispatcher (입금 알림 전송/재시도)
+    if let (Some(publisher), Some(notification_config)) = (&publisher, &settings.notification) {
+        let outbox_config = crate::tasks::OutboxDispatcherConfig {
+            poll_interval_secs: notification_config.outbox_poll_interval_secs,
+            batch_size: notification_config.outbox_batch_size,
+            retry_base_delay_secs: notification_config.outbox_retry_base_delay_secs,
+            max_retry_delay_secs: notification_config.outbox_max_retry_delay_secs,
+            max_attempts: notification_config.outbox_max_attempts,
+        };
+        worker_handles.push(tokio::spawn(crate::tasks::run_outbox_dispatcher(repository.clone(), publisher.clone(), outbox_config, shutdown.clone())));
+    }
+
     // 8. Spawn customer address sync task (if configured)
     if let Some(customer_sync_config) = &settings.customer_sync {
-        if let Some(kv_db_ref) = &kv_db {
-            info!("Starting customer address sync service...");
-            let sync_config = crate::tasks::CustomerSyncConfig {
-                sqs_queue_url: customer_sync_config.sqs_queue_url.clone(),
-                aws_region: customer_sync_config.aws_region.clone(),
-                batch_size: customer_sync_config.batch_size,
-                flush_interval_secs: customer_sync_config.flush_interval_secs,
-                cache_file_path: customer_sync_config.cache_file_path.clone(),
-            };
-            crate::tasks::run_customer_address_sync(kv_db_ref.clone(), sync_config).await;
-        } else {
-            warn!("Customer sync configured but no RocksDB available, skipping");
-        }
+        info!("Starting customer address sync service...");
+        let sync_config = crate::tasks::CustomerSyncConfig {
+            sqs_queue_url: customer_sync_config.sqs_queue_url.clone(),
+            aws_region: customer_sync_config.aws_region.clone(),
+            batch_size: customer_sync_config.batch_size,
+            flush_interval_secs: customer_sync_config.flush_interval_secs,
+            cache_file_path: customer_sync_config.cache_file_path.clone(),
+            visibility_timeout_secs: customer_sync_config.visibility_timeout_secs,
+            max_receive_count: customer_sync_config.max_receive_count,
+            retry_delay_secs: customer_sync_config.retry_delay_secs,
+            dlq_url: customer_sync_config.dlq_url.clone(),
+            poison_queue_url: customer_sync_config.poison_queue_url.clone(),
+        };
+        worker_handles.push(crate::tasks::run_customer_address_sync(address_index.clone(), Some(backfill_scheduler.clone()), sync_config, shutdown.clone()).await);
     }
 
     // 8.5. Spawn confirmation checker task (if configured)
@@ -221,8 +217,10 @@ async fn main() -> Result<(), AppError> {
         Some(tokio::spawn(crate::tasks::run_confirmation_checker(
             repository.clone(),
             chain_configs_map,
-            sqs_notifier.clone(),
+            publisher.clone(),
             checker_config,
+            head_tracker.clone(),
+            shutdown.clone(),
         )))
     } else {
         info!("Confirmation checker not configured, using defaults");
@@ -232,33 +230,112 @@ async fn main() -> Result<(), AppError> {
         Some(tokio::spawn(crate::tasks::run_confirmation_checker(
             repository.clone(),
             chain_configs_map,
-            sqs_notifier.clone(),
+            publisher.clone(),
             crate::tasks::ConfirmationCheckerConfig::default(),
+            head_tracker.clone(),
+            shutdown.clone(),
         )))
     };
 
-    // 9. Spawn analyzer
-    let analyzer_handle = tokio::spawn(analyzer::run_analyzer(
-        receiver,
-        repository.clone(),
-        kv_db,
-        sqs_notifier,
-        settings.get_chain_configs().into_iter().collect(),
-    ));
+    // 8.7. Spawn HTTP API (if configured)
+    if let Some(api_config) = &settings.api {
+        let api_state = crate::api::ApiState {
+            repository: repository.clone(),
+            address_index: address_index.clone(),
+            head_tracker: head_tracker.clone(),
+            backfill: backfill_scheduler.clone(),
+            settings: Arc::new(api_config.clone()),
+        };
+        worker_handles.push(tokio::spawn(crate::api::run_api_server(api_state, shutdown.clone())));
+    } else {
+        info!("HTTP API not configured, skipping");
+    }
+
+    // 8.8. Spawn /metrics, /healthz, /readyz endpoint (if configured)
+    if let Some(monitoring_config) = &settings.monitoring {
+        // 진행 위치와 주소 인덱스는 fetcher의 체인 이름으로 기록되므로 같은 이름으로 확인
+        let monitored_chains = chain_names(&settings.get_chain_configs());
+        let stall_timeouts = settings.get_chain_configs().into_iter()
+            .filter_map(|(name, c)| {
+                let timeout = Duration::from_secs(c.interval_secs * monitoring_config.stall_threshold_multiplier);
+                monitored_chains.get(&name.to_lowercase()).map(|chain| (chain.to_string(), timeout))
+            })
+            .collect();
+        let mut metric_chains: Vec<String> = monitored_chains.values().map(|chain| chain.to_string()).collect();
+        metric_chains.sort();
+        metric_chains.dedup();
+        let health = Arc::new(crate::api::HealthChecker::new(
+            repository.clone(),
+            address_index.clone(),
+            publisher.clone(),
+            stall_timeouts,
+            Duration::from_secs(monitoring_config.health_check_timeout_secs),
+        ));
+        worker_handles.push(tokio::spawn(crate::api::run_progress_sampler(health.clone(), shutdown.clone())));
+        worker_handles.push(tokio::spawn(crate::api::run_monitoring_server(monitoring_config.listen_addr.clone(), health, shutdown.clone())));
+        worker_handles.push(tokio::spawn(crate::tasks::run_address_metrics(
+            address_index.clone(),
+            metric_chains,
+            monitoring_config.address_metrics_interval_secs,
+            shutdown.clone(),
+        )));
+    } else {
+        info!("Monitoring not configured, skipping /metrics, /healthz, /readyz");
+    }
+
+    // 9. Spawn one analyzer worker per chain (repository, 주소 인덱스, 알림 핸들 공유)
+    info!("Token registry: {} asset(s) configured", settings.tokens.len());
+    let analyzer_context = Arc::new(analyzer::AnalyzerContext {
+        repository: repository.clone(),
+        address_index,
+        publisher,
+        chain_configs: settings.get_chain_configs().into_iter().collect(),
+        token_registry: analyzer::TokenRegistry::new(&settings.tokens, &chain_names(&settings.get_chain_configs())),
+        head_tracker,
+        fetchers: analyzer_fetchers,
+    });
+    let analyzer_handles: Vec<JoinHandle<()>> = analyzer_receivers.into_iter()
+        .map(|(chain_name, receiver, retry)| tokio::spawn(analyzer::run_analyzer(chain_name, receiver, retry, analyzer_context.clone(), shutdown.clone())))
+        .collect();
 
     // 11. Wait for shutdown signal
     shutdown_signal().await;
-    info!("Shutdown signal received. Waiting for tasks to finish...");
+    let timeout = Duration::from_secs(settings.shutdown.timeout_secs);
+    info!("Shutdown signal received. Draining in-flight work (timeout: {:?})...", timeout);
+    shutdown.cancel();
 
-    // 12. Gracefully wait for all fetchers
-    for handle in fetcher_handles {
-        let _ = handle.await;
-    }
-    let _ = analyzer_handle.await;
-    if let Some(handle) = confirmation_checker_handle {
-        let _ = handle.await;
+    // 12. fetcher는 새 블록 조회를 멈추고, analyzer는 처리 중인 블록을 commit하고,
+    //     customer sync는 버퍼를 flush하고, outbox는 전송 중인 배치를 마치고,
+    //     backfill은 실행 중인 작업의 진행 위치를 저장하고, HTTP 서버는 처리 중인 요청에 응답한 뒤 종료
+    worker_handles.extend(confirmation_checker_handle);
+    let drain = async {
+        for handle in fetcher_handles.into_iter().chain(analyzer_handles).chain(worker_handles) {
+            let _ = handle.await;
+        }
+    };
+    if tokio::time::timeout(timeout, drain).await.is_err() {
+        warn!("Shutdown timed out after {:?}, exiting with unfinished tasks (they resume from the last commit after restart)", timeout);
+        return Ok(());
     }
 
     info!("Application exited cleanly.");
     Ok(())
 }
+
+/// 저장된 최근 블록 해시로 ReorgDetector 생성 (재시작 후에도 reorg 감지 가능)
+async fn load_reorg_detector(repository: &Arc<RepositoryWrapper>, chain_name: &str, reorg_depth: u64) -> ReorgDetector {
+    if reorg_depth == 0 {
+        return ReorgDetector::new(0, Vec::new());
+    }
+
+    match repository.get_recent_block_hashes(chain_name, reorg_depth).await {
+        Ok(hashes) => {
+            info!("{} reorg detection enabled (depth: {}, restored {} block hashes)", chain_name, reorg_depth, hashes.len());
+            ReorgDetector::new(reorg_depth, hashes)
+        }
+        Err(e) => {
+            warn!("Failed to load recent block hashes for {}: {}", chain_name, e);
+            ReorgDetector::new(reorg_depth, Vec::new())
+        }
+    }
+}
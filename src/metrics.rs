@@ -0,0 +1,145 @@
+//! Prometheus 메트릭 (`/metrics`에서 노출)
+
+use crate::types::AppError;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec,
+    Encoder, HistogramTimer, HistogramVec, IntCounterVec, IntGaugeVec, TextEncoder,
+};
+use std::sync::LazyLock;
+
+// RPC 호출 / 블록 분석 시간 bucket (초)
+const LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+static LAST_PROCESSED_BLOCK: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!("xscanner_last_processed_block", "Last block committed by the analyzer", &["chain"])
+        .expect("register xscanner_last_processed_block")
+});
+
+static CHAIN_HEAD: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!("xscanner_chain_head", "Latest block number reported by the node", &["chain"])
+        .expect("register xscanner_chain_head")
+});
+
+static CHAIN_LAG: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!("xscanner_chain_lag_blocks", "Chain head minus last processed block", &["chain"])
+        .expect("register xscanner_chain_lag_blocks")
+});
+
+static BLOCKS_FETCHED: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("xscanner_blocks_fetched_total", "Blocks fetched from the node", &["chain"])
+        .expect("register xscanner_blocks_fetched_total")
+});
+
+static FETCH_ERRORS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("xscanner_fetch_errors_total", "Block fetch failures by error type", &["chain", "kind"])
+        .expect("register xscanner_fetch_errors_total")
+});
+
+static DEPOSITS_DETECTED: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("xscanner_deposits_detected_total", "Deposits saved as detected", &["chain"])
+        .expect("register xscanner_deposits_detected_total")
+});
+
+static DEPOSITS_CONFIRMED: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("xscanner_deposits_confirmed_total", "Deposits transitioned to confirmed", &["chain"])
+        .expect("register xscanner_deposits_confirmed_total")
+});
+
+static NOTIFICATION_FAILURES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("xscanner_notification_failures_total", "Failed notification deliveries by sink (sqs, webhook, kafka, file)", &["sink"])
+        .expect("register xscanner_notification_failures_total")
+});
+
+static CUSTOMER_SYNC_MESSAGES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("xscanner_customer_sync_messages_total", "Customer address sync messages by result", &["result"])
+        .expect("register xscanner_customer_sync_messages_total")
+});
+
+static RPC_LATENCY: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!("xscanner_rpc_latency_seconds", "Node RPC latency", &["client", "method"], LATENCY_BUCKETS.to_vec())
+        .expect("register xscanner_rpc_latency_seconds")
+});
+
+static BLOCK_ANALYSIS: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!("xscanner_block_analysis_seconds", "Time to analyze a block and store its deposits", &["chain"], LATENCY_BUCKETS.to_vec())
+        .expect("register xscanner_block_analysis_seconds")
+});
+
+static ANALYZER_BACKLOG: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!("xscanner_analyzer_backlog", "Events waiting in the chain's analyzer channel", &["chain"])
+        .expect("register xscanner_analyzer_backlog")
+});
+
+static MONITORED_ADDRESSES: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!("xscanner_monitored_addresses", "Addresses in the monitored address cache", &["chain"])
+        .expect("register xscanner_monitored_addresses")
+});
+
+pub fn set_last_processed_block(chain: &str, block_number: u64) {
+    LAST_PROCESSED_BLOCK.with_label_values(&[chain]).set(block_number as i64);
+    refresh_lag(chain);
+}
+
+pub fn set_chain_head(chain: &str, block_number: u64) {
+    CHAIN_HEAD.with_label_values(&[chain]).set(block_number as i64);
+    refresh_lag(chain);
+}
+
+// head와 last processed block이 모두 기록된 뒤에만 lag 계산
+fn refresh_lag(chain: &str) {
+    let head = CHAIN_HEAD.with_label_values(&[chain]).get();
+    let last = LAST_PROCESSED_BLOCK.with_label_values(&[chain]).get();
+    if head > 0 && last > 0 {
+        CHAIN_LAG.with_label_values(&[chain]).set((head - last).max(0));
+    }
+}
+
+pub fn inc_blocks_fetched(chain: &str) {
+    BLOCKS_FETCHED.with_label_values(&[chain]).inc();
+}
+
+pub fn inc_fetch_error(chain: &str, error: &AppError) {
+    FETCH_ERRORS.with_label_values(&[chain, error.kind()]).inc();
+}
+
+pub fn inc_deposits_detected(chain: &str) {
+    DEPOSITS_DETECTED.with_label_values(&[chain]).inc();
+}
+
+pub fn inc_deposits_confirmed(chain: &str) {
+    DEPOSITS_CONFIRMED.with_label_values(&[chain]).inc();
+}
+
+pub fn inc_notification_failure(sink: &str) {
+    NOTIFICATION_FAILURES.with_label_values(&[sink]).inc();
+}
+
+pub fn inc_customer_sync_messages(result: &str, count: usize) {
+    CUSTOMER_SYNC_MESSAGES.with_label_values(&[result]).inc_by(count as u64);
+}
+
+/// RPC 호출 시간 측정 - 반환된 timer가 drop될 때 기록
+pub fn rpc_timer(client: &str, method: &str) -> HistogramTimer {
+    RPC_LATENCY.with_label_values(&[client, method]).start_timer()
+}
+
+pub fn observe_block_analysis(chain: &str, seconds: f64) {
+    BLOCK_ANALYSIS.with_label_values(&[chain]).observe(seconds);
+}
+
+pub fn set_analyzer_backlog(chain: &str, pending: usize) {
+    ANALYZER_BACKLOG.with_label_values(&[chain]).set(pending as i64);
+}
+
+pub fn set_monitored_addresses(chain: &str, count: usize) {
+    MONITORED_ADDRESSES.with_label_values(&[chain]).set(count as i64);
+}
+
+/// Prometheus text format으로 모든 메트릭 출력
+pub fn render() -> Result<String, String> {
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&prometheus::gather(), &mut buffer)
+        .map_err(|e| format!("Failed to encode metrics: {}", e))?;
+    String::from_utf8(buffer).map_err(|e| format!("Metrics are not valid UTF-8: {}", e))
+}
@@ -0,0 +1,383 @@
+use ed25519_dalek::{Signer, SigningKey};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::SigningSettings;
+use super::notifier::Notifier;
+use super::sqs_client::DepositEvent;
+
+/// envelope 형식 버전 (필드 의미가 바뀌면 올림)
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// 모든 입금 이벤트를 감싸는 envelope
+///
+/// 이벤트 필드는 기존 형식 그대로 최상위에 펼쳐서 보낸다 (`event` 태그 포함).
+/// 서명 대상은 `signature`를 제외한 envelope의 canonical JSON (키 정렬, 공백 없음)이다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    pub event_id: String,
+    pub schema_version: u32,
+    pub emitted_at: u64, // unix epoch milliseconds
+    pub scanner_instance: String,
+    #[serde(flatten)]
+    pub event: DepositEvent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<EnvelopeSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvelopeSignature {
+    pub algorithm: String, // "hmac-sha256" | "ed25519"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    pub value: String, // hex
+}
+
+impl EventEnvelope {
+    /// 서명 대상 바이트 (signature 제외, 키 정렬 JSON)
+    pub fn signing_input(&self) -> Result<Vec<u8>, String> {
+        let mut value = serde_json::to_value(self)
+            .map_err(|e| format!("Failed to serialize envelope: {}", e))?;
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("signature");
+        }
+        serde_json::to_vec(&value).map_err(|e| format!("Failed to serialize envelope: {}", e))
+    }
+
+    /// FIFO 큐 / 파티션 키용 그룹 (같은 입금의 이벤트는 같은 그룹)
+    pub fn group_key(&self) -> String {
+        format!("{}:{}", self.event.chain(), self.event.tx_hash())
+    }
+}
+
+/// 이벤트 ID (chain + tx_hash + output index + event type + block hash의 SHA-256)
+///
+/// 재전송되어도 같은 값이므로 consumer는 event_id로 중복을 걸러낼 수 있다.
+/// orphaned 입금이 다른 블록에 다시 포함되면 block hash가 달라져 새 ID가 된다 (블록 해시가 없으면 생략).
+pub fn event_id(event: &DepositEvent) -> String {
+    let mut input = format!(
+        "{}:{}:{}:{}",
+        event.chain(),
+        event.tx_hash(),
+        event.output_index(),
+        event.event_type()
+    );
+    if let Some(block_hash) = event.block_hash() {
+        input.push(':');
+        input.push_str(&block_hash.to_lowercase());
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(input);
+    hex::encode(hasher.finalize())
+}
+
+/// envelope 서명 키
+pub enum EnvelopeSigner {
+    HmacSha256 { key: Vec<u8>, key_id: Option<String> },
+    Ed25519 { key: SigningKey, key_id: Option<String> },
+}
+
+impl EnvelopeSigner {
+    pub fn from_settings(settings: &SigningSettings) -> Result<Self, String> {
+        match settings.algorithm.as_str() {
+            "hmac-sha256" => Ok(EnvelopeSigner::HmacSha256 {
+                key: settings.key.as_bytes().to_vec(),
+                key_id: settings.key_id.clone(),
+            }),
+            "ed25519" => {
+                // 32바이트 seed (hex)
+                let seed: [u8; 32] = hex::decode(settings.key.trim_start_matches("0x"))
+                    .map_err(|e| format!("Invalid ed25519 signing key: {}", e))?
+                    .try_into()
+                    .map_err(|_| "ed25519 signing key must be 32 bytes".to_string())?;
+                Ok(EnvelopeSigner::Ed25519 {
+                    key: SigningKey::from_bytes(&seed),
+                    key_id: settings.key_id.clone(),
+                })
+            }
+            other => Err(format!("Unknown signing algorithm: {}", other)),
+        }
+    }
+
+    fn sign(&self, input: &[u8]) -> Result<EnvelopeSignature, String> {
+        match self {
+            EnvelopeSigner::HmacSha256 { key, key_id } => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(key)
+                    .map_err(|e| format!("Invalid HMAC key: {}", e))?;
+                mac.update(input);
+                Ok(EnvelopeSignature {
+                    algorithm: "hmac-sha256".to_string(),
+                    key_id: key_id.clone(),
+                    value: hex::encode(mac.finalize().into_bytes()),
+                })
+            }
+            EnvelopeSigner::Ed25519 { key, key_id } => Ok(EnvelopeSignature {
+                algorithm: "ed25519".to_string(),
+                key_id: key_id.clone(),
+                value: hex::encode(key.sign(input).to_bytes()),
+            }),
+        }
+    }
+}
+
+/// 이벤트를 envelope로 감싸(서명 포함) 설정된 sink로 전달
+///
+/// sink마다 outbox 전송 상태를 따로 관리하므로 sink는 키(`sinks()`)로 구분한다.
+/// 키는 sink 이름이고, 같은 종류의 sink가 여러 개면 설정 순서대로 `webhook`, `webhook#2`, ...가 된다.
+pub struct EventPublisher {
+    sinks: Vec<(String, Arc<dyn Notifier>)>,
+    scanner_instance: String,
+    signer: Option<EnvelopeSigner>,
+}
+
+impl EventPublisher {
+    pub fn new(sinks: Vec<Arc<dyn Notifier>>, scanner_instance: String, signer: Option<EnvelopeSigner>) -> Self {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let sinks = sinks.into_iter()
+            .map(|sink| {
+                let count = counts.entry(sink.name().to_string()).or_default();
+                *count += 1;
+                let key = if *count == 1 { sink.name().to_string() } else { format!("{}#{}", sink.name(), count) };
+                (key, sink)
+            })
+            .collect();
+        Self { sinks, scanner_instance, signer }
+    }
+
+    /// 로그용 이름 (sink 키 목록)
+    pub fn name(&self) -> String {
+        self.sinks().join(",")
+    }
+
+    /// outbox 전송 상태를 기록하는 sink 키 (설정 순서)
+    pub fn sinks(&self) -> Vec<String> {
+        self.sinks.iter().map(|(key, _)| key.clone()).collect()
+    }
+
+    pub async fn health_check(&self) -> Result<(), String> {
+        let mut failures = Vec::new();
+        for (key, sink) in &self.sinks {
+            if let Err(e) = sink.health_check().await {
+                failures.push(format!("{}: {}", key, e));
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("; "))
+        }
+    }
+
+    pub fn seal(&self, event: DepositEvent) -> Result<EventEnvelope, String> {
+        let emitted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut envelope = EventEnvelope {
+            event_id: event_id(&event),
+            schema_version: SCHEMA_VERSION,
+            emitted_at,
+            scanner_instance: self.scanner_instance.clone(),
+            event,
+            signature: None,
+        };
+
+        if let Some(signer) = &self.signer {
+            envelope.signature = Some(signer.sign(&envelope.signing_input()?)?);
+        }
+
+        Ok(envelope)
+    }
+
+    /// `sink` 키의 sink 하나로 전달 (실패는 `xscanner_notification_failures_total`에 기록)
+    pub async fn publish_to(&self, sink: &str, event: DepositEvent) -> Result<(), String> {
+        let (_, notifier) = self.sinks.iter()
+            .find(|(key, _)| key == sink)
+            .ok_or_else(|| format!("Unknown notification sink: {}", sink))?;
+        let envelope = self.seal(event)?;
+
+        notifier.notify(&envelope).await.inspect_err(|_| {
+            crate::metrics::inc_notification_failure(notifier.name());
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AssetInfo;
+    use async_trait::async_trait;
+    use ed25519_dalek::{Signature, Verifier};
+
+    struct NullNotifier;
+
+    #[async_trait]
+    impl Notifier for NullNotifier {
+        fn name(&self) -> &str {
+            "null"
+        }
+
+        async fn notify(&self, _envelope: &EventEnvelope) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    fn detected(output_index: u32, block_hash: Option<&str>) -> DepositEvent {
+        DepositEvent::DepositDetected {
+            address: "0xdeposit".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            account_id: Some("account-1".to_string()),
+            chain: "ETHEREUM".to_string(),
+            tx_hash: "0xabc".to_string(),
+            output_index,
+            amount: "1000".to_string(),
+            asset: AssetInfo::native("ETH", 18),
+            block_number: 100,
+            block_hash: block_hash.map(str::to_string),
+            confirmations: 1,
+        }
+    }
+
+    fn confirmed(block_hash: Option<&str>) -> DepositEvent {
+        DepositEvent::DepositConfirmed {
+            address: "0xdeposit".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            account_id: Some("account-1".to_string()),
+            chain: "ETHEREUM".to_string(),
+            tx_hash: "0xabc".to_string(),
+            output_index: 0,
+            amount: "1000".to_string(),
+            asset: AssetInfo::native("ETH", 18),
+            block_number: 100,
+            block_hash: block_hash.map(str::to_string),
+            confirmations: 12,
+        }
+    }
+
+    fn publisher(signer: Option<EnvelopeSigner>) -> EventPublisher {
+        EventPublisher::new(vec![Arc::new(NullNotifier)], "scanner-1".to_string(), signer)
+    }
+
+    fn signer(algorithm: &str, key: &str) -> EnvelopeSigner {
+        EnvelopeSigner::from_settings(&SigningSettings {
+            algorithm: algorithm.to_string(),
+            key: key.to_string(),
+            key_id: Some("key-1".to_string()),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn event_id_is_pinned() {
+        // consumer가 저장한 event_id와 호환되도록 입력 형식을 고정한다
+        assert_eq!(
+            event_id(&detected(0, None)),
+            "08299b388d589a25b240ca519cbe5afd24115d24c16c0977a0878e39d3ced90d"
+        );
+        assert_eq!(
+            event_id(&detected(0, Some("0xBLOCK1"))),
+            "2d8ff54fb2a171e0196b499bb6843c9a04e6dfcc0171ba9b7deb5dcabd451dcc"
+        );
+    }
+
+    #[test]
+    fn event_id_is_stable_across_resends() {
+        let event = detected(0, Some("0xblock1"));
+        let first = publisher(None).seal(event.clone()).unwrap();
+        let second = publisher(None).seal(event).unwrap();
+        assert_eq!(first.event_id, second.event_id);
+
+        // confirmations는 ID에 포함되지 않는다
+        let mut later = first.event.clone();
+        if let DepositEvent::DepositDetected { confirmations, .. } = &mut later {
+            *confirmations += 1;
+        }
+        assert_eq!(event_id(&later), first.event_id);
+    }
+
+    #[test]
+    fn event_id_distinguishes_deposits_and_events() {
+        let base = event_id(&detected(0, Some("0xblock1")));
+        assert_ne!(base, event_id(&detected(1, Some("0xblock1"))));
+        assert_ne!(base, event_id(&confirmed(Some("0xblock1"))));
+        // 다른 블록에 다시 포함된 입금
+        assert_ne!(base, event_id(&detected(0, Some("0xblock2"))));
+    }
+
+    #[test]
+    fn signing_input_excludes_signature_and_sorts_keys() {
+        let envelope = publisher(Some(signer("hmac-sha256", "secret"))).seal(detected(0, None)).unwrap();
+        assert!(envelope.signature.is_some());
+
+        let mut unsigned = envelope.clone();
+        unsigned.signature = None;
+        assert_eq!(envelope.signing_input().unwrap(), unsigned.signing_input().unwrap());
+
+        let input = String::from_utf8(envelope.signing_input().unwrap()).unwrap();
+        assert!(!input.contains("signature"));
+        assert!(!input.contains(' '));
+        let keys: Vec<String> = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&input)
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+        assert!(input.starts_with("{\"account_id\":"));
+    }
+
+    #[test]
+    fn signing_input_survives_round_trip() {
+        // consumer가 받은 JSON을 다시 파싱해도 같은 서명 입력이 나와야 한다
+        let envelope = publisher(Some(signer("hmac-sha256", "secret"))).seal(detected(0, Some("0xblock1"))).unwrap();
+        let wire = serde_json::to_string(&envelope).unwrap();
+        let received: EventEnvelope = serde_json::from_str(&wire).unwrap();
+        assert_eq!(received.signing_input().unwrap(), envelope.signing_input().unwrap());
+    }
+
+    #[test]
+    fn hmac_signature_verifies() {
+        let envelope = publisher(Some(signer("hmac-sha256", "secret"))).seal(detected(0, None)).unwrap();
+        let signature = envelope.signature.as_ref().unwrap();
+        assert_eq!(signature.algorithm, "hmac-sha256");
+        assert_eq!(signature.key_id.as_deref(), Some("key-1"));
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(&envelope.signing_input().unwrap());
+        mac.verify_slice(&hex::decode(&signature.value).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn ed25519_signature_verifies() {
+        let seed = "0x".to_string() + &"07".repeat(32);
+        let envelope = publisher(Some(signer("ed25519", &seed))).seal(detected(0, None)).unwrap();
+        let signature = envelope.signature.as_ref().unwrap();
+        assert_eq!(signature.algorithm, "ed25519");
+
+        let verifying_key = SigningKey::from_bytes(&[7u8; 32]).verifying_key();
+        let bytes: [u8; 64] = hex::decode(&signature.value).unwrap().try_into().unwrap();
+        verifying_key
+            .verify(&envelope.signing_input().unwrap(), &Signature::from_bytes(&bytes))
+            .unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_signing_settings() {
+        let settings = |algorithm: &str, key: &str| SigningSettings {
+            algorithm: algorithm.to_string(),
+            key: key.to_string(),
+            key_id: None,
+        };
+        assert!(EnvelopeSigner::from_settings(&settings("rsa", "secret")).is_err());
+        assert!(EnvelopeSigner::from_settings(&settings("ed25519", "not-hex")).is_err());
+        assert!(EnvelopeSigner::from_settings(&settings("ed25519", "0707")).is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+use super::notifier::Notifier;
+use super::envelope::EventEnvelope;
+
+/// 입금 이벤트를 JSONL 파일에 추가 (한 줄에 이벤트 하나)
+pub struct FileNotifier {
+    path: String,
+    // 여러 이벤트의 줄이 섞이지 않도록 쓰기 직렬화
+    lock: Mutex<()>,
+}
+
+impl FileNotifier {
+    pub fn new(path: String) -> Self {
+        Self { path, lock: Mutex::new(()) }
+    }
+}
+
+#[async_trait]
+impl Notifier for FileNotifier {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    async fn notify(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        let mut line = serde_json::to_string(envelope)
+            .map_err(|e| format!("Failed to serialize event: {}", e))?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| format!("Failed to open {}: {}", self.path, e))?;
+
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", self.path, e))?;
+        file.sync_data()
+            .await
+            .map_err(|e| format!("Failed to sync {}: {}", self.path, e))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+
+use super::notifier::Notifier;
+use super::envelope::EventEnvelope;
+
+#[cfg(feature = "kafka")]
+use rdkafka::config::ClientConfig;
+#[cfg(feature = "kafka")]
+use rdkafka::message::{Header, OwnedHeaders};
+#[cfg(feature = "kafka")]
+use rdkafka::producer::{FutureProducer, FutureRecord};
+#[cfg(feature = "kafka")]
+use std::time::Duration;
+
+/// Kafka 프로토콜(Kafka, Redpanda, MSK 등) topic으로 입금 이벤트 전달
+///
+/// 메시지 키는 `{chain}:{tx_hash}` - 같은 입금의 이벤트는 같은 파티션에서 순서대로 전달된다.
+pub struct KafkaNotifier {
+    #[cfg(feature = "kafka")]
+    producer: FutureProducer,
+    #[cfg(feature = "kafka")]
+    topic: String,
+    #[cfg(feature = "kafka")]
+    timeout: Duration,
+}
+
+#[cfg(feature = "kafka")]
+impl KafkaNotifier {
+    pub fn new(brokers: &str, topic: String, timeout_secs: u64) -> Result<Self, String> {
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("message.timeout.ms", (timeout_secs * 1000).to_string())
+            .set("enable.idempotence", "true")
+            .create()
+            .map_err(|e| format!("Failed to create Kafka producer: {}", e))?;
+
+        Ok(Self { producer, topic, timeout: Duration::from_secs(timeout_secs) })
+    }
+}
+
+#[cfg(not(feature = "kafka"))]
+impl KafkaNotifier {
+    pub fn new(_brokers: &str, _topic: String, _timeout_secs: u64) -> Result<Self, String> {
+        Err("Kafka sink requires the `kafka` feature".to_string())
+    }
+}
+
+#[async_trait]
+impl Notifier for KafkaNotifier {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    #[cfg(feature = "kafka")]
+    async fn notify(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        let body = serde_json::to_string(envelope)
+            .map_err(|e| format!("Failed to serialize event: {}", e))?;
+        let key = envelope.group_key();
+        let headers = OwnedHeaders::new().insert(Header {
+            key: "event_id",
+            value: Some(envelope.event_id.as_str()),
+        });
+
+        self.producer
+            .send(FutureRecord::to(&self.topic).key(&key).payload(&body).headers(headers), self.timeout)
+            .await
+            .map_err(|(e, _)| format!("Failed to produce Kafka message: {}", e))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "kafka"))]
+    async fn notify(&self, _envelope: &EventEnvelope) -> Result<(), String> {
+        Err("Kafka sink requires the `kafka` feature".to_string())
+    }
+}
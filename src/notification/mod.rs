@@ -1,3 +1,10 @@
 pub mod sqs_client;
+pub mod notifier;
+pub mod envelope;
+pub mod webhook;
+pub mod kafka;
+pub mod file_sink;
 
 pub use sqs_client::*;
+pub use notifier::build_publisher;
+pub use envelope::EventPublisher;
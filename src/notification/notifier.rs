@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use log::info;
+use std::sync::Arc;
+
+use crate::config::{NotificationSettings, NotificationSinkSettings};
+use super::file_sink::FileNotifier;
+use super::kafka::KafkaNotifier;
+use super::envelope::{EnvelopeSigner, EventEnvelope, EventPublisher};
+use super::sqs_client::SqsNotifier;
+use super::webhook::WebhookNotifier;
+
+/// 입금 이벤트 envelope 전달 인터페이스 (SQS, webhook, Kafka, 파일 등)
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// 로그용 sink 이름
+    fn name(&self) -> &str;
+
+    async fn notify(&self, envelope: &EventEnvelope) -> Result<(), String>;
+
+    /// sink 연결 확인 (readiness probe)
+    async fn health_check(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// 설정된 sink와 서명 키로 EventPublisher 생성 (sink가 없으면 None)
+pub async fn build_publisher(settings: &NotificationSettings) -> Result<Option<Arc<EventPublisher>>, String> {
+    let mut sinks: Vec<Arc<dyn Notifier>> = Vec::new();
+
+    for sink in settings.all_sinks() {
+        let notifier: Arc<dyn Notifier> = match sink {
+            NotificationSinkSettings::Sqs { queue_url, aws_region } => {
+                info!("[Notifier] SQS sink: {}", queue_url);
+                Arc::new(SqsNotifier::new(queue_url, aws_region).await?)
+            }
+            NotificationSinkSettings::Webhook { url, secret, timeout_secs } => {
+                info!("[Notifier] Webhook sink: {}", url);
+                Arc::new(WebhookNotifier::new(url, secret, timeout_secs)?)
+            }
+            NotificationSinkSettings::Kafka { brokers, topic, timeout_secs } => {
+                info!("[Notifier] Kafka sink: {} (topic: {})", brokers, topic);
+                Arc::new(KafkaNotifier::new(&brokers, topic, timeout_secs)?)
+            }
+            NotificationSinkSettings::File { path } => {
+                info!("[Notifier] File sink: {}", path);
+                Arc::new(FileNotifier::new(path))
+            }
+        };
+        sinks.push(notifier);
+    }
+
+    if sinks.is_empty() {
+        return Ok(None);
+    }
+
+    let signer = settings.signing.as_ref()
+        .map(EnvelopeSigner::from_settings)
+        .transpose()?;
+
+    Ok(Some(Arc::new(EventPublisher::new(sinks, settings.scanner_instance(), signer))))
+}
@@ -1,9 +1,17 @@
+use async_trait::async_trait;
 use aws_sdk_sqs::Client as SqsClient;
+use aws_sdk_sqs::types::{MessageAttributeValue, QueueAttributeName};
 use serde::{Serialize, Deserialize};
 use log::{info, error};
 
-#[derive(Debug, Serialize, Deserialize)]
+use super::envelope::EventEnvelope;
+use super::notifier::Notifier;
+
+use crate::types::AssetInfo;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "event")]
+#[allow(clippy::enum_variant_names)] // variant names are the wire-format event names
 pub enum DepositEvent {
     DepositDetected {
         address: String,
@@ -11,8 +19,15 @@ pub enum DepositEvent {
         account_id: Option<String>, // None for Omnibus (Master) Address
         chain: String,
         tx_hash: String,
+        #[serde(default)]
+        output_index: u32,
         amount: String,
+        #[serde(flatten)]
+        asset: AssetInfo,
         block_number: u64,
+        /// 입금이 포함된 블록 해시 (reorg 후 다시 포함되면 달라짐)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_hash: Option<String>,
         confirmations: u64,
     },
     DepositConfirmed {
@@ -21,10 +36,77 @@ pub enum DepositEvent {
         account_id: Option<String>, // None for Omnibus (Master) Address
         chain: String,
         tx_hash: String,
+        #[serde(default)]
+        output_index: u32,
         amount: String,
+        #[serde(flatten)]
+        asset: AssetInfo,
         block_number: u64,
+        /// 입금이 포함된 블록 해시 (reorg 후 다시 포함되면 달라짐)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_hash: Option<String>,
         confirmations: u64,
     },
+    /// Reorg로 canonical 체인에서 사라진 입금 (Backend는 credit을 되돌려야 함)
+    DepositOrphaned {
+        address: String,
+        wallet_id: String,
+        account_id: Option<String>, // None for Omnibus (Master) Address
+        chain: String,
+        tx_hash: String,
+        #[serde(default)]
+        output_index: u32,
+        amount: String,
+        #[serde(flatten)]
+        asset: AssetInfo,
+        block_number: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block_hash: Option<String>,
+    },
+}
+
+impl DepositEvent {
+    /// wire format의 이벤트 이름
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DepositEvent::DepositDetected { .. } => "DepositDetected",
+            DepositEvent::DepositConfirmed { .. } => "DepositConfirmed",
+            DepositEvent::DepositOrphaned { .. } => "DepositOrphaned",
+        }
+    }
+
+    pub fn chain(&self) -> &str {
+        match self {
+            DepositEvent::DepositDetected { chain, .. }
+            | DepositEvent::DepositConfirmed { chain, .. }
+            | DepositEvent::DepositOrphaned { chain, .. } => chain,
+        }
+    }
+
+    /// 트랜잭션 내 입금 위치 (UTXO output index / 로그 위치)
+    pub fn output_index(&self) -> u32 {
+        match self {
+            DepositEvent::DepositDetected { output_index, .. }
+            | DepositEvent::DepositConfirmed { output_index, .. }
+            | DepositEvent::DepositOrphaned { output_index, .. } => *output_index,
+        }
+    }
+
+    pub fn tx_hash(&self) -> &str {
+        match self {
+            DepositEvent::DepositDetected { tx_hash, .. }
+            | DepositEvent::DepositConfirmed { tx_hash, .. }
+            | DepositEvent::DepositOrphaned { tx_hash, .. } => tx_hash,
+        }
+    }
+
+    pub fn block_hash(&self) -> Option<&str> {
+        match self {
+            DepositEvent::DepositDetected { block_hash, .. }
+            | DepositEvent::DepositConfirmed { block_hash, .. }
+            | DepositEvent::DepositOrphaned { block_hash, .. } => block_hash.as_deref(),
+        }
+    }
 }
 
 pub struct SqsNotifier {
@@ -59,16 +141,29 @@ impl SqsNotifier {
         Ok(Self { client, queue_url })
     }
 
-    pub async fn send_deposit_event(&self, event: DepositEvent) -> Result<(), String> {
-        let message_body = serde_json::to_string(&event)
+    /// envelope 전송 (FIFO 큐(.fifo)면 event_id로 중복 제거, 입금 단위로 순서 보장)
+    pub async fn send_deposit_event(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        let message_body = serde_json::to_string(envelope)
             .map_err(|e| format!("Failed to serialize event: {}", e))?;
 
         info!("Sending SQS message: {}", message_body);
 
-        self.client
+        let mut request = self.client
             .send_message()
             .queue_url(&self.queue_url)
             .message_body(message_body)
+            .message_attributes("event_type", string_attribute(envelope.event.event_type())?)
+            .message_attributes("event_id", string_attribute(&envelope.event_id)?)
+            .message_attributes("schema_version", string_attribute(&envelope.schema_version.to_string())?)
+            .message_attributes("chain", string_attribute(envelope.event.chain())?);
+
+        if self.queue_url.ends_with(".fifo") {
+            request = request
+                .message_deduplication_id(&envelope.event_id)
+                .message_group_id(envelope.group_key());
+        }
+
+        request
             .send()
             .await
             .map_err(|e| format!("Failed to send SQS message: {}", e))?;
@@ -76,53 +171,34 @@ impl SqsNotifier {
         info!("✅ SQS message sent successfully");
         Ok(())
     }
+}
 
-    pub async fn send_deposit_detected(
-        &self,
-        address: String,
-        wallet_id: String,
-        account_id: Option<String>,
-        chain: String,
-        tx_hash: String,
-        amount: String,
-        block_number: u64,
-    ) -> Result<(), String> {
-        let event = DepositEvent::DepositDetected {
-            address,
-            wallet_id,
-            account_id,
-            chain,
-            tx_hash,
-            amount,
-            block_number,
-            confirmations: 1,
-        };
-
-        self.send_deposit_event(event).await
+fn string_attribute(value: &str) -> Result<MessageAttributeValue, String> {
+    MessageAttributeValue::builder()
+        .data_type("String")
+        .string_value(value)
+        .build()
+        .map_err(|e| format!("Failed to build message attribute: {}", e))
+}
+
+#[async_trait]
+impl Notifier for SqsNotifier {
+    fn name(&self) -> &str {
+        "sqs"
     }
 
-    pub async fn send_deposit_confirmed(
-        &self,
-        address: String,
-        wallet_id: String,
-        account_id: Option<String>,
-        chain: String,
-        tx_hash: String,
-        amount: String,
-        block_number: u64,
-        confirmations: u64,
-    ) -> Result<(), String> {
-        let event = DepositEvent::DepositConfirmed {
-            address,
-            wallet_id,
-            account_id,
-            chain,
-            tx_hash,
-            amount,
-            block_number,
-            confirmations,
-        };
-
-        self.send_deposit_event(event).await
+    async fn notify(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        self.send_deposit_event(envelope).await
+    }
+
+    async fn health_check(&self) -> Result<(), String> {
+        self.client
+            .get_queue_attributes()
+            .queue_url(&self.queue_url)
+            .attribute_names(QueueAttributeName::QueueArn)
+            .send()
+            .await
+            .map_err(|e| format!("SQS queue is unreachable: {}", e))?;
+        Ok(())
     }
 }
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use log::info;
+use reqwest::Client as HttpClient;
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::notifier::Notifier;
+use super::envelope::EventEnvelope;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const SIGNATURE_HEADER: &str = "X-Xscanner-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Xscanner-Timestamp";
+pub const EVENT_ID_HEADER: &str = "X-Xscanner-Event-Id";
+
+/// HMAC 서명된 HTTP webhook으로 입금 이벤트 전달
+///
+/// 서명: `sha256=hex(HMAC-SHA256(secret, "{timestamp}.{body}"))`
+pub struct WebhookNotifier {
+    client: HttpClient,
+    url: String,
+    secret: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, secret: String, timeout_secs: u64) -> Result<Self, String> {
+        let client = HttpClient::builder()
+            .timeout(Duration::from_secs(timeout_secs))
+            .build()
+            .map_err(|e| format!("Failed to build webhook client: {}", e))?;
+
+        Ok(Self { client, url, secret })
+    }
+
+    fn sign(&self, timestamp: u64, body: &str) -> Result<String, String> {
+        let mut mac = HmacSha256::new_from_slice(self.secret.as_bytes())
+            .map_err(|e| format!("Invalid webhook secret: {}", e))?;
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body.as_bytes());
+        Ok(format!("sha256={}", hex::encode(mac.finalize().into_bytes())))
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn notify(&self, envelope: &EventEnvelope) -> Result<(), String> {
+        let body = serde_json::to_string(envelope)
+            .map_err(|e| format!("Failed to serialize event: {}", e))?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let signature = self.sign(timestamp, &body)?;
+
+        let response = self.client
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_ID_HEADER, &envelope.event_id)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send webhook: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Webhook returned {}", status));
+        }
+
+        info!("✅ Webhook delivered: {} {}", envelope.event.event_type(), envelope.event_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,402 @@
+use crate::config::Settings;
+use crate::respository::bloom::BloomFilter;
+use crate::respository::RepositoryWrapper;
+use crate::types::AppError;
+use async_trait::async_trait;
+use log::info;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+#[cfg(feature = "rocksdb-backend")]
+use rocksdb::DB;
+
+/// 관리 대상 주소의 메타데이터
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AddressMetadata {
+    pub wallet_id: String,
+    pub account_id: Option<String>, // None for Omnibus (Master) Address
+}
+
+/// 인덱스에 등록할 관리 대상 주소
+#[derive(Debug, Clone)]
+pub struct MonitoredAddress {
+    pub address: String,
+    pub chain_name: String,
+    pub metadata: AddressMetadata,
+}
+
+/// 관리 대상 주소 조회 인터페이스
+///
+/// 주소/체인 이름은 구현체에서 소문자로 정규화한다.
+#[async_trait]
+pub trait AddressIndex: Send + Sync {
+    /// 주소의 메타데이터 조회 (관리 대상이 아니면 None)
+    async fn lookup(&self, address: &str, chain_name: &str) -> Result<Option<AddressMetadata>, AppError>;
+
+    /// 여러 주소를 한 번에 조회, 관리 대상인 주소만 (입력 주소 -> 메타데이터)으로 반환
+    async fn lookup_batch(&self, addresses: &[String], chain_name: &str) -> Result<HashMap<String, AddressMetadata>, AppError> {
+        let mut found = HashMap::new();
+        for address in addresses {
+            if let Some(metadata) = self.lookup(address, chain_name).await? {
+                found.insert(address.clone(), metadata);
+            }
+        }
+        Ok(found)
+    }
+
+    /// 주소 등록 (이미 있으면 메타데이터 갱신), 등록한 주소 수 반환
+    async fn insert(&self, entries: &[MonitoredAddress]) -> Result<usize, AppError>;
+
+    /// 주소 삭제, 삭제된 주소가 있었는지 반환
+    async fn remove(&self, address: &str, chain_name: &str) -> Result<bool, AppError>;
+
+    /// wallet_id에 속한 모든 주소 삭제 (지갑 비활성화), 삭제된 주소 수 반환
+    async fn remove_wallet(&self, wallet_id: &str) -> Result<usize, AppError>;
+
+    /// 체인별 관리 대상 주소 수
+    async fn count(&self, chain_name: &str) -> Result<usize, AppError>;
+
+    /// 관리 대상 주소 목록 (chain_name이 None이면 전체, 체인/주소 오름차순)
+    async fn list(&self, chain_name: Option<&str>) -> Result<Vec<MonitoredAddress>, AppError>;
+
+    /// 인덱스 저장소 연결 확인 (readiness probe)
+    async fn health_check(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+/// 인덱스 키 (chain_name:address, 소문자 정규화)
+pub(crate) fn address_key(address: &str, chain_name: &str) -> String {
+    format!("{}:{}", chain_name.to_lowercase(), address.to_lowercase())
+}
+
+/// 메모리 기반 AddressIndex (memory_db 모드, 테스트용)
+#[derive(Clone, Default)]
+pub struct MemoryAddressIndex {
+    // chain_name:address -> metadata
+    addresses: Arc<RwLock<HashMap<String, AddressMetadata>>>,
+}
+
+impl MemoryAddressIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AddressIndex for MemoryAddressIndex {
+    async fn lookup(&self, address: &str, chain_name: &str) -> Result<Option<AddressMetadata>, AppError> {
+        let addresses = self.addresses.read().await;
+        Ok(addresses.get(&address_key(address, chain_name)).cloned())
+    }
+
+    async fn lookup_batch(&self, addresses: &[String], chain_name: &str) -> Result<HashMap<String, AddressMetadata>, AppError> {
+        let index = self.addresses.read().await;
+        Ok(addresses.iter()
+            .filter_map(|address| {
+                index.get(&address_key(address, chain_name))
+                    .map(|metadata| (address.clone(), metadata.clone()))
+            })
+            .collect())
+    }
+
+    async fn insert(&self, entries: &[MonitoredAddress]) -> Result<usize, AppError> {
+        let mut addresses = self.addresses.write().await;
+        for entry in entries {
+            addresses.insert(address_key(&entry.address, &entry.chain_name), entry.metadata.clone());
+        }
+        Ok(entries.len())
+    }
+
+    async fn remove(&self, address: &str, chain_name: &str) -> Result<bool, AppError> {
+        let mut addresses = self.addresses.write().await;
+        Ok(addresses.remove(&address_key(address, chain_name)).is_some())
+    }
+
+    async fn remove_wallet(&self, wallet_id: &str) -> Result<usize, AppError> {
+        let mut addresses = self.addresses.write().await;
+        let before = addresses.len();
+        addresses.retain(|_, metadata| metadata.wallet_id != wallet_id);
+        Ok(before - addresses.len())
+    }
+
+    async fn count(&self, chain_name: &str) -> Result<usize, AppError> {
+        let addresses = self.addresses.read().await;
+        let prefix = format!("{}:", chain_name.to_lowercase());
+        Ok(addresses.keys().filter(|k| k.starts_with(&prefix)).count())
+    }
+
+    async fn list(&self, chain_name: Option<&str>) -> Result<Vec<MonitoredAddress>, AppError> {
+        let addresses = self.addresses.read().await;
+        let prefix = chain_name.map(|chain| format!("{}:", chain.to_lowercase())).unwrap_or_default();
+        let mut entries: Vec<MonitoredAddress> = addresses.iter()
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .filter_map(|(key, metadata)| {
+                let (chain_name, address) = key.split_once(':')?;
+                Some(MonitoredAddress {
+                    address: address.to_string(),
+                    chain_name: chain_name.to_string(),
+                    metadata: metadata.clone(),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| (&a.chain_name, &a.address).cmp(&(&b.chain_name, &b.address)));
+        Ok(entries)
+    }
+}
+
+/// PostgreSQL 기반 AddressIndex (monitored_addresses 테이블)
+#[derive(Clone)]
+pub struct PostgresAddressIndex {
+    pool: Arc<PgPool>,
+}
+
+impl PostgresAddressIndex {
+    pub fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AddressIndex for PostgresAddressIndex {
+    async fn lookup(&self, address: &str, chain_name: &str) -> Result<Option<AddressMetadata>, AppError> {
+        crate::respository::postgresql::get_monitored_address(&self.pool, address, chain_name).await
+    }
+
+    async fn lookup_batch(&self, addresses: &[String], chain_name: &str) -> Result<HashMap<String, AddressMetadata>, AppError> {
+        let found = crate::respository::postgresql::get_monitored_addresses(&self.pool, addresses, chain_name).await?;
+        Ok(addresses.iter()
+            .filter_map(|address| {
+                found.get(&address.to_lowercase())
+                    .map(|metadata| (address.clone(), metadata.clone()))
+            })
+            .collect())
+    }
+
+    async fn insert(&self, entries: &[MonitoredAddress]) -> Result<usize, AppError> {
+        crate::respository::postgresql::upsert_monitored_addresses(&self.pool, entries).await
+    }
+
+    async fn remove(&self, address: &str, chain_name: &str) -> Result<bool, AppError> {
+        crate::respository::postgresql::delete_monitored_address(&self.pool, address, chain_name).await
+    }
+
+    async fn remove_wallet(&self, wallet_id: &str) -> Result<usize, AppError> {
+        crate::respository::postgresql::delete_wallet_addresses(&self.pool, wallet_id).await
+    }
+
+    async fn count(&self, chain_name: &str) -> Result<usize, AppError> {
+        crate::respository::postgresql::count_monitored_addresses(&self.pool, chain_name).await
+    }
+
+    async fn list(&self, chain_name: Option<&str>) -> Result<Vec<MonitoredAddress>, AppError> {
+        crate::respository::postgresql::list_monitored_addresses(&self.pool, chain_name).await
+    }
+
+    async fn health_check(&self) -> Result<(), AppError> {
+        sqlx::query("SELECT 1")
+            .execute(self.pool.as_ref())
+            .await
+            .map_err(|e| AppError::Database(format!("PostgreSQL address index is unreachable: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// RocksDB 기반 AddressIndex (Key: chain_name:address, Value: AddressMetadata JSON)
+#[cfg(feature = "rocksdb-backend")]
+#[derive(Clone)]
+pub struct RocksDBAddressIndex {
+    db: Arc<DB>,
+}
+
+#[cfg(feature = "rocksdb-backend")]
+impl RocksDBAddressIndex {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db }
+    }
+
+    /// 저장된 모든 주소 키 (Bloom filter 초기화용)
+    pub fn keys(&self) -> Result<Vec<String>, AppError> {
+        crate::respository::list_monitored_address_keys(&self.db)
+    }
+}
+
+#[cfg(feature = "rocksdb-backend")]
+#[async_trait]
+impl AddressIndex for RocksDBAddressIndex {
+    async fn lookup(&self, address: &str, chain_name: &str) -> Result<Option<AddressMetadata>, AppError> {
+        crate::respository::get_address_metadata_from_rocksdb(&self.db, address, chain_name)
+    }
+
+    async fn lookup_batch(&self, addresses: &[String], chain_name: &str) -> Result<HashMap<String, AddressMetadata>, AppError> {
+        let found = crate::respository::get_address_metadata_batch_from_rocksdb(&self.db, addresses, chain_name)?;
+        Ok(addresses.iter()
+            .zip(found)
+            .filter_map(|(address, metadata)| metadata.map(|m| (address.clone(), m)))
+            .collect())
+    }
+
+    async fn insert(&self, entries: &[MonitoredAddress]) -> Result<usize, AppError> {
+        let batch = entries.iter()
+            .map(|entry| (
+                entry.address.clone(),
+                entry.chain_name.clone(),
+                entry.metadata.wallet_id.clone(),
+                entry.metadata.account_id.clone(),
+            ))
+            .collect();
+        crate::respository::batch_add_monitored_addresses(&self.db, batch)
+    }
+
+    async fn remove(&self, address: &str, chain_name: &str) -> Result<bool, AppError> {
+        crate::respository::remove_monitored_address_from_rocksdb(&self.db, address, chain_name)
+    }
+
+    async fn remove_wallet(&self, wallet_id: &str) -> Result<usize, AppError> {
+        crate::respository::remove_wallet_addresses_from_rocksdb(&self.db, wallet_id)
+    }
+
+    async fn count(&self, chain_name: &str) -> Result<usize, AppError> {
+        crate::respository::count_monitored_addresses_in_rocksdb(&self.db, chain_name)
+    }
+
+    async fn list(&self, chain_name: Option<&str>) -> Result<Vec<MonitoredAddress>, AppError> {
+        crate::respository::list_monitored_addresses_in_rocksdb(&self.db, chain_name)
+    }
+
+    async fn health_check(&self) -> Result<(), AppError> {
+        self.db.get(b"health_check")
+            .map_err(|e| AppError::Database(format!("RocksDB address index is unreachable: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Bloom filter를 앞에 둔 AddressIndex
+///
+/// 관리 대상이 아닌 주소 대부분을 내부 인덱스 조회 없이 걸러낸다.
+/// 삭제된 주소는 filter에 남지만 내부 인덱스에서 다시 확인하므로 결과에는 영향이 없다.
+pub struct BloomFilteredAddressIndex {
+    inner: Arc<dyn AddressIndex>,
+    filter: RwLock<BloomFilter>,
+}
+
+impl BloomFilteredAddressIndex {
+    /// keys: 이미 등록된 주소 키 (chain_name:address)
+    pub fn new(inner: Arc<dyn AddressIndex>, keys: Vec<String>, capacity: usize, false_positive_rate: f64) -> Self {
+        let mut filter = BloomFilter::new(capacity.max(keys.len()), false_positive_rate);
+        for key in &keys {
+            filter.insert(key);
+        }
+        Self { inner, filter: RwLock::new(filter) }
+    }
+}
+
+#[async_trait]
+impl AddressIndex for BloomFilteredAddressIndex {
+    async fn lookup(&self, address: &str, chain_name: &str) -> Result<Option<AddressMetadata>, AppError> {
+        if !self.filter.read().await.contains(&address_key(address, chain_name)) {
+            return Ok(None);
+        }
+        self.inner.lookup(address, chain_name).await
+    }
+
+    async fn lookup_batch(&self, addresses: &[String], chain_name: &str) -> Result<HashMap<String, AddressMetadata>, AppError> {
+        let candidates: Vec<String> = {
+            let filter = self.filter.read().await;
+            addresses.iter()
+                .filter(|address| filter.contains(&address_key(address, chain_name)))
+                .cloned()
+                .collect()
+        };
+        if candidates.is_empty() {
+            return Ok(HashMap::new());
+        }
+        self.inner.lookup_batch(&candidates, chain_name).await
+    }
+
+    async fn insert(&self, entries: &[MonitoredAddress]) -> Result<usize, AppError> {
+        // filter에 먼저 추가 (내부 인덱스에는 있는데 filter에 없는 구간이 생기지 않도록)
+        {
+            let mut filter = self.filter.write().await;
+            for entry in entries {
+                filter.insert(&address_key(&entry.address, &entry.chain_name));
+            }
+        }
+        self.inner.insert(entries).await
+    }
+
+    async fn remove(&self, address: &str, chain_name: &str) -> Result<bool, AppError> {
+        self.inner.remove(address, chain_name).await
+    }
+
+    async fn remove_wallet(&self, wallet_id: &str) -> Result<usize, AppError> {
+        self.inner.remove_wallet(wallet_id).await
+    }
+
+    async fn count(&self, chain_name: &str) -> Result<usize, AppError> {
+        self.inner.count(chain_name).await
+    }
+
+    async fn list(&self, chain_name: Option<&str>) -> Result<Vec<MonitoredAddress>, AppError> {
+        self.inner.list(chain_name).await
+    }
+
+    async fn health_check(&self) -> Result<(), AppError> {
+        self.inner.health_check().await
+    }
+}
+
+/// 설정에 맞는 주소 인덱스 열기 (memory_db면 메모리, address_index = "postgres"면 PostgreSQL, 아니면 RocksDB)
+///
+/// RocksDB는 한 프로세스만 열 수 있다 - 스캐너가 실행 중이면 CLI에서는 열리지 않는다.
+pub fn open_address_index(
+    settings: &Settings,
+    repository: &RepositoryWrapper,
+    with_bloom_filter: bool,
+) -> Result<Arc<dyn AddressIndex>, AppError> {
+    if let RepositoryWrapper::Memory(_) = repository {
+        info!("Using in-memory address index (memory_db = true)");
+        return Ok(Arc::new(MemoryAddressIndex::new()));
+    }
+    let postgres_index = || -> Result<Arc<dyn AddressIndex>, AppError> {
+        let pg_repo = repository.get_postgresql_repo().ok_or_else(|| {
+            AppError::Config("PostgreSQL address index requires the PostgreSQL repository (rocksdb_path is set)".to_string())
+        })?;
+        info!("Using PostgreSQL address index.");
+        Ok(Arc::new(PostgresAddressIndex::new(pg_repo.shared_pool())))
+    };
+
+    if settings.repository.address_index.eq_ignore_ascii_case("postgres") {
+        return postgres_index();
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    {
+        let db = Arc::new(crate::respository::open_rocksdb(&settings.repository.leveldb_path)?);
+        info!("Opened RocksDB address index.");
+        let rocksdb_index = RocksDBAddressIndex::new(db);
+
+        if with_bloom_filter && settings.repository.address_bloom_filter {
+            let keys = rocksdb_index.keys()?;
+            info!("Address bloom filter enabled ({} cached addresses, capacity {})", keys.len(), settings.repository.bloom_capacity);
+            Ok(Arc::new(BloomFilteredAddressIndex::new(
+                Arc::new(rocksdb_index),
+                keys,
+                settings.repository.bloom_capacity,
+                settings.repository.bloom_false_positive_rate,
+            )))
+        } else {
+            Ok(Arc::new(rocksdb_index))
+        }
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    {
+        let _ = with_bloom_filter;
+        log::warn!("RocksDB feature not enabled. Using PostgreSQL address index.");
+        postgres_index()
+    }
+}
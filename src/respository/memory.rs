@@ -1,11 +1,16 @@
-use crate::types::AppError;
+use crate::notification::DepositEvent as DepositNotification;
+use crate::types::{
+    AppError, AssetInfo, BackfillJob, BackfillStatus, BlockCommit, DepositFilter, DepositRecord, DepositStatus,
+    DepositStatusChange, DepositTransition, NewDeposit, StatusTransition,
+};
 use crate::respository::r#trait::Repository;
 use async_trait::async_trait;
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
-use log::{info, warn};
+use log::info;
 
 /// ??? ?? Repository ??
 /// ???? ?? ???? ??
@@ -14,34 +19,88 @@ pub struct MemoryRepository {
     // chain_name -> last_processed_block
     last_processed_blocks: Arc<RwLock<HashMap<String, u64>>>,
     
-    // chain_name:address -> monitored flag (customer_id 제거됨)
-    monitored_addresses: Arc<RwLock<HashMap<String, bool>>>, // key: "chain_name:address", value: true
-    
-    // (chain_name, tx_hash) -> deposit_event
-    deposit_events: Arc<RwLock<HashMap<(String, String), DepositEvent>>>,
+    // (chain_name, tx_hash, output_index) -> deposit_event
+    deposit_events: Arc<RwLock<HashMap<DepositKey, DepositEvent>>>,
     
     // (customer_id, chain_name) -> balance
     customer_balances: Arc<RwLock<HashMap<(String, String), Decimal>>>,
+
+    // chain_name -> (block_number -> block_hash)
+    block_hashes: Arc<RwLock<HashMap<String, BTreeMap<u64, String>>>>,
+
+    // (chain_name, tx_hash, output_index) -> 실패 사유
+    failed_deposits: Arc<RwLock<HashMap<DepositKey, String>>>,
+
+    // id -> 전송 대기 중인 알림 (모든 sink로 전송 완료 시 삭제)
+    notification_outbox: Arc<RwLock<BTreeMap<i64, OutboxRecord>>>,
+
+    // (chain_name, tx_hash, output_index) -> 상태 전이 이력
+    status_history: Arc<RwLock<HashMap<DepositKey, Vec<DepositStatusChange>>>>,
+
+    // id -> backfill 작업
+    backfill_jobs: Arc<RwLock<BTreeMap<i64, BackfillJob>>>,
+}
+
+type DepositKey = (String, String, u32);
+
+fn deposit_key(chain_name: &str, tx_hash: &str, output_index: u32) -> DepositKey {
+    (chain_name.to_string(), tx_hash.to_string(), output_index)
+}
+
+struct OutboxRecord {
+    event_type: String,
+    chain_name: String,
+    tx_hash: String,
+    output_index: u32,
+    payload: String,
+    deliveries: HashMap<String, SinkDelivery>, // sink -> 전송 상태 (아직 시도하지 않은 sink는 없음)
+    dead: bool, // 모든 sink 처리가 끝났고 dead인 sink가 있음 (더 이상 전송하지 않음)
+}
+
+/// sink 하나로의 전송 상태
+#[derive(Default)]
+struct SinkDelivery {
+    attempts: u32,
+    next_attempt_at: Option<Instant>,
+    sent: bool,
+    dead: bool, // 이 sink로는 재시도 한도 초과
+}
+
+impl SinkDelivery {
+    fn is_finished(&self) -> bool {
+        self.sent || self.dead
+    }
 }
 
 #[derive(Clone)]
 struct DepositEvent {
     address: String,
+    wallet_id: String,
+    account_id: Option<String>,
     chain_name: String,
     tx_hash: String,
+    output_index: u32,
     block_number: u64,
     amount: String,
     amount_decimal: Option<Decimal>,
-    confirmed: bool,
+    asset: AssetInfo,
+    block_hash: Option<String>,
+    status: DepositStatus,
+    status_version: i64,
+    confirmations: u64,
 }
 
 impl MemoryRepository {
     pub fn new() -> Self {
         Self {
             last_processed_blocks: Arc::new(RwLock::new(HashMap::new())),
-            monitored_addresses: Arc::new(RwLock::new(HashMap::new())),
             deposit_events: Arc::new(RwLock::new(HashMap::new())),
             customer_balances: Arc::new(RwLock::new(HashMap::new())),
+            block_hashes: Arc::new(RwLock::new(HashMap::new())),
+            failed_deposits: Arc::new(RwLock::new(HashMap::new())),
+            notification_outbox: Arc::new(RwLock::new(BTreeMap::new())),
+            status_history: Arc::new(RwLock::new(HashMap::new())),
+            backfill_jobs: Arc::new(RwLock::new(BTreeMap::new())),
         }
     }
 }
@@ -59,6 +118,13 @@ impl Repository for MemoryRepository {
         Ok(())
     }
     
+    async fn list_last_processed_blocks(&self) -> Result<Vec<(String, u64)>, AppError> {
+        let blocks = self.last_processed_blocks.read().await;
+        let mut list: Vec<(String, u64)> = blocks.iter().map(|(chain, block)| (chain.clone(), *block)).collect();
+        list.sort();
+        Ok(list)
+    }
+
     async fn init_last_processed_block(&self, chain: &str, initial_block: u64) -> Result<(), AppError> {
         let mut blocks = self.last_processed_blocks.write().await;
         
@@ -77,122 +143,440 @@ impl Repository for MemoryRepository {
         Ok(())
     }
     
-    async fn is_monitored_address(&self, address: &str, chain_name: &str) -> Result<bool, AppError> {
-        let addresses = self.monitored_addresses.read().await;
-        let normalized_address = address.to_lowercase();
-        let key = format!("{}:{}", chain_name.to_lowercase(), normalized_address);
+    async fn commit_block(&self, commit: &BlockCommit) -> Result<usize, AppError> {
+        // 실패할 수 있는 검증/직렬화를 먼저 끝내고, 모든 lock을 잡은 채 한 번에 적용 (중간에 실패하지 않음)
+        for change in &commit.transitions {
+            change.transition.validate()?;
+        }
+        let deposit_notifications = commit.deposits.iter()
+            .map(|deposit| deposit.notification.as_ref().map(outbox_record).transpose())
+            .collect::<Result<Vec<_>, _>>()?;
+        let transition_notifications = commit.transitions.iter()
+            .map(|change| change.notification.as_ref().map(outbox_record).transpose())
+            .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(addresses.contains_key(&key))
+        let mut events = self.deposit_events.write().await;
+        let mut failed = self.failed_deposits.write().await;
+        let mut hashes = self.block_hashes.write().await;
+        let mut blocks = self.last_processed_blocks.write().await;
+        let mut outbox = self.notification_outbox.write().await;
+        let mut history = self.status_history.write().await;
+        let chain_name = commit.chain_name.as_str();
+
+        for (deposit, notification) in commit.deposits.iter().zip(deposit_notifications) {
+            if insert_deposit(&mut events, &mut history, chain_name, deposit) {
+                if let Some(record) = notification {
+                    push_outbox(&mut outbox, record);
+                }
+            }
+        }
+
+        let mut applied = 0;
+        for (change, notification) in commit.transitions.iter().zip(transition_notifications) {
+            if apply_transition(&mut events, &mut history, chain_name, change) {
+                applied += 1;
+                if let Some(record) = notification {
+                    push_outbox(&mut outbox, record);
+                }
+            }
+        }
+
+        for deposit in &commit.failed_deposits {
+            failed.insert(deposit_key(chain_name, &deposit.tx_hash, deposit.output_index), deposit.reason.clone());
+        }
+
+        if let Some(cursor) = &commit.cursor {
+            blocks.insert(chain_name.to_string(), commit.block_number);
+            if let Some(block_ref) = &cursor.block_ref {
+                let chain_hashes = hashes.entry(chain_name.to_string()).or_default();
+                chain_hashes.insert(commit.block_number, block_ref.hash.clone());
+                chain_hashes.retain(|n, _| *n >= cursor.prune_below);
+            }
+        }
+
+        Ok(applied)
     }
 
-    async fn save_deposit_event(
+    // Note: increment_customer_balance removed
+    // Balance management is handled by blockbit-back-custody, not xScanner
+
+    async fn get_deposit_status(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Option<(DepositStatus, i64)>, AppError> {
+        let events = self.deposit_events.read().await;
+        let key = deposit_key(chain_name, tx_hash, output_index);
+        Ok(events.get(&key).map(|e| (e.status, e.status_version)))
+    }
+
+    async fn transition_deposit_status(
         &self,
-        address: &str,
-        _wallet_id: &str,
-        _account_id: Option<&str>,
-        chain_name: &str,
         tx_hash: &str,
-        block_number: u64,
-        amount: &str,
-        amount_decimal: Option<Decimal>,
-    ) -> Result<(), AppError> {
+        output_index: u32,
+        chain_name: &str,
+        transition: &StatusTransition,
+        notification: Option<&DepositNotification>,
+    ) -> Result<bool, AppError> {
+        transition.validate()?;
+        let record = notification.map(outbox_record).transpose()?;
+
         let mut events = self.deposit_events.write().await;
-        let key = (chain_name.to_string(), tx_hash.to_string());
+        let mut outbox = self.notification_outbox.write().await;
+        let mut history = self.status_history.write().await;
+        let change = DepositTransition {
+            tx_hash: tx_hash.to_string(),
+            output_index,
+            transition: *transition,
+            notification: None,
+        };
 
-        // 중복 체크 (UNIQUE 제약)
-        if events.contains_key(&key) {
-            return Ok(());
+        if !apply_transition(&mut events, &mut history, chain_name, &change) {
+            return Ok(false);
+        }
+        if let Some(record) = record {
+            push_outbox(&mut outbox, record);
         }
 
-        events.insert(key, DepositEvent {
-            address: address.to_string(),
-            chain_name: chain_name.to_string(),
-            tx_hash: tx_hash.to_string(),
-            block_number,
-            amount: amount.to_string(),
-            amount_decimal,
-            confirmed: false,
-        });
-
-        Ok(())
+        Ok(true)
     }
 
-    async fn get_address_metadata(&self, _address: &str, _chain_name: &str) -> Result<Option<(String, Option<String>)>, AppError> {
-        // MemoryRepository doesn't store address metadata
-        Ok(None)
+    async fn get_deposit_status_history(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Vec<DepositStatusChange>, AppError> {
+        let history = self.status_history.read().await;
+        Ok(history.get(&deposit_key(chain_name, tx_hash, output_index)).cloned().unwrap_or_default())
     }
 
-    // Note: increment_customer_balance removed
-    // Balance management is handled by blockbit-back-custody, not xScanner
+    async fn list_deposits(&self, filter: &DepositFilter) -> Result<Vec<DepositRecord>, AppError> {
+        let events = self.deposit_events.read().await;
 
-    async fn load_customer_addresses(&self, chain_name: &str) -> Result<usize, AppError> {
-        // 메모리 저장소에서 특정 체인의 주소 개수 반환
-        let addresses = self.monitored_addresses.read().await;
-        let prefix = format!("{}:", chain_name.to_lowercase());
-        let count = addresses.iter()
-            .filter(|(k, _)| k.starts_with(&prefix))
-            .count();
+        let mut deposits: Vec<&DepositEvent> = events.values()
+            .filter(|e| filter.wallet_id.as_ref().is_none_or(|w| e.wallet_id == *w))
+            .filter(|e| filter.account_id.as_ref().is_none_or(|a| e.account_id.as_ref() == Some(a)))
+            .filter(|e| filter.address.as_ref().is_none_or(|a| e.address.eq_ignore_ascii_case(a)))
+            .filter(|e| filter.chain_name.as_ref().is_none_or(|c| e.chain_name.eq_ignore_ascii_case(c)))
+            .filter(|e| filter.tx_hash.as_ref().is_none_or(|t| e.tx_hash == *t))
+            .filter(|e| filter.status.is_none_or(|s| e.status == s))
+            .collect();
+        deposits.sort_by(|a, b| b.block_number.cmp(&a.block_number).then(a.output_index.cmp(&b.output_index)));
 
-        warn!("MemoryRepository: load_customer_addresses called for {}, found {} addresses", chain_name, count);
-        Ok(count)
+        Ok(deposits.into_iter()
+            .skip(filter.offset)
+            .take(filter.limit)
+            .map(DepositEvent::to_record)
+            .collect())
     }
 
-    async fn deposit_exists(&self, tx_hash: &str, chain_name: &str) -> Result<bool, AppError> {
+    async fn get_pending_deposits(&self) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
         let events = self.deposit_events.read().await;
-        let key = (chain_name.to_string(), tx_hash.to_string());
-        Ok(events.contains_key(&key))
+
+        let deposits = events.values()
+            .filter(|event| event.status.is_pending())
+            .map(DepositEvent::to_pending)
+            .collect();
+
+        Ok(deposits)
     }
 
-    async fn is_deposit_confirmed(&self, tx_hash: &str) -> Result<bool, AppError> {
-        let events = self.deposit_events.read().await;
+    async fn get_recent_block_hashes(&self, chain: &str, limit: u64) -> Result<Vec<(u64, String)>, AppError> {
+        let hashes = self.block_hashes.read().await;
+        let mut recent: Vec<(u64, String)> = hashes.get(chain)
+            .map(|m| m.iter().rev().take(limit as usize).map(|(n, h)| (*n, h.clone())).collect())
+            .unwrap_or_default();
+        recent.reverse();
+        Ok(recent)
+    }
+
+    async fn rollback_to_block(&self, chain: &str, common_ancestor: u64, notify: bool) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
+        let mut events = self.deposit_events.write().await;
+        let mut hashes = self.block_hashes.write().await;
+        let mut blocks = self.last_processed_blocks.write().await;
+        let mut outbox = self.notification_outbox.write().await;
+        let mut history = self.status_history.write().await;
+
+        let mut orphaned = Vec::new();
+        for (key, event) in events.iter_mut() {
+            if event.chain_name != chain
+                || event.block_number <= common_ancestor
+                || !event.status.can_transition_to(DepositStatus::Orphaned)
+            {
+                continue;
+            }
+
+            record_status_change(&mut history, key.clone(), Some(event.status), DepositStatus::Orphaned, event.confirmations);
+            event.status = DepositStatus::Orphaned;
+            event.status_version += 1;
+
+            let deposit = event.to_pending();
+            if notify {
+                enqueue_notification(&mut outbox, &deposit.orphaned_event())?;
+            }
+            orphaned.push(deposit);
+        }
+
+        if let Some(m) = hashes.get_mut(chain) {
+            m.retain(|n, _| *n <= common_ancestor);
+        }
 
-        // Find the deposit event by tx_hash (search across all chains)
-        for ((_, hash), event) in events.iter() {
-            if hash == tx_hash {
-                return Ok(event.confirmed);
+        if let Some(last) = blocks.get_mut(chain) {
+            if *last > common_ancestor {
+                *last = common_ancestor;
             }
         }
 
-        Ok(false)
+        Ok(orphaned)
     }
 
-    async fn get_pending_deposits(&self) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
-        let events = self.deposit_events.read().await;
+    async fn get_pending_notifications(&self, sink: &str, limit: usize) -> Result<Vec<crate::tasks::OutboxEntry>, AppError> {
+        let outbox = self.notification_outbox.read().await;
+        let now = Instant::now();
 
-        let mut deposits = Vec::new();
-        for ((_, _), event) in events.iter() {
-            if !event.confirmed {
-                deposits.push(crate::tasks::PendingDeposit {
-                    address: event.address.clone(),
-                    wallet_id: String::new(), // MemoryRepository doesn't store wallet_id
-                    account_id: None,
-                    chain_name: event.chain_name.clone(),
-                    tx_hash: event.tx_hash.clone(),
-                    block_number: event.block_number,
-                    amount: event.amount.clone(),
-                    amount_decimal: event.amount_decimal,
+        // 같은 입금의 이전 알림이 이 sink로 아직 전송되지 않았으면 이후 알림은 보내지 않음 (dead 알림은 제외)
+        let mut blocked = std::collections::HashSet::new();
+        let mut entries = Vec::new();
+        for (id, record) in outbox.iter().filter(|(_, record)| !record.dead) {
+            let delivery = record.deliveries.get(sink);
+            if delivery.is_some_and(SinkDelivery::is_finished) {
+                continue;
+            }
+            if !blocked.insert((record.chain_name.as_str(), record.tx_hash.as_str(), record.output_index)) {
+                continue;
+            }
+            if delivery.and_then(|d| d.next_attempt_at).is_none_or(|at| at <= now) {
+                entries.push(crate::tasks::OutboxEntry {
+                    id: *id,
+                    event_type: record.event_type.clone(),
+                    payload: record.payload.clone(),
+                    attempts: delivery.map_or(0, |d| d.attempts),
                 });
             }
+            if entries.len() >= limit {
+                break;
+            }
         }
 
-        Ok(deposits)
+        Ok(entries)
     }
-}
 
-// Helper function to update deposit confirmation status for MemoryRepository
-impl MemoryRepository {
-    pub async fn update_deposit_confirmed(&self, tx_hash: &str) -> Result<(), AppError> {
-        let mut events = self.deposit_events.write().await;
+    async fn mark_notification_sent(&self, id: i64, sink: &str) -> Result<(), AppError> {
+        if let Some(record) = self.notification_outbox.write().await.get_mut(&id) {
+            record.deliveries.entry(sink.to_string()).or_default().sent = true;
+        }
+        Ok(())
+    }
 
-        // Find and update the deposit event
-        for ((_, hash), event) in events.iter_mut() {
-            if hash == tx_hash {
-                event.confirmed = true;
-                return Ok(());
+    async fn mark_notification_failed(&self, id: i64, sink: &str, _error: &str, retry_after_secs: u64) -> Result<(), AppError> {
+        if let Some(record) = self.notification_outbox.write().await.get_mut(&id) {
+            let delivery = record.deliveries.entry(sink.to_string()).or_default();
+            delivery.attempts += 1;
+            delivery.next_attempt_at = Some(Instant::now() + Duration::from_secs(retry_after_secs));
+        }
+        Ok(())
+    }
+
+    async fn mark_notification_dead(&self, id: i64, sink: &str, _error: &str) -> Result<(), AppError> {
+        if let Some(record) = self.notification_outbox.write().await.get_mut(&id) {
+            let delivery = record.deliveries.entry(sink.to_string()).or_default();
+            delivery.attempts += 1;
+            delivery.dead = true;
+        }
+        Ok(())
+    }
+
+    async fn complete_notifications(&self, sinks: &[String]) -> Result<usize, AppError> {
+        let mut outbox = self.notification_outbox.write().await;
+        let finished: Vec<(i64, bool)> = outbox.iter()
+            .filter(|(_, record)| !record.dead)
+            .filter(|(_, record)| sinks.iter().all(|sink| record.deliveries.get(sink).is_some_and(SinkDelivery::is_finished)))
+            .map(|(id, record)| (*id, sinks.iter().any(|sink| record.deliveries[sink].dead)))
+            .collect();
+
+        // 모두 전송된 알림은 삭제, dead인 sink가 있으면 dead로 남김
+        for (id, dead) in &finished {
+            if *dead {
+                if let Some(record) = outbox.get_mut(id) {
+                    record.dead = true;
+                }
+            } else {
+                outbox.remove(id);
             }
         }
+        Ok(finished.len())
+    }
+
+    async fn enqueue_notification(&self, event: &DepositNotification) -> Result<(), AppError> {
+        enqueue_notification(&mut *self.notification_outbox.write().await, event)
+    }
+
+    async fn create_backfill_job(&self, chain: &str, from_block: u64, to_block: u64, address: Option<&str>) -> Result<BackfillJob, AppError> {
+        let mut jobs = self.backfill_jobs.write().await;
+        let id = jobs.keys().next_back().map(|id| id + 1).unwrap_or(1);
+        let now = unix_now();
+        let job = BackfillJob {
+            id,
+            chain: chain.to_string(),
+            from_block,
+            to_block,
+            next_block: from_block,
+            address: address.map(str::to_string),
+            status: BackfillStatus::Pending,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        jobs.insert(id, job.clone());
+        Ok(job)
+    }
+
+    async fn update_backfill_job(&self, id: i64, next_block: u64, status: BackfillStatus, error: Option<&str>) -> Result<(), AppError> {
+        if let Some(job) = self.backfill_jobs.write().await.get_mut(&id) {
+            job.next_block = next_block;
+            job.status = status;
+            job.error = error.map(str::to_string);
+            job.updated_at = unix_now();
+        }
+        Ok(())
+    }
+
+    async fn get_backfill_job(&self, id: i64) -> Result<Option<BackfillJob>, AppError> {
+        Ok(self.backfill_jobs.read().await.get(&id).cloned())
+    }
+
+    async fn list_backfill_jobs(&self, unfinished_only: bool) -> Result<Vec<BackfillJob>, AppError> {
+        Ok(self.backfill_jobs.read().await.values()
+            .filter(|job| !unfinished_only || job.status.is_unfinished())
+            .cloned()
+            .collect())
+    }
+}
+
+fn enqueue_notification(outbox: &mut BTreeMap<i64, OutboxRecord>, event: &DepositNotification) -> Result<(), AppError> {
+    push_outbox(outbox, outbox_record(event)?);
+    Ok(())
+}
+
+fn outbox_record(event: &DepositNotification) -> Result<OutboxRecord, AppError> {
+    let payload = serde_json::to_string(event)
+        .map_err(|e| AppError::Database(format!("Failed to serialize notification: {}", e)))?;
+
+    Ok(OutboxRecord {
+        event_type: event.event_type().to_string(),
+        chain_name: event.chain().to_string(),
+        tx_hash: event.tx_hash().to_string(),
+        output_index: event.output_index(),
+        payload,
+        deliveries: HashMap::new(),
+        dead: false,
+    })
+}
 
-        Err(AppError::Database(format!("Deposit not found: {}", tx_hash)))
+fn push_outbox(outbox: &mut BTreeMap<i64, OutboxRecord>, record: OutboxRecord) {
+    let id = outbox.keys().next_back().map(|id| id + 1).unwrap_or(1);
+    outbox.insert(id, record);
+}
+
+/// 입금 저장 - 이미 저장된 입금이면 false (UNIQUE 제약, orphaned 입금은 canonical 체인에 다시 포함된 경우 갱신)
+fn insert_deposit(
+    events: &mut HashMap<DepositKey, DepositEvent>,
+    history: &mut HashMap<DepositKey, Vec<DepositStatusChange>>,
+    chain_name: &str,
+    deposit: &NewDeposit,
+) -> bool {
+    let key = deposit_key(chain_name, &deposit.tx_hash, deposit.output_index);
+    let previous = events.get(&key).map(|e| (e.status, e.status_version));
+    if previous.is_some_and(|(status, _)| status != DepositStatus::Orphaned) {
+        return false;
+    }
+
+    events.insert(key.clone(), DepositEvent {
+        address: deposit.address.clone(),
+        wallet_id: deposit.wallet_id.clone(),
+        account_id: deposit.account_id.clone(),
+        chain_name: chain_name.to_string(),
+        tx_hash: deposit.tx_hash.clone(),
+        output_index: deposit.output_index,
+        block_number: deposit.block_number,
+        amount: deposit.amount.clone(),
+        amount_decimal: deposit.amount_decimal,
+        asset: deposit.asset.clone(),
+        block_hash: deposit.block_hash.clone(),
+        status: DepositStatus::Detected,
+        status_version: previous.map(|(_, version)| version + 1).unwrap_or(0),
+        confirmations: 1,
+    });
+    record_status_change(history, key, previous.map(|(status, _)| status), DepositStatus::Detected, 1);
+    true
+}
+
+/// 상태 전이 - 현재 상태나 status_version이 다르면 false
+fn apply_transition(
+    events: &mut HashMap<DepositKey, DepositEvent>,
+    history: &mut HashMap<DepositKey, Vec<DepositStatusChange>>,
+    chain_name: &str,
+    change: &DepositTransition,
+) -> bool {
+    let transition = &change.transition;
+    let key = deposit_key(chain_name, &change.tx_hash, change.output_index);
+
+    let Some(event) = events.get_mut(&key)
+        .filter(|e| e.status == transition.from && e.status_version == transition.expected_version)
+    else {
+        return false;
+    };
+
+    event.status = transition.to;
+    event.status_version += 1;
+    event.confirmations = transition.confirmations;
+    record_status_change(history, key, Some(transition.from), transition.to, transition.confirmations);
+    true
+}
+
+fn record_status_change(
+    history: &mut HashMap<DepositKey, Vec<DepositStatusChange>>,
+    key: DepositKey,
+    from: Option<DepositStatus>,
+    to: DepositStatus,
+    confirmations: u64,
+) {
+    history.entry(key).or_default().push(DepositStatusChange {
+        from_status: from,
+        to_status: to,
+        confirmations,
+        changed_at: unix_now(),
+    });
+}
+
+impl DepositEvent {
+    fn to_record(&self) -> DepositRecord {
+        DepositRecord {
+            address: self.address.clone(),
+            wallet_id: self.wallet_id.clone(),
+            account_id: self.account_id.clone(),
+            chain: self.chain_name.clone(),
+            tx_hash: self.tx_hash.clone(),
+            output_index: self.output_index,
+            block_number: self.block_number,
+            block_hash: self.block_hash.clone(),
+            amount: self.amount.clone(),
+            amount_decimal: self.amount_decimal,
+            asset: self.asset.clone(),
+            status: self.status,
+            status_version: self.status_version,
+            confirmations: self.confirmations,
+        }
+    }
+
+    fn to_pending(&self) -> crate::tasks::PendingDeposit {
+        crate::tasks::PendingDeposit {
+            address: self.address.clone(),
+            wallet_id: self.wallet_id.clone(),
+            account_id: self.account_id.clone(),
+            chain_name: self.chain_name.clone(),
+            tx_hash: self.tx_hash.clone(),
+            output_index: self.output_index,
+            block_number: self.block_number,
+            amount: self.amount.clone(),
+            amount_decimal: self.amount_decimal,
+            asset: self.asset.clone(),
+            block_hash: self.block_hash.clone(),
+            status: self.status,
+            status_version: self.status_version,
+        }
     }
 }
 
@@ -201,3 +585,233 @@ impl Default for MemoryRepository {
         Self::new()
     }
 }
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN: &str = "BTC";
+
+    fn new_deposit(tx_hash: &str, block_number: u64) -> NewDeposit {
+        NewDeposit {
+            address: "bc1qdeposit".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            account_id: None,
+            tx_hash: tx_hash.to_string(),
+            output_index: 0,
+            block_number,
+            amount: "1000".to_string(),
+            amount_decimal: None,
+            asset: AssetInfo::native("BTC", 8),
+            block_hash: None,
+            notification: None,
+        }
+    }
+
+    fn block(block_number: u64, deposits: Vec<NewDeposit>, transitions: Vec<DepositTransition>) -> BlockCommit {
+        BlockCommit {
+            chain_name: CHAIN.to_string(),
+            block_number,
+            deposits,
+            transitions,
+            failed_deposits: Vec::new(),
+            cursor: None,
+        }
+    }
+
+    fn transition(from: DepositStatus, to: DepositStatus, expected_version: i64) -> StatusTransition {
+        StatusTransition { from, to, expected_version, confirmations: 6 }
+    }
+
+    fn confirmed(tx_hash: &str) -> DepositNotification {
+        DepositNotification::DepositConfirmed {
+            address: "bc1qdeposit".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            account_id: None,
+            chain: CHAIN.to_string(),
+            tx_hash: tx_hash.to_string(),
+            output_index: 0,
+            amount: "1000".to_string(),
+            asset: AssetInfo::native("BTC", 8),
+            block_number: 11,
+            block_hash: None,
+            confirmations: 6,
+        }
+    }
+
+    fn detected_notification(tx_hash: &str) -> DepositNotification {
+        DepositNotification::DepositDetected {
+            address: "bc1qdeposit".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            account_id: None,
+            chain: CHAIN.to_string(),
+            tx_hash: tx_hash.to_string(),
+            output_index: 0,
+            amount: "1000".to_string(),
+            asset: AssetInfo::native("BTC", 8),
+            block_number: 11,
+            block_hash: None,
+            confirmations: 1,
+        }
+    }
+
+    async fn detected(repository: &MemoryRepository, tx_hash: &str) {
+        repository.commit_block(&block(11, vec![new_deposit(tx_hash, 11)], Vec::new())).await.unwrap();
+    }
+
+    fn statuses(history: &[DepositStatusChange]) -> Vec<(Option<DepositStatus>, DepositStatus)> {
+        history.iter().map(|change| (change.from_status, change.to_status)).collect()
+    }
+
+    #[tokio::test]
+    async fn transition_bumps_version_and_records_history() {
+        let repository = MemoryRepository::new();
+        detected(&repository, "tx-1").await;
+        assert_eq!(repository.get_deposit_status("tx-1", 0, CHAIN).await.unwrap(), Some((DepositStatus::Detected, 0)));
+
+        let applied = repository
+            .transition_deposit_status("tx-1", 0, CHAIN, &transition(DepositStatus::Detected, DepositStatus::Confirming, 0), None)
+            .await
+            .unwrap();
+
+        assert!(applied);
+        assert_eq!(repository.get_deposit_status("tx-1", 0, CHAIN).await.unwrap(), Some((DepositStatus::Confirming, 1)));
+        let history = repository.get_deposit_status_history("tx-1", 0, CHAIN).await.unwrap();
+        assert_eq!(
+            statuses(&history),
+            [(None, DepositStatus::Detected), (Some(DepositStatus::Detected), DepositStatus::Confirming)]
+        );
+        assert_eq!(history[1].confirmations, 6);
+    }
+
+    #[tokio::test]
+    async fn stale_expected_version_changes_nothing() {
+        let repository = MemoryRepository::new();
+        detected(&repository, "tx-1").await;
+        repository
+            .transition_deposit_status("tx-1", 0, CHAIN, &transition(DepositStatus::Detected, DepositStatus::Confirming, 0), None)
+            .await
+            .unwrap();
+
+        // 다른 작업이 먼저 상태를 바꾼 뒤 이전 버전으로 전이 시도
+        let applied = repository
+            .transition_deposit_status(
+                "tx-1", 0, CHAIN,
+                &transition(DepositStatus::Confirming, DepositStatus::Confirmed, 0),
+                Some(&confirmed("tx-1")),
+            )
+            .await
+            .unwrap();
+
+        assert!(!applied);
+        assert_eq!(repository.get_deposit_status("tx-1", 0, CHAIN).await.unwrap(), Some((DepositStatus::Confirming, 1)));
+        assert_eq!(repository.get_deposit_status_history("tx-1", 0, CHAIN).await.unwrap().len(), 2);
+        assert!(repository.get_pending_notifications("sqs", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stale_transition_in_block_commit_is_skipped() {
+        let repository = MemoryRepository::new();
+        detected(&repository, "tx-1").await;
+        let stale = DepositTransition {
+            tx_hash: "tx-1".to_string(),
+            output_index: 0,
+            transition: transition(DepositStatus::Detected, DepositStatus::Confirmed, 3),
+            notification: Some(confirmed("tx-1")),
+        };
+
+        let applied = repository.commit_block(&block(17, Vec::new(), vec![stale])).await.unwrap();
+
+        assert_eq!(applied, 0);
+        assert_eq!(repository.get_deposit_status("tx-1", 0, CHAIN).await.unwrap(), Some((DepositStatus::Detected, 0)));
+        assert!(repository.get_pending_notifications("sqs", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn redetected_orphan_keeps_counting_versions() {
+        let repository = MemoryRepository::new();
+        detected(&repository, "tx-1").await;
+
+        let orphaned = repository.rollback_to_block(CHAIN, 10, false).await.unwrap();
+        assert_eq!(orphaned.len(), 1);
+        assert_eq!(repository.get_deposit_status("tx-1", 0, CHAIN).await.unwrap(), Some((DepositStatus::Orphaned, 1)));
+
+        // canonical 체인에 다시 포함되면 detected로 돌아가고 버전은 이어진다
+        detected(&repository, "tx-1").await;
+        assert_eq!(repository.get_deposit_status("tx-1", 0, CHAIN).await.unwrap(), Some((DepositStatus::Detected, 2)));
+        let history = repository.get_deposit_status_history("tx-1", 0, CHAIN).await.unwrap();
+        assert_eq!(
+            statuses(&history),
+            [
+                (None, DepositStatus::Detected),
+                (Some(DepositStatus::Detected), DepositStatus::Orphaned),
+                (Some(DepositStatus::Orphaned), DepositStatus::Detected),
+            ]
+        );
+
+        // 이미 detected인 입금을 다시 저장해도 바뀌지 않는다
+        detected(&repository, "tx-1").await;
+        assert_eq!(repository.get_deposit_status_history("tx-1", 0, CHAIN).await.unwrap().len(), 3);
+    }
+
+    async fn pending_ids(repository: &MemoryRepository, sink: &str) -> Vec<i64> {
+        repository.get_pending_notifications(sink, 10).await.unwrap().iter().map(|entry| entry.id).collect()
+    }
+
+    #[tokio::test]
+    async fn pending_notifications_keep_per_output_order() {
+        let repository = MemoryRepository::new();
+        repository.enqueue_notification(&detected_notification("tx-1")).await.unwrap();
+        repository.enqueue_notification(&confirmed("tx-1")).await.unwrap();
+        repository.enqueue_notification(&detected_notification("tx-2")).await.unwrap();
+
+        // tx-1의 confirmed는 detected가 전송될 때까지 기다리지만 다른 입금은 막지 않는다
+        assert_eq!(pending_ids(&repository, "sqs").await, [1, 3]);
+
+        // 재시도를 기다리는 동안에도 이후 알림은 보내지 않는다
+        repository.mark_notification_failed(1, "sqs", "timeout", 60).await.unwrap();
+        assert_eq!(pending_ids(&repository, "sqs").await, [3]);
+
+        // 다른 sink의 순서는 따로 관리
+        assert_eq!(pending_ids(&repository, "webhook").await, [1, 3]);
+    }
+
+    #[tokio::test]
+    async fn sent_notification_releases_the_next_one() {
+        let repository = MemoryRepository::new();
+        repository.enqueue_notification(&detected_notification("tx-1")).await.unwrap();
+        repository.enqueue_notification(&confirmed("tx-1")).await.unwrap();
+
+        repository.mark_notification_sent(1, "sqs").await.unwrap();
+
+        assert_eq!(pending_ids(&repository, "sqs").await, [2]);
+        let entries = repository.get_pending_notifications("sqs", 10).await.unwrap();
+        assert_eq!(entries[0].event_type, "DepositConfirmed");
+        assert_eq!(entries[0].attempts, 0);
+    }
+
+    #[tokio::test]
+    async fn dead_notifications_are_skipped_without_blocking_later_ones() {
+        let repository = MemoryRepository::new();
+        repository.enqueue_notification(&detected_notification("tx-1")).await.unwrap();
+        repository.enqueue_notification(&confirmed("tx-1")).await.unwrap();
+
+        repository.mark_notification_dead(1, "sqs", "rejected").await.unwrap();
+        assert_eq!(pending_ids(&repository, "sqs").await, [2]);
+
+        // 모든 sink 처리가 끝나면 dead로 남고 새 sink에도 보내지 않는다
+        repository.mark_notification_sent(2, "sqs").await.unwrap();
+        assert_eq!(repository.complete_notifications(&["sqs".to_string()]).await.unwrap(), 2);
+        assert!(pending_ids(&repository, "sqs").await.is_empty());
+        assert!(pending_ids(&repository, "webhook").await.is_empty());
+        assert!(repository.notification_outbox.read().await[&1].dead);
+        assert!(!repository.notification_outbox.read().await.contains_key(&2));
+    }
+}
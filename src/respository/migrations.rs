@@ -0,0 +1,254 @@
+use crate::types::AppError;
+use log::{info, warn};
+use sha2::{Digest, Sha256};
+use sqlx::{Connection, Executor, PgPool};
+
+pub const SCHEMA_MIGRATIONS_TABLE: &str = "schema_migrations";
+
+// 여러 인스턴스가 동시에 시작해도 마이그레이션은 한 번만 적용 (pg_advisory_lock 키)
+const MIGRATION_LOCK_ID: i64 = 0x7853_6361_6e6e_6572; // "xScanner"
+
+/// 바이너리에 포함된 스키마 마이그레이션
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+impl Migration {
+    /// SQL 내용의 SHA-256 (적용 후 파일이 바뀌었는지 확인용)
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+/// version 오름차순 - 이미 배포된 마이그레이션은 수정하지 말고 새 파일을 추가한다
+pub const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, name: "initial_schema", sql: include_str!("../../migrations/0001_initial_schema.sql") },
+    Migration { version: 2, name: "reorg_tracking", sql: include_str!("../../migrations/0002_reorg_tracking.sql") },
+    Migration { version: 3, name: "deposit_assets", sql: include_str!("../../migrations/0003_deposit_assets.sql") },
+    Migration { version: 4, name: "failed_deposits", sql: include_str!("../../migrations/0004_failed_deposits.sql") },
+    Migration { version: 5, name: "monitored_addresses", sql: include_str!("../../migrations/0005_monitored_addresses.sql") },
+    Migration { version: 6, name: "notification_outbox", sql: include_str!("../../migrations/0006_notification_outbox.sql") },
+    Migration { version: 7, name: "deposit_output_index", sql: include_str!("../../migrations/0007_deposit_output_index.sql") },
+    Migration { version: 8, name: "deposit_status", sql: include_str!("../../migrations/0008_deposit_status.sql") },
+    Migration { version: 9, name: "backfill_jobs", sql: include_str!("../../migrations/0009_backfill_jobs.sql") },
+    Migration { version: 10, name: "outbox_dead_letters", sql: include_str!("../../migrations/0010_outbox_dead_letters.sql") },
+    Migration { version: 11, name: "outbox_sink_deliveries", sql: include_str!("../../migrations/0011_outbox_sink_deliveries.sql") },
+];
+
+/// 마이그레이션 적용 (dry_run이면 적용할 목록만 반환)
+///
+/// 이미 적용된 마이그레이션의 checksum이 다르거나, DB에 이 바이너리가 모르는 버전이 있으면 실패한다.
+/// 각 마이그레이션은 schema_migrations 기록과 함께 하나의 트랜잭션으로 적용된다.
+pub async fn run_migrations(pool: &PgPool, dry_run: bool) -> Result<Vec<&'static Migration>, AppError> {
+    let mut conn = pool.acquire().await
+        .map_err(|e| AppError::Database(format!("Failed to acquire connection: {}", e)))?;
+
+    sqlx::query("SELECT pg_advisory_lock($1)")
+        .bind(MIGRATION_LOCK_ID)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to acquire migration lock: {}", e)))?;
+
+    let result = apply_pending(&mut conn, dry_run).await;
+
+    if let Err(e) = sqlx::query("SELECT pg_advisory_unlock($1)")
+        .bind(MIGRATION_LOCK_ID)
+        .execute(&mut *conn)
+        .await
+    {
+        warn!("Failed to release migration lock: {}", e);
+    }
+
+    result
+}
+
+/// 아직 적용되지 않은 마이그레이션 (checksum 검증 포함)
+pub async fn pending_migrations(pool: &PgPool) -> Result<Vec<&'static Migration>, AppError> {
+    let mut conn = pool.acquire().await
+        .map_err(|e| AppError::Database(format!("Failed to acquire connection: {}", e)))?;
+    pending(&mut conn).await
+}
+
+async fn apply_pending(
+    conn: &mut sqlx::PgConnection,
+    dry_run: bool,
+) -> Result<Vec<&'static Migration>, AppError> {
+    let pending = pending(conn).await?;
+
+    if pending.is_empty() {
+        info!("Database schema is up to date (version {})", MIGRATIONS.last().map(|m| m.version).unwrap_or(0));
+        return Ok(pending);
+    }
+
+    for migration in &pending {
+        if dry_run {
+            info!("[dry-run] Would apply migration {:04}_{}", migration.version, migration.name);
+            continue;
+        }
+
+        info!("Applying migration {:04}_{}", migration.version, migration.name);
+
+        let mut tx = conn.begin().await
+            .map_err(|e| AppError::Database(format!("Failed to begin migration transaction: {}", e)))?;
+
+        // 여러 statement를 담은 SQL 파일은 simple query protocol로 실행
+        (&mut *tx).execute(migration.sql).await
+            .map_err(|e| AppError::Database(format!("Migration {:04}_{} failed: {}", migration.version, migration.name, e)))?;
+
+        sqlx::query(&format!(
+            "INSERT INTO {} (version, name, checksum) VALUES ($1, $2, $3)",
+            SCHEMA_MIGRATIONS_TABLE
+        ))
+            .bind(migration.version)
+            .bind(migration.name)
+            .bind(migration.checksum())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Database(format!("Failed to record migration {}: {}", migration.version, e)))?;
+
+        tx.commit().await
+            .map_err(|e| AppError::Database(format!("Failed to commit migration {}: {}", migration.version, e)))?;
+    }
+
+    Ok(pending)
+}
+
+async fn pending(conn: &mut sqlx::PgConnection) -> Result<Vec<&'static Migration>, AppError> {
+    sqlx::query(&format!(
+        r#"
+        CREATE TABLE IF NOT EXISTS {} (
+            version BIGINT PRIMARY KEY,
+            name VARCHAR(255) NOT NULL,
+            checksum VARCHAR(64) NOT NULL,
+            applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+        SCHEMA_MIGRATIONS_TABLE
+    ))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to create {}: {}", SCHEMA_MIGRATIONS_TABLE, e)))?;
+
+    let applied: Vec<(i64, String, String)> = sqlx::query_as(&format!(
+        "SELECT version, name, checksum FROM {} ORDER BY version ASC",
+        SCHEMA_MIGRATIONS_TABLE
+    ))
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| AppError::Database(format!("Failed to read applied migrations: {}", e)))?;
+
+    unapplied(MIGRATIONS, &applied)
+}
+
+/// 적용 기록(version, name, checksum)을 검증하고 아직 적용되지 않은 마이그레이션을 반환
+fn unapplied<'a>(migrations: &'a [Migration], applied: &[(i64, String, String)]) -> Result<Vec<&'a Migration>, AppError> {
+    for (version, name, checksum) in applied {
+        let migration = migrations.iter()
+            .find(|m| m.version == *version)
+            .ok_or_else(|| AppError::Database(format!(
+                "Database has migration {:04}_{} which this binary does not know (schema is newer than the binary)",
+                version, name
+            )))?;
+
+        if migration.checksum() != *checksum {
+            return Err(AppError::Database(format!(
+                "Checksum mismatch for applied migration {:04}_{} (file was modified after it was applied)",
+                version, name
+            )));
+        }
+    }
+
+    Ok(migrations.iter()
+        .filter(|m| !applied.iter().any(|(version, _, _)| *version == m.version))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 배포된 마이그레이션 checksum - 값이 바뀌면 기존 DB에서 시작이 실패한다
+    const DEPLOYED_CHECKSUMS: &[(i64, &str)] = &[
+        (1, "ea70ec65333af6f7553b5501d70fb20d3e64d06deb6b981df8f9aabc4aa1d992"),
+        (2, "3b432900959014b36705863805921e281cec555442a887442e90d04f9b08cfa8"),
+        (3, "62403ab1a22de962d98e46e3bdec81f60e358ce4ba72136dfa75b577dcb525a3"),
+        (4, "2dfe44b37ee3663dde8aeda9a2cb49bc6c9b04b5f80925c8f6074d39236a730a"),
+        (5, "50026266029a88f0315f12ec7e63af48986970ca76d0ccea0bb5a1725ce64bf7"),
+        (6, "3e7a241b3996b9f3151681728e09301f769b302bc46051c85f5715cfa8af6de3"),
+        (7, "6959a8b2b0e3b18803a17ad517c0d4ae06e002ea99cf9b4fc2762b811cc82a55"),
+        (8, "b49db8f5d0cdc27e8b4cb53310f725d5e63d98767143a65ef88b5baebe55a556"),
+        (9, "8ea9f6abbf749a6aa77a3eac6cf34336d215898d20ee580124911cafb3304f8c"),
+        (10, "0e92383c0cf00971b4f9d4ea633fb2a1ea95fd9938bd83017e55a8fce5a63922"),
+        (11, "3ae5ce7a9a88287b86a05d79195970aa297c28025118d62fda1e6e91bd09c8a6"),
+    ];
+
+    fn record(migration: &Migration) -> (i64, String, String) {
+        (migration.version, migration.name.to_string(), migration.checksum())
+    }
+
+    #[test]
+    fn versions_are_contiguous_and_ascending() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as i64 + 1, "{}", migration.name);
+        }
+    }
+
+    #[test]
+    fn every_migration_file_is_registered() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/migrations");
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.ends_with(".sql"))
+            .collect();
+        files.sort();
+
+        let registered: Vec<String> = MIGRATIONS.iter()
+            .map(|m| format!("{:04}_{}.sql", m.version, m.name))
+            .collect();
+        assert_eq!(files, registered);
+    }
+
+    #[test]
+    fn deployed_migrations_are_unchanged() {
+        for (version, checksum) in DEPLOYED_CHECKSUMS {
+            let migration = MIGRATIONS.iter().find(|m| m.version == *version).unwrap();
+            assert_eq!(migration.checksum(), *checksum, "{:04}_{} was modified", version, migration.name);
+        }
+    }
+
+    #[test]
+    fn all_migrations_pending_on_empty_database() {
+        let pending = unapplied(MIGRATIONS, &[]).unwrap();
+        assert_eq!(pending.len(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn only_unapplied_migrations_are_pending() {
+        let applied: Vec<_> = MIGRATIONS[..3].iter().map(record).collect();
+        let pending = unapplied(MIGRATIONS, &applied).unwrap();
+        assert_eq!(pending.first().map(|m| m.version), Some(4));
+        assert_eq!(pending.len(), MIGRATIONS.len() - 3);
+
+        let applied: Vec<_> = MIGRATIONS.iter().map(record).collect();
+        assert!(unapplied(MIGRATIONS, &applied).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_checksum_mismatch() {
+        let mut applied: Vec<_> = MIGRATIONS[..2].iter().map(record).collect();
+        applied[1].2 = "0".repeat(64);
+        let Err(err) = unapplied(MIGRATIONS, &applied) else { panic!("expected an error") };
+        assert!(err.to_string().contains("Checksum mismatch"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_unknown_applied_version() {
+        let mut applied: Vec<_> = MIGRATIONS.iter().map(record).collect();
+        applied.push((MIGRATIONS.len() as i64 + 1, "from_newer_binary".to_string(), "0".repeat(64)));
+        let Err(err) = unapplied(MIGRATIONS, &applied) else { panic!("expected an error") };
+        assert!(err.to_string().contains("does not know"), "{}", err);
+    }
+}
@@ -1,4 +1,7 @@
 mod postgresql;
+pub mod migrations;
+mod address_index;
+mod bloom;
 #[cfg(feature = "leveldb-backend")]
 mod leveldb;
 #[cfg(feature = "rocksdb-backend")]
@@ -24,13 +27,11 @@ pub use leveldb_repo::LevelDBRepository;
 pub use rocksdb_repo::RocksDBRepository;
 pub use wrapper::RepositoryWrapper;
 
+// Address index (관리 대상 주소 조회)
+pub use address_index::{AddressIndex, AddressMetadata, MonitoredAddress, open_address_index};
+
 // Legacy exports (for backward compatibility)
 pub use postgresql::connect_db;
-pub use postgresql::get_last_processed_block;
-pub use postgresql::setup_db_schema;
-pub use postgresql::update_last_processed_block;
-pub use postgresql::init_last_processed_block;
-pub use postgresql::save_deposit_event;
 // Note: increment_customer_balance removed - balance management handled by backend
 // Note: get_customer_id_by_address removed - uses RocksDB cache only
 // Note: load_customer_addresses_to_rocksdb removed - uses SQS + file cache instead
@@ -53,9 +54,19 @@ pub use rocksdb::is_monitored_address_in_rocksdb;
 #[cfg(feature = "rocksdb-backend")]
 pub use rocksdb::get_address_metadata_from_rocksdb;
 #[cfg(feature = "rocksdb-backend")]
+pub use rocksdb::get_address_metadata_batch_from_rocksdb;
+#[cfg(feature = "rocksdb-backend")]
+pub use rocksdb::list_monitored_address_keys;
+#[cfg(feature = "rocksdb-backend")]
 pub use rocksdb::batch_add_monitored_addresses;
 #[cfg(feature = "rocksdb-backend")]
-pub use rocksdb::AddressMetadata;
+pub use rocksdb::remove_monitored_address_from_rocksdb;
+#[cfg(feature = "rocksdb-backend")]
+pub use rocksdb::remove_wallet_addresses_from_rocksdb;
+#[cfg(feature = "rocksdb-backend")]
+pub use rocksdb::count_monitored_addresses_in_rocksdb;
+#[cfg(feature = "rocksdb-backend")]
+pub use rocksdb::list_monitored_addresses_in_rocksdb;
 // Deprecated exports for backward compatibility
 #[cfg(feature = "rocksdb-backend")]
 #[allow(deprecated)]
@@ -1,7 +1,13 @@
 // src/db.rs
-use crate::types::AppError;
-use sqlx::{postgres::PgPoolOptions, PgPool, Row};
-use log::{info, warn};
+use crate::notification::DepositEvent;
+use crate::types::{
+  AppError, AssetInfo, BackfillJob, BackfillStatus, BlockCommit, DepositFilter, DepositRecord, DepositStatus,
+  DepositStatusChange, DepositTransition, FailedDeposit, NewDeposit, StatusTransition,
+};
+use crate::respository::address_index::{AddressMetadata, MonitoredAddress};
+use sqlx::{postgres::{PgPoolOptions, PgRow}, PgExecutor, PgPool, Postgres, QueryBuilder, Row, Transaction};
+use log::info;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 
@@ -12,68 +18,19 @@ pub async fn connect_db(db_url: &str) -> Result<PgPool, sqlx::Error> {
     .await
 }
 
+// 테이블 스키마는 migrations/*.sql로 관리 (respository::migrations)
 pub const STATE_TABLE_NAME: &str = "blockchain_state";
-pub const CUSTOMER_ADDRESSES_TABLE: &str = "customer_addresses";
+pub const MONITORED_ADDRESSES_TABLE: &str = "monitored_addresses";
 pub const DEPOSIT_EVENTS_TABLE: &str = "deposit_events";
-pub const CUSTOMER_BALANCES_TABLE: &str = "customer_balances";
-
-// Ensure all tables exist
-pub async fn setup_db_schema(pool: &PgPool) -> Result<(), sqlx::Error> {
-  // ???? ?? ???
-  sqlx::query(&format!(
-    r#"
-        CREATE TABLE IF NOT EXISTS {} (
-            chain_name VARCHAR(50) PRIMARY KEY,
-            last_processed_block BIGINT NOT NULL
-        )
-        "#,
-    STATE_TABLE_NAME
-  ))
-    .execute(pool)
-    .await?;
-  
-  // Note: customer_addresses table removed
-  // Customer addresses are managed by blockbit-back-custody (Backend)
-  // xScanner receives address updates via SQS and caches them in RocksDB only
-  
-  // 입금 이벤트 테이블 (wallet_id, account_id 추가)
-  sqlx::query(&format!(
-    r#"
-        CREATE TABLE IF NOT EXISTS {} (
-            id SERIAL PRIMARY KEY,
-            address VARCHAR(255) NOT NULL,
-            wallet_id VARCHAR(255) NOT NULL,
-            account_id VARCHAR(255),
-            chain_name VARCHAR(50) NOT NULL,
-            tx_hash VARCHAR(255) NOT NULL,
-            block_number BIGINT NOT NULL,
-            amount VARCHAR(255) NOT NULL,
-            amount_decimal NUMERIC(36, 18),
-            confirmed BOOLEAN DEFAULT FALSE,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            UNIQUE(chain_name, tx_hash)
-        )
-        "#,
-    DEPOSIT_EVENTS_TABLE
-  ))
-    .execute(pool)
-    .await?;
-
-  // 인덱스 생성
-  let _ = sqlx::query(&format!("CREATE INDEX IF NOT EXISTS idx_de_address ON {} (address)", DEPOSIT_EVENTS_TABLE))
-    .execute(pool)
-    .await;
-  let _ = sqlx::query(&format!("CREATE INDEX IF NOT EXISTS idx_de_block_number ON {} (block_number)", DEPOSIT_EVENTS_TABLE))
-    .execute(pool)
-    .await;
-
-  // Note: customer_balances table is NOT created by xScanner
-  // Balance management is handled by blockbit-back-custody
-  // xScanner only logs deposit events for audit purposes
-
-  Ok(())
-}
+pub const BLOCK_HASHES_TABLE: &str = "block_hashes";
+pub const FAILED_DEPOSITS_TABLE: &str = "failed_deposit_events";
+pub const NOTIFICATION_OUTBOX_TABLE: &str = "notification_outbox";
+pub const NOTIFICATION_DELIVERIES_TABLE: &str = "notification_deliveries";
+pub const DEPOSIT_STATUS_HISTORY_TABLE: &str = "deposit_status_history";
+pub const BACKFILL_JOBS_TABLE: &str = "backfill_jobs";
 
+// pending_deposit_from_row가 읽는 컬럼
+const PENDING_DEPOSIT_COLUMNS: &str = "address, wallet_id, account_id, chain_name, tx_hash, output_index, block_number, amount, amount_decimal, asset, token_address, decimals, block_hash, status, status_version";
 
 pub async fn get_last_processed_block(
   pool: &PgPool,
@@ -93,8 +50,8 @@ pub async fn get_last_processed_block(
 }
 
 
-pub async fn update_last_processed_block(
-  pool: &PgPool,
+pub async fn update_last_processed_block<'e, E: PgExecutor<'e>>(
+  executor: E,
   chain: &str,
   block_number: u64,
 ) -> Result<(), AppError> {
@@ -110,12 +67,27 @@ pub async fn update_last_processed_block(
   sqlx::query(&query)
     .bind(chain)
     .bind(block_number as i64) // Bind as i64 if column is BIGINT
-    .execute(pool)
+    .execute(executor)
     .await
     .map_err(|e| AppError::Database(format!("Failed to update last block: {}", e)))?; // Use specific error variant
   Ok(())
 }
 
+// 체인별 마지막 처리 블록
+pub async fn list_last_processed_blocks(
+  pool: &PgPool,
+) -> Result<Vec<(String, u64)>, AppError> {
+  let rows: Vec<(String, i64)> = sqlx::query_as(&format!(
+    "SELECT chain_name, last_processed_block FROM {} ORDER BY chain_name ASC",
+    STATE_TABLE_NAME
+  ))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to list last processed blocks: {}", e)))?;
+
+  Ok(rows.into_iter().map(|(chain, block)| (chain, block.max(0) as u64)).collect())
+}
+
 // ?? ?? ??? DB? ?? (?? ?? ??)
 pub async fn init_last_processed_block(
   pool: &PgPool,
@@ -137,50 +109,312 @@ pub async fn init_last_processed_block(
   Ok(())
 }
 
-// Note: get_customer_id_by_address removed
-// xScanner no longer queries customer addresses from PostgreSQL
-// All address lookups are done via RocksDB cache (populated from Backend via SQS)
+// 관리 대상 주소 조회 (chain_name/address는 소문자로 저장)
+pub async fn get_monitored_address(
+  pool: &PgPool,
+  address: &str,
+  chain_name: &str,
+) -> Result<Option<AddressMetadata>, AppError> {
+  let query = format!(
+    "SELECT wallet_id, account_id FROM {} WHERE chain_name = $1 AND address = $2",
+    MONITORED_ADDRESSES_TABLE
+  );
+
+  let row: Option<(String, Option<String>)> = sqlx::query_as(&query)
+    .bind(chain_name.to_lowercase())
+    .bind(address.to_lowercase())
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to get monitored address: {}", e)))?;
+
+  Ok(row.map(|(wallet_id, account_id)| AddressMetadata { wallet_id, account_id }))
+}
 
-// 입금 이벤트 저장 (wallet_id, account_id 추가)
-pub async fn save_deposit_event(
+// 여러 주소 일괄 조회 (소문자 주소 -> 메타데이터)
+pub async fn get_monitored_addresses(
+  pool: &PgPool,
+  addresses: &[String],
+  chain_name: &str,
+) -> Result<HashMap<String, AddressMetadata>, AppError> {
+  if addresses.is_empty() {
+    return Ok(HashMap::new());
+  }
+
+  let query = format!(
+    "SELECT address, wallet_id, account_id FROM {} WHERE chain_name = $1 AND address = ANY($2)",
+    MONITORED_ADDRESSES_TABLE
+  );
+  let normalized: Vec<String> = addresses.iter().map(|a| a.to_lowercase()).collect();
+
+  let rows: Vec<(String, String, Option<String>)> = sqlx::query_as(&query)
+    .bind(chain_name.to_lowercase())
+    .bind(normalized)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to get monitored addresses: {}", e)))?;
+
+  Ok(rows.into_iter()
+    .map(|(address, wallet_id, account_id)| (address, AddressMetadata { wallet_id, account_id }))
+    .collect())
+}
+
+// 관리 대상 주소 등록 (이미 있으면 메타데이터 갱신)
+pub async fn upsert_monitored_addresses(
+  pool: &PgPool,
+  entries: &[MonitoredAddress],
+) -> Result<usize, AppError> {
+  let query = format!(
+    r#"
+        INSERT INTO {} (chain_name, address, wallet_id, account_id)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (chain_name, address) DO UPDATE SET
+            wallet_id = EXCLUDED.wallet_id,
+            account_id = EXCLUDED.account_id,
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+    MONITORED_ADDRESSES_TABLE
+  );
+
+  let mut tx = pool.begin().await
+    .map_err(|e| AppError::Database(format!("Failed to begin transaction: {}", e)))?;
+
+  for entry in entries {
+    sqlx::query(&query)
+      .bind(entry.chain_name.to_lowercase())
+      .bind(entry.address.to_lowercase())
+      .bind(&entry.metadata.wallet_id)
+      .bind(entry.metadata.account_id.as_deref())
+      .execute(&mut *tx)
+      .await
+      .map_err(|e| AppError::Database(format!("Failed to upsert monitored address: {}", e)))?;
+  }
+
+  tx.commit().await
+    .map_err(|e| AppError::Database(format!("Failed to commit monitored addresses: {}", e)))?;
+
+  Ok(entries.len())
+}
+
+// 관리 대상 주소 삭제
+pub async fn delete_monitored_address(
   pool: &PgPool,
   address: &str,
+  chain_name: &str,
+) -> Result<bool, AppError> {
+  let query = format!(
+    "DELETE FROM {} WHERE chain_name = $1 AND address = $2",
+    MONITORED_ADDRESSES_TABLE
+  );
+
+  let result = sqlx::query(&query)
+    .bind(chain_name.to_lowercase())
+    .bind(address.to_lowercase())
+    .execute(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to delete monitored address: {}", e)))?;
+
+  Ok(result.rows_affected() > 0)
+}
+
+// wallet_id에 속한 모든 주소 삭제 (지갑 비활성화)
+pub async fn delete_wallet_addresses(
+  pool: &PgPool,
   wallet_id: &str,
-  account_id: Option<&str>,
+) -> Result<usize, AppError> {
+  let query = format!(
+    "DELETE FROM {} WHERE wallet_id = $1",
+    MONITORED_ADDRESSES_TABLE
+  );
+
+  let result = sqlx::query(&query)
+    .bind(wallet_id)
+    .execute(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to delete wallet addresses: {}", e)))?;
+
+  Ok(result.rows_affected() as usize)
+}
+
+// 체인별 관리 대상 주소 수
+pub async fn count_monitored_addresses(
+  pool: &PgPool,
   chain_name: &str,
-  tx_hash: &str,
-  block_number: u64,
-  amount: &str,
-  amount_decimal: Option<rust_decimal::Decimal>,
+) -> Result<usize, AppError> {
+  let query = format!(
+    "SELECT COUNT(*) FROM {} WHERE chain_name = $1",
+    MONITORED_ADDRESSES_TABLE
+  );
+
+  let row: (i64,) = sqlx::query_as(&query)
+    .bind(chain_name.to_lowercase())
+    .fetch_one(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to count monitored addresses: {}", e)))?;
+
+  Ok(row.0 as usize)
+}
+
+// 관리 대상 주소 목록 (chain_name이 None이면 전체)
+pub async fn list_monitored_addresses(
+  pool: &PgPool,
+  chain_name: Option<&str>,
+) -> Result<Vec<MonitoredAddress>, AppError> {
+  let query = format!(
+    "SELECT address, chain_name, wallet_id, account_id FROM {} WHERE ($1::TEXT IS NULL OR chain_name = $1) ORDER BY chain_name, address",
+    MONITORED_ADDRESSES_TABLE
+  );
+
+  let rows: Vec<(String, String, String, Option<String>)> = sqlx::query_as(&query)
+    .bind(chain_name.map(str::to_lowercase))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to list monitored addresses: {}", e)))?;
+
+  Ok(rows.into_iter()
+    .map(|(address, chain_name, wallet_id, account_id)| MonitoredAddress {
+      address,
+      chain_name,
+      metadata: AddressMetadata { wallet_id, account_id },
+    })
+    .collect())
+}
+
+// 블록 하나의 분석 결과를 하나의 트랜잭션으로 적용 (입금, 상태 전이, 실패 기록, 진행 위치, 블록 해시)
+// 어느 하나라도 실패하면 트랜잭션은 drop 시 rollback되어 아무것도 기록되지 않음
+pub async fn commit_block(
+  pool: &PgPool,
+  commit: &BlockCommit,
+) -> Result<usize, AppError> {
+  let chain_name = commit.chain_name.as_str();
+  let mut tx = pool.begin().await
+    .map_err(|e| AppError::Database(format!("Failed to begin block transaction: {}", e)))?;
+
+  for deposit in &commit.deposits {
+    insert_deposit(&mut tx, chain_name, deposit).await?;
+  }
+
+  let mut applied = 0;
+  for transition in &commit.transitions {
+    if apply_transition(&mut tx, chain_name, transition).await? {
+      applied += 1;
+    }
+  }
+
+  for failed in &commit.failed_deposits {
+    insert_failed_deposit(&mut tx, chain_name, failed).await?;
+  }
+
+  if let Some(cursor) = &commit.cursor {
+    update_last_processed_block(&mut *tx, chain_name, commit.block_number).await?;
+    if let Some(block_ref) = &cursor.block_ref {
+      save_block_hash(&mut *tx, chain_name, commit.block_number, &block_ref.hash, &block_ref.parent_hash).await?;
+      prune_block_hashes(&mut *tx, chain_name, cursor.prune_below).await?;
+    }
+  }
+
+  tx.commit().await
+    .map_err(|e| AppError::Database(format!("Failed to commit block {} of {}: {}", commit.block_number, chain_name, e)))?;
+
+  Ok(applied)
+}
+
+// 입금 저장 (호출한 쪽의 트랜잭션 안에서)
+// orphaned 입금이 canonical 체인에 다시 포함된 경우에만 기존 행을 갱신 (orphaned -> detected)
+async fn insert_deposit(
+  tx: &mut Transaction<'_, Postgres>,
+  chain_name: &str,
+  deposit: &NewDeposit,
 ) -> Result<(), AppError> {
   let query = format!(
     r#"
-        INSERT INTO {} (address, wallet_id, account_id, chain_name, tx_hash, block_number, amount, amount_decimal)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-        ON CONFLICT (chain_name, tx_hash) DO NOTHING
+        INSERT INTO {0} (address, wallet_id, account_id, chain_name, tx_hash, output_index, block_number, amount, amount_decimal, asset, token_address, decimals, block_hash, status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        ON CONFLICT (chain_name, tx_hash, output_index) DO UPDATE SET
+            block_number = EXCLUDED.block_number,
+            amount = EXCLUDED.amount,
+            amount_decimal = EXCLUDED.amount_decimal,
+            asset = EXCLUDED.asset,
+            token_address = EXCLUDED.token_address,
+            decimals = EXCLUDED.decimals,
+            block_hash = EXCLUDED.block_hash,
+            status = EXCLUDED.status,
+            status_version = {0}.status_version + 1,
+            confirmations = 1,
+            status_updated_at = CURRENT_TIMESTAMP
+        WHERE {0}.status = $15
+        RETURNING (xmax = 0) AS inserted
         "#,
     DEPOSIT_EVENTS_TABLE
   );
 
   // Convert rust_decimal::Decimal to bigdecimal::BigDecimal for sqlx
-  let amount_decimal_bigdecimal = amount_decimal.map(|d| {
+  let amount_decimal_bigdecimal = deposit.amount_decimal.map(|d| {
     bigdecimal::BigDecimal::from_str(&d.to_string())
       .ok()
   }).flatten();
 
-  sqlx::query(&query)
-    .bind(address)
-    .bind(wallet_id)
-    .bind(account_id)
+  let saved: Option<(bool,)> = sqlx::query_as(&query)
+    .bind(&deposit.address)
+    .bind(&deposit.wallet_id)
+    .bind(deposit.account_id.as_deref())
     .bind(chain_name)
-    .bind(tx_hash)
-    .bind(block_number as i64)
-    .bind(amount)
+    .bind(&deposit.tx_hash)
+    .bind(deposit.output_index as i32)
+    .bind(deposit.block_number as i64)
+    .bind(&deposit.amount)
     .bind(amount_decimal_bigdecimal)
-    .execute(pool)
+    .bind(&deposit.asset.symbol)
+    .bind(deposit.asset.token_address.as_deref())
+    .bind(deposit.asset.decimals.map(|d| d as i32))
+    .bind(deposit.block_hash.as_deref())
+    .bind(DepositStatus::Detected.as_str())
+    .bind(DepositStatus::Orphaned.as_str())
+    .fetch_optional(&mut **tx)
     .await
     .map_err(|e| AppError::Database(format!("Failed to save deposit event: {}", e)))?;
 
+  // 이미 저장된 입금이면 이력과 알림도 다시 기록하지 않음
+  if let Some((inserted,)) = saved {
+    let from = (!inserted).then_some(DepositStatus::Orphaned);
+    insert_status_history(tx, chain_name, &deposit.tx_hash, deposit.output_index, from, DepositStatus::Detected, 1).await?;
+
+    if let Some(event) = &deposit.notification {
+      insert_outbox_entry(tx, event).await?;
+    }
+  }
+
+  Ok(())
+}
+
+// 실패한 트랜잭션의 입금 시도 저장 (감사용, 호출한 쪽의 트랜잭션 안에서)
+async fn insert_failed_deposit(
+  tx: &mut Transaction<'_, Postgres>,
+  chain_name: &str,
+  failed: &FailedDeposit,
+) -> Result<(), AppError> {
+  let query = format!(
+    r#"
+        INSERT INTO {} (address, chain_name, tx_hash, output_index, block_number, amount, asset, token_address, reason)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (chain_name, tx_hash, output_index) DO NOTHING
+        "#,
+    FAILED_DEPOSITS_TABLE
+  );
+
+  sqlx::query(&query)
+    .bind(&failed.address)
+    .bind(chain_name)
+    .bind(&failed.tx_hash)
+    .bind(failed.output_index as i32)
+    .bind(failed.block_number as i64)
+    .bind(&failed.amount)
+    .bind(&failed.asset.symbol)
+    .bind(failed.asset.token_address.as_deref())
+    .bind(&failed.reason)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to save failed deposit: {}", e)))?;
+
   Ok(())
 }
 
@@ -188,111 +422,730 @@ pub async fn save_deposit_event(
 // Balance management is handled by blockbit-back-custody, not xScanner
 // xScanner only logs deposit events for audit/reconciliation purposes
 
-// Note: load_customer_addresses_to_rocksdb removed
-// RocksDB cache is populated via SQS messages from Backend
-// No need to load from PostgreSQL (customer_addresses table doesn't exist in xScanner DB)
-
-// Check if a deposit already exists in the database
-pub async fn deposit_exists(
+// 입금 상태와 status_version 조회
+pub async fn get_deposit_status(
   pool: &PgPool,
   tx_hash: &str,
+  output_index: u32,
   chain_name: &str,
-) -> Result<bool, AppError> {
+) -> Result<Option<(DepositStatus, i64)>, AppError> {
   let query = format!(
-    "SELECT EXISTS(SELECT 1 FROM {} WHERE tx_hash = $1 AND chain_name = $2)",
+    "SELECT status, status_version FROM {} WHERE tx_hash = $1 AND output_index = $2 AND chain_name = $3",
     DEPOSIT_EVENTS_TABLE
   );
 
-  let row: (bool,) = sqlx::query_as(&query)
+  let row: Option<(String, i64)> = sqlx::query_as(&query)
     .bind(tx_hash)
+    .bind(output_index as i32)
     .bind(chain_name)
-    .fetch_one(pool)
+    .fetch_optional(pool)
     .await
-    .map_err(|e| AppError::Database(format!("Failed to check deposit existence: {}", e)))?;
+    .map_err(|e| AppError::Database(format!("Failed to get deposit status: {}", e)))?;
 
-  Ok(row.0)
+  row.map(|(status, version)| Ok((parse_status(&status)?, version))).transpose()
 }
 
-// Check if a deposit is already confirmed
-pub async fn is_deposit_confirmed(
+// 입금 상태 전이 (status와 status_version이 일치할 때만 적용, 이력/알림은 같은 트랜잭션)
+pub async fn transition_deposit_status(
   pool: &PgPool,
   tx_hash: &str,
+  output_index: u32,
+  chain_name: &str,
+  transition: &StatusTransition,
+  notification: Option<&DepositEvent>,
 ) -> Result<bool, AppError> {
+  let change = DepositTransition {
+    tx_hash: tx_hash.to_string(),
+    output_index,
+    transition: *transition,
+    notification: notification.cloned(),
+  };
+
+  let mut tx = pool.begin().await
+    .map_err(|e| AppError::Database(format!("Failed to begin transaction: {}", e)))?;
+
+  // 다른 작업이 먼저 상태를 바꿈 (트랜잭션은 drop 시 rollback)
+  if !apply_transition(&mut tx, chain_name, &change).await? {
+    return Ok(false);
+  }
+
+  tx.commit().await
+    .map_err(|e| AppError::Database(format!("Failed to commit deposit status transition: {}", e)))?;
+
+  Ok(true)
+}
+
+// 상태 전이 적용 (호출한 쪽의 트랜잭션 안에서) - 적용되지 않았으면 false
+async fn apply_transition(
+  tx: &mut Transaction<'_, Postgres>,
+  chain_name: &str,
+  change: &DepositTransition,
+) -> Result<bool, AppError> {
+  let transition = &change.transition;
+  transition.validate()?;
+
   let query = format!(
-    "SELECT confirmed FROM {} WHERE tx_hash = $1",
+    r#"
+        UPDATE {} SET status = $4, status_version = status_version + 1, confirmations = $5, status_updated_at = CURRENT_TIMESTAMP
+        WHERE tx_hash = $1 AND output_index = $2 AND chain_name = $3 AND status = $6 AND status_version = $7
+        "#,
     DEPOSIT_EVENTS_TABLE
   );
 
-  let row: Option<(bool,)> = sqlx::query_as(&query)
-    .bind(tx_hash)
-    .fetch_optional(pool)
+  let result = sqlx::query(&query)
+    .bind(&change.tx_hash)
+    .bind(change.output_index as i32)
+    .bind(chain_name)
+    .bind(transition.to.as_str())
+    .bind(transition.confirmations as i64)
+    .bind(transition.from.as_str())
+    .bind(transition.expected_version)
+    .execute(&mut **tx)
     .await
-    .map_err(|e| AppError::Database(format!("Failed to check deposit confirmation status: {}", e)))?;
+    .map_err(|e| AppError::Database(format!("Failed to update deposit status: {}", e)))?;
+
+  if result.rows_affected() == 0 {
+    return Ok(false);
+  }
+
+  insert_status_history(tx, chain_name, &change.tx_hash, change.output_index, Some(transition.from), transition.to, transition.confirmations).await?;
+
+  if let Some(event) = &change.notification {
+    insert_outbox_entry(tx, event).await?;
+  }
 
-  Ok(row.map(|(confirmed,)| confirmed).unwrap_or(false))
+  Ok(true)
 }
 
-// Update deposit confirmation status
-pub async fn update_deposit_confirmed(
+// 입금 상태 전이 이력 (오래된 순)
+pub async fn get_deposit_status_history(
   pool: &PgPool,
   tx_hash: &str,
-) -> Result<(), AppError> {
+  output_index: u32,
+  chain_name: &str,
+) -> Result<Vec<DepositStatusChange>, AppError> {
   let query = format!(
-    "UPDATE {} SET confirmed = TRUE WHERE tx_hash = $1",
-    DEPOSIT_EVENTS_TABLE
+    r#"
+        SELECT from_status, to_status, confirmations, EXTRACT(EPOCH FROM created_at)::BIGINT
+        FROM {} WHERE chain_name = $1 AND tx_hash = $2 AND output_index = $3
+        ORDER BY id ASC
+        "#,
+    DEPOSIT_STATUS_HISTORY_TABLE
   );
 
-  sqlx::query(&query)
+  let rows: Vec<(Option<String>, String, i64, Option<i64>)> = sqlx::query_as(&query)
+    .bind(chain_name)
+    .bind(tx_hash)
+    .bind(output_index as i32)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to get deposit status history: {}", e)))?;
+
+  rows.into_iter()
+    .map(|(from, to, confirmations, changed_at)| Ok(DepositStatusChange {
+      from_status: from.as_deref().map(parse_status).transpose()?,
+      to_status: parse_status(&to)?,
+      confirmations: confirmations.max(0) as u64,
+      changed_at: changed_at.unwrap_or(0).max(0) as u64,
+    }))
+    .collect()
+}
+
+fn parse_status(status: &str) -> Result<DepositStatus, AppError> {
+  DepositStatus::from_str(status).map_err(AppError::Database)
+}
+
+// 상태 전이 이력 기록 (호출한 쪽의 트랜잭션 안에서)
+async fn insert_status_history(
+  tx: &mut Transaction<'_, Postgres>,
+  chain_name: &str,
+  tx_hash: &str,
+  output_index: u32,
+  from: Option<DepositStatus>,
+  to: DepositStatus,
+  confirmations: u64,
+) -> Result<(), AppError> {
+  sqlx::query(&format!(
+    "INSERT INTO {} (chain_name, tx_hash, output_index, from_status, to_status, confirmations) VALUES ($1, $2, $3, $4, $5, $6)",
+    DEPOSIT_STATUS_HISTORY_TABLE
+  ))
+    .bind(chain_name)
     .bind(tx_hash)
+    .bind(output_index as i32)
+    .bind(from.map(|s| s.as_str()))
+    .bind(to.as_str())
+    .bind(confirmations as i64)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to insert deposit status history: {}", e)))?;
+
+  Ok(())
+}
+
+// outbox에 알림 기록 (호출한 쪽의 트랜잭션 안에서)
+async fn insert_outbox_entry(
+  tx: &mut Transaction<'_, Postgres>,
+  event: &DepositEvent,
+) -> Result<(), AppError> {
+  let payload = serde_json::to_string(event)
+    .map_err(|e| AppError::Database(format!("Failed to serialize notification: {}", e)))?;
+
+  sqlx::query(&format!(
+    "INSERT INTO {} (event_type, chain_name, tx_hash, output_index, payload) VALUES ($1, $2, $3, $4, $5)",
+    NOTIFICATION_OUTBOX_TABLE
+  ))
+    .bind(event.event_type())
+    .bind(event.chain())
+    .bind(event.tx_hash())
+    .bind(event.output_index() as i32)
+    .bind(payload)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to insert notification outbox entry: {}", e)))?;
+
+  Ok(())
+}
+
+// sink로 전송 대기 중인 알림 조회 (sink별 전송 상태는 notification_deliveries)
+// 같은 입금의 이전 알림이 이 sink로 아직 전송되지 않았으면 제외 - DepositDetected가 DepositConfirmed보다 먼저 전송되도록
+// 이 sink로 dead 처리된 알림은 전송하지 않고 이후 알림도 막지 않음
+pub async fn get_pending_notifications(
+  pool: &PgPool,
+  sink: &str,
+  limit: usize,
+) -> Result<Vec<crate::tasks::OutboxEntry>, AppError> {
+  let query = format!(
+    r#"
+        SELECT o.id, o.event_type, o.payload, COALESCE(d.attempts, 0) FROM {0} o
+        LEFT JOIN {1} d ON d.outbox_id = o.id AND d.sink = $1
+        WHERE o.sent_at IS NULL AND o.dead_at IS NULL
+          AND d.sent_at IS NULL AND d.dead_at IS NULL
+          AND COALESCE(d.next_attempt_at, o.next_attempt_at) <= CURRENT_TIMESTAMP
+          AND NOT EXISTS (
+            SELECT 1 FROM {0} p
+            LEFT JOIN {1} pd ON pd.outbox_id = p.id AND pd.sink = $1
+            WHERE p.chain_name = o.chain_name AND p.tx_hash = o.tx_hash AND p.output_index = o.output_index
+              AND p.sent_at IS NULL AND p.dead_at IS NULL AND p.id < o.id
+              AND pd.sent_at IS NULL AND pd.dead_at IS NULL
+          )
+        ORDER BY o.id ASC
+        LIMIT $2
+        "#,
+    NOTIFICATION_OUTBOX_TABLE, NOTIFICATION_DELIVERIES_TABLE
+  );
+
+  let rows: Vec<(i64, String, String, i32)> = sqlx::query_as(&query)
+    .bind(sink)
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to get pending notifications: {}", e)))?;
+
+  Ok(rows.into_iter()
+    .map(|(id, event_type, payload, attempts)| crate::tasks::OutboxEntry {
+      id,
+      event_type,
+      payload,
+      attempts: attempts.max(0) as u32,
+    })
+    .collect())
+}
+
+// sink로 알림 전송 완료 표시
+pub async fn mark_notification_sent(
+  pool: &PgPool,
+  id: i64,
+  sink: &str,
+) -> Result<(), AppError> {
+  sqlx::query(&format!(
+    r#"
+        INSERT INTO {0} (outbox_id, sink, sent_at) VALUES ($1, $2, CURRENT_TIMESTAMP)
+        ON CONFLICT (outbox_id, sink) DO UPDATE SET sent_at = CURRENT_TIMESTAMP, last_error = NULL
+        "#,
+    NOTIFICATION_DELIVERIES_TABLE
+  ))
+    .bind(id)
+    .bind(sink)
+    .execute(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to mark notification sent: {}", e)))?;
+
+  Ok(())
+}
+
+// sink로 알림 전송 실패 기록 (retry_after_secs 후 재시도)
+pub async fn mark_notification_failed(
+  pool: &PgPool,
+  id: i64,
+  sink: &str,
+  error: &str,
+  retry_after_secs: u64,
+) -> Result<(), AppError> {
+  sqlx::query(&format!(
+    r#"
+        INSERT INTO {0} (outbox_id, sink, attempts, last_error, next_attempt_at)
+        VALUES ($1, $2, 1, $3, CURRENT_TIMESTAMP + make_interval(secs => $4))
+        ON CONFLICT (outbox_id, sink) DO UPDATE SET attempts = {0}.attempts + 1,
+            last_error = EXCLUDED.last_error,
+            next_attempt_at = EXCLUDED.next_attempt_at
+        "#,
+    NOTIFICATION_DELIVERIES_TABLE
+  ))
+    .bind(id)
+    .bind(sink)
+    .bind(error)
+    .bind(retry_after_secs as f64)
+    .execute(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to record notification failure: {}", e)))?;
+
+  Ok(())
+}
+
+// 재시도 한도를 넘은 알림을 그 sink에 대해서만 dead로 표시 (이 sink로는 더 이상 전송하지 않음)
+pub async fn mark_notification_dead(
+  pool: &PgPool,
+  id: i64,
+  sink: &str,
+  error: &str,
+) -> Result<(), AppError> {
+  sqlx::query(&format!(
+    r#"
+        INSERT INTO {0} (outbox_id, sink, attempts, last_error, dead_at) VALUES ($1, $2, 1, $3, CURRENT_TIMESTAMP)
+        ON CONFLICT (outbox_id, sink) DO UPDATE SET attempts = {0}.attempts + 1,
+            last_error = EXCLUDED.last_error,
+            dead_at = CURRENT_TIMESTAMP
+        "#,
+    NOTIFICATION_DELIVERIES_TABLE
+  ))
+    .bind(id)
+    .bind(sink)
+    .bind(error)
     .execute(pool)
     .await
-    .map_err(|e| AppError::Database(format!("Failed to update deposit confirmation: {}", e)))?;
+    .map_err(|e| AppError::Database(format!("Failed to mark notification dead: {}", e)))?;
 
   Ok(())
 }
 
-// Get all pending (unconfirmed) deposits for confirmation checking
+// 모든 sink로 전송(또는 dead 처리)된 알림 완료 처리 - 하나라도 dead면 dead_at, 아니면 sent_at
+pub async fn complete_notifications(
+  pool: &PgPool,
+  sinks: &[String],
+) -> Result<usize, AppError> {
+  let result = sqlx::query(&format!(
+    r#"
+        WITH finished AS (
+            SELECT o.id, COUNT(*) FILTER (WHERE d.dead_at IS NOT NULL) AS dead
+            FROM {0} o
+            JOIN {1} d ON d.outbox_id = o.id
+            WHERE o.sent_at IS NULL AND o.dead_at IS NULL
+              AND d.sink = ANY($1) AND (d.sent_at IS NOT NULL OR d.dead_at IS NOT NULL)
+            GROUP BY o.id
+            HAVING COUNT(*) = $2
+        )
+        UPDATE {0} o SET
+            sent_at = CASE WHEN f.dead = 0 THEN CURRENT_TIMESTAMP END,
+            dead_at = CASE WHEN f.dead > 0 THEN CURRENT_TIMESTAMP END
+        FROM finished f
+        WHERE o.id = f.id
+        "#,
+    NOTIFICATION_OUTBOX_TABLE, NOTIFICATION_DELIVERIES_TABLE
+  ))
+    .bind(sinks)
+    .bind(sinks.len() as i64)
+    .execute(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to complete notifications: {}", e)))?;
+
+  Ok(result.rows_affected() as usize)
+}
+
+// outbox에 알림 기록 (단독 트랜잭션)
+pub async fn enqueue_notification(
+  pool: &PgPool,
+  event: &DepositEvent,
+) -> Result<(), AppError> {
+  let mut tx = pool.begin().await
+    .map_err(|e| AppError::Database(format!("Failed to begin transaction: {}", e)))?;
+  insert_outbox_entry(&mut tx, event).await?;
+  tx.commit().await
+    .map_err(|e| AppError::Database(format!("Failed to commit notification: {}", e)))?;
+
+  Ok(())
+}
+
+// 입금 조회 (조건은 모두 AND, address/chain은 대소문자 무시)
+pub async fn list_deposits(
+  pool: &PgPool,
+  filter: &DepositFilter,
+) -> Result<Vec<DepositRecord>, AppError> {
+  let mut query = QueryBuilder::<Postgres>::new(format!(
+    "SELECT {}, confirmations FROM {} WHERE TRUE",
+    PENDING_DEPOSIT_COLUMNS, DEPOSIT_EVENTS_TABLE
+  ));
+  if let Some(wallet_id) = &filter.wallet_id {
+    query.push(" AND wallet_id = ").push_bind(wallet_id);
+  }
+  if let Some(account_id) = &filter.account_id {
+    query.push(" AND account_id = ").push_bind(account_id);
+  }
+  if let Some(address) = &filter.address {
+    query.push(" AND LOWER(address) = LOWER(").push_bind(address).push(")");
+  }
+  if let Some(chain_name) = &filter.chain_name {
+    query.push(" AND UPPER(chain_name) = UPPER(").push_bind(chain_name).push(")");
+  }
+  if let Some(tx_hash) = &filter.tx_hash {
+    query.push(" AND tx_hash = ").push_bind(tx_hash);
+  }
+  if let Some(status) = filter.status {
+    query.push(" AND status = ").push_bind(status.as_str());
+  }
+  query.push(" ORDER BY block_number DESC, id DESC LIMIT ").push_bind(filter.limit as i64);
+  query.push(" OFFSET ").push_bind(filter.offset as i64);
+
+  let rows = query.build()
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to list deposits: {}", e)))?;
+
+  Ok(rows.iter().map(|row| {
+    let deposit = pending_deposit_from_row(row);
+    let confirmations: i64 = row.get("confirmations");
+    DepositRecord {
+      address: deposit.address,
+      wallet_id: deposit.wallet_id,
+      account_id: deposit.account_id,
+      chain: deposit.chain_name,
+      tx_hash: deposit.tx_hash,
+      output_index: deposit.output_index,
+      block_number: deposit.block_number,
+      block_hash: deposit.block_hash,
+      amount: deposit.amount,
+      amount_decimal: deposit.amount_decimal,
+      asset: deposit.asset,
+      status: deposit.status,
+      status_version: deposit.status_version,
+      confirmations: confirmations.max(0) as u64,
+    }
+  }).collect())
+}
+
+// Get all pending (detected / confirming) deposits for confirmation checking
 pub async fn get_pending_deposits(
   pool: &PgPool,
 ) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
   let query = format!(
-    "SELECT address, wallet_id, account_id, chain_name, tx_hash, block_number, amount, amount_decimal FROM {} WHERE confirmed = FALSE ORDER BY block_number ASC",
-    DEPOSIT_EVENTS_TABLE
+    "SELECT {} FROM {} WHERE status IN ($1, $2) ORDER BY block_number ASC",
+    PENDING_DEPOSIT_COLUMNS, DEPOSIT_EVENTS_TABLE
   );
 
   let rows = sqlx::query(&query)
+    .bind(DepositStatus::Detected.as_str())
+    .bind(DepositStatus::Confirming.as_str())
     .fetch_all(pool)
     .await
     .map_err(|e| AppError::Database(format!("Failed to get pending deposits: {}", e)))?;
 
-  let mut deposits = Vec::new();
-  for row in rows {
-    let address: String = row.get("address");
-    let wallet_id: String = row.get("wallet_id");
-    let account_id: Option<String> = row.get("account_id");
-    let chain_name: String = row.get("chain_name");
-    let tx_hash: String = row.get("tx_hash");
-    let block_number: i64 = row.get("block_number");
-    let amount: String = row.get("amount");
-    let amount_decimal_bigdecimal: Option<bigdecimal::BigDecimal> = row.get("amount_decimal");
-
-    // Convert bigdecimal::BigDecimal to rust_decimal::Decimal
-    let amount_decimal = amount_decimal_bigdecimal.and_then(|bd| {
-      rust_decimal::Decimal::from_str(&bd.to_string()).ok()
-    });
-
-    deposits.push(crate::tasks::PendingDeposit {
-      address,
-      wallet_id,
-      account_id,
-      chain_name,
-      tx_hash,
-      block_number: block_number as u64,
-      amount,
-      amount_decimal,
-    });
+  Ok(rows.iter().map(pending_deposit_from_row).collect())
+}
+
+fn pending_deposit_from_row(row: &PgRow) -> crate::tasks::PendingDeposit {
+  let amount_decimal_bigdecimal: Option<bigdecimal::BigDecimal> = row.get("amount_decimal");
+
+  // Convert bigdecimal::BigDecimal to rust_decimal::Decimal
+  let amount_decimal = amount_decimal_bigdecimal.and_then(|bd| {
+    rust_decimal::Decimal::from_str(&bd.to_string()).ok()
+  });
+  let block_number: i64 = row.get("block_number");
+  let output_index: i32 = row.get("output_index");
+  let chain_name: String = row.get("chain_name");
+  let status: String = row.get("status");
+
+  // 자산 컬럼 추가 이전의 행은 네이티브 코인 입금
+  let symbol: Option<String> = row.get("asset");
+  let decimals: Option<i32> = row.get("decimals");
+  let asset = AssetInfo {
+    symbol: symbol.unwrap_or_else(|| chain_name.clone()),
+    token_address: row.get("token_address"),
+    decimals: decimals.map(|d| d as u32),
+  };
+
+  crate::tasks::PendingDeposit {
+    address: row.get("address"),
+    wallet_id: row.get("wallet_id"),
+    account_id: row.get("account_id"),
+    chain_name,
+    tx_hash: row.get("tx_hash"),
+    output_index: output_index as u32,
+    block_number: block_number as u64,
+    amount: row.get("amount"),
+    amount_decimal,
+    asset,
+    block_hash: row.get("block_hash"),
+    // CHECK 제약으로 모르는 값은 저장되지 않지만, 혹시 있으면 운영자 확인 대상으로 취급
+    status: DepositStatus::from_str(&status).unwrap_or(DepositStatus::FlaggedForReview),
+    status_version: row.get("status_version"),
   }
+}
+
+// 처리한 블록의 해시 저장 (reorg 감지용)
+async fn save_block_hash<'e, E: PgExecutor<'e>>(
+  executor: E,
+  chain: &str,
+  block_number: u64,
+  block_hash: &str,
+  parent_hash: &str,
+) -> Result<(), AppError> {
+  let query = format!(
+    r#"
+        INSERT INTO {0} (chain_name, block_number, block_hash, parent_hash)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (chain_name, block_number) DO UPDATE SET block_hash = $3, parent_hash = $4
+        "#,
+    BLOCK_HASHES_TABLE
+  );
 
-  Ok(deposits)
+  sqlx::query(&query)
+    .bind(chain)
+    .bind(block_number as i64)
+    .bind(block_hash)
+    .bind(parent_hash)
+    .execute(executor)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to save block hash: {}", e)))?;
+
+  Ok(())
+}
+
+// 최근 블록 해시 조회 (block_number 오름차순)
+pub async fn get_recent_block_hashes(
+  pool: &PgPool,
+  chain: &str,
+  limit: u64,
+) -> Result<Vec<(u64, String)>, AppError> {
+  let query = format!(
+    "SELECT block_number, block_hash FROM {} WHERE chain_name = $1 ORDER BY block_number DESC LIMIT $2",
+    BLOCK_HASHES_TABLE
+  );
+
+  let rows: Vec<(i64, String)> = sqlx::query_as(&query)
+    .bind(chain)
+    .bind(limit as i64)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to get recent block hashes: {}", e)))?;
+
+  Ok(rows.into_iter().rev().map(|(n, h)| (n as u64, h)).collect())
+}
+
+// below_block 미만의 블록 해시 삭제
+async fn prune_block_hashes<'e, E: PgExecutor<'e>>(
+  executor: E,
+  chain: &str,
+  below_block: u64,
+) -> Result<(), AppError> {
+  let query = format!(
+    "DELETE FROM {} WHERE chain_name = $1 AND block_number < $2",
+    BLOCK_HASHES_TABLE
+  );
+
+  sqlx::query(&query)
+    .bind(chain)
+    .bind(below_block as i64)
+    .execute(executor)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to prune block hashes: {}", e)))?;
+
+  Ok(())
+}
+
+// Reorg rollback (하나의 트랜잭션으로 처리)
+// 1. common_ancestor 이후 입금을 orphaned로 전이 (상태 이력, notify면 DepositOrphaned 알림)
+// 2. common_ancestor 이후 블록 해시 삭제
+// 3. last_processed_block을 common_ancestor로 되돌림
+pub async fn rollback_to_block(
+  pool: &PgPool,
+  chain: &str,
+  common_ancestor: u64,
+  notify: bool,
+) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
+  let mut tx = pool.begin().await
+    .map_err(|e| AppError::Database(format!("Failed to begin rollback transaction: {}", e)))?;
+
+  // orphaned로 전이할 수 있는 상태 (failed / 이미 orphaned인 입금은 그대로)
+  let orphanable = [
+    DepositStatus::Detected.as_str(),
+    DepositStatus::Confirming.as_str(),
+    DepositStatus::Confirmed.as_str(),
+    DepositStatus::FlaggedForReview.as_str(),
+  ];
+
+  let orphaned_rows = sqlx::query(&format!(
+    r#"
+        WITH previous AS (
+            SELECT id, status, confirmations FROM {0}
+            WHERE chain_name = $1 AND block_number > $2 AND status = ANY($3)
+            FOR UPDATE
+        )
+        UPDATE {0} d SET status = $4, status_version = d.status_version + 1, status_updated_at = CURRENT_TIMESTAMP
+        FROM previous p
+        WHERE d.id = p.id
+        RETURNING d.*, p.status AS previous_status, p.confirmations AS previous_confirmations
+        "#,
+    DEPOSIT_EVENTS_TABLE
+  ))
+    .bind(chain)
+    .bind(common_ancestor as i64)
+    .bind(&orphanable[..])
+    .bind(DepositStatus::Orphaned.as_str())
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to mark orphaned deposits: {}", e)))?;
+
+  let mut orphaned = Vec::with_capacity(orphaned_rows.len());
+  for row in &orphaned_rows {
+    let deposit = pending_deposit_from_row(row);
+    let previous_status: String = row.get("previous_status");
+    let previous_confirmations: i64 = row.get("previous_confirmations");
+
+    insert_status_history(
+      &mut tx,
+      &deposit.chain_name,
+      &deposit.tx_hash,
+      deposit.output_index,
+      Some(parse_status(&previous_status)?),
+      DepositStatus::Orphaned,
+      previous_confirmations.max(0) as u64,
+    ).await?;
+
+    if notify {
+      insert_outbox_entry(&mut tx, &deposit.orphaned_event()).await?;
+    }
+    orphaned.push(deposit);
+  }
+
+  sqlx::query(&format!(
+    "DELETE FROM {} WHERE chain_name = $1 AND block_number > $2",
+    BLOCK_HASHES_TABLE
+  ))
+    .bind(chain)
+    .bind(common_ancestor as i64)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to delete orphaned block hashes: {}", e)))?;
+
+  sqlx::query(&format!(
+    "UPDATE {} SET last_processed_block = $2 WHERE chain_name = $1 AND last_processed_block > $2",
+    STATE_TABLE_NAME
+  ))
+    .bind(chain)
+    .bind(common_ancestor as i64)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to rewind last processed block: {}", e)))?;
+
+  tx.commit().await
+    .map_err(|e| AppError::Database(format!("Failed to commit rollback transaction: {}", e)))?;
+
+  Ok(orphaned)
+}
+
+const BACKFILL_JOB_COLUMNS: &str = "id, chain_name, from_block, to_block, next_block, address, status, error, EXTRACT(EPOCH FROM created_at)::BIGINT, EXTRACT(EPOCH FROM updated_at)::BIGINT";
+
+type BackfillJobRow = (i64, String, i64, i64, i64, Option<String>, String, Option<String>, Option<i64>, Option<i64>);
+
+fn backfill_job_from_row(row: BackfillJobRow) -> Result<BackfillJob, AppError> {
+  let (id, chain, from_block, to_block, next_block, address, status, error, created_at, updated_at) = row;
+  Ok(BackfillJob {
+    id,
+    chain,
+    from_block: from_block.max(0) as u64,
+    to_block: to_block.max(0) as u64,
+    next_block: next_block.max(0) as u64,
+    address,
+    status: BackfillStatus::from_str(&status).map_err(AppError::Database)?,
+    error,
+    created_at: created_at.unwrap_or(0).max(0) as u64,
+    updated_at: updated_at.unwrap_or(0).max(0) as u64,
+  })
 }
 
+// backfill 작업 등록
+pub async fn create_backfill_job(
+  pool: &PgPool,
+  chain: &str,
+  from_block: u64,
+  to_block: u64,
+  address: Option<&str>,
+) -> Result<BackfillJob, AppError> {
+  let query = format!(
+    "INSERT INTO {} (chain_name, from_block, to_block, next_block, address) VALUES ($1, $2, $3, $2, $4) RETURNING {}",
+    BACKFILL_JOBS_TABLE, BACKFILL_JOB_COLUMNS
+  );
+
+  let row: BackfillJobRow = sqlx::query_as(&query)
+    .bind(chain)
+    .bind(from_block as i64)
+    .bind(to_block as i64)
+    .bind(address)
+    .fetch_one(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to create backfill job: {}", e)))?;
+
+  backfill_job_from_row(row)
+}
+
+// backfill 진행 위치 / 상태 저장
+pub async fn update_backfill_job(
+  pool: &PgPool,
+  id: i64,
+  next_block: u64,
+  status: BackfillStatus,
+  error: Option<&str>,
+) -> Result<(), AppError> {
+  let query = format!(
+    "UPDATE {} SET next_block = $2, status = $3, error = $4, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+    BACKFILL_JOBS_TABLE
+  );
+
+  sqlx::query(&query)
+    .bind(id)
+    .bind(next_block as i64)
+    .bind(status.as_str())
+    .bind(error)
+    .execute(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to update backfill job {}: {}", id, e)))?;
+
+  Ok(())
+}
+
+pub async fn get_backfill_job(pool: &PgPool, id: i64) -> Result<Option<BackfillJob>, AppError> {
+  let query = format!("SELECT {} FROM {} WHERE id = $1", BACKFILL_JOB_COLUMNS, BACKFILL_JOBS_TABLE);
+
+  let row: Option<BackfillJobRow> = sqlx::query_as(&query)
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to get backfill job {}: {}", id, e)))?;
+
+  row.map(backfill_job_from_row).transpose()
+}
+
+// backfill 작업 목록 (id 오름차순)
+pub async fn list_backfill_jobs(pool: &PgPool, unfinished_only: bool) -> Result<Vec<BackfillJob>, AppError> {
+  let query = format!(
+    "SELECT {} FROM {} WHERE NOT $1 OR status IN ('pending', 'running') ORDER BY id ASC",
+    BACKFILL_JOB_COLUMNS, BACKFILL_JOBS_TABLE
+  );
+
+  let rows: Vec<BackfillJobRow> = sqlx::query_as(&query)
+    .bind(unfinished_only)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::Database(format!("Failed to list backfill jobs: {}", e)))?;
+
+  rows.into_iter().map(backfill_job_from_row).collect()
+}
@@ -1,7 +1,7 @@
-use crate::types::AppError;
+use crate::notification::DepositEvent;
+use crate::types::{AppError, BackfillJob, BackfillStatus, BlockCommit, DepositFilter, DepositRecord, DepositStatus, DepositStatusChange, StatusTransition};
 use crate::respository::r#trait::Repository;
 use async_trait::async_trait;
-use rust_decimal::Decimal;
 use sqlx::PgPool;
 use std::sync::Arc;
 use log::info;
@@ -20,6 +20,10 @@ impl PostgreSQLRepository {
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
+
+    pub fn shared_pool(&self) -> Arc<PgPool> {
+        self.pool.clone()
+    }
 }
 
 #[async_trait]
@@ -29,83 +33,96 @@ impl Repository for PostgreSQLRepository {
     }
     
     async fn update_last_processed_block(&self, chain: &str, block_number: u64) -> Result<(), AppError> {
-        crate::respository::postgresql::update_last_processed_block(&self.pool, chain, block_number).await
+        crate::respository::postgresql::update_last_processed_block(self.pool.as_ref(), chain, block_number).await
     }
     
+    async fn list_last_processed_blocks(&self) -> Result<Vec<(String, u64)>, AppError> {
+        crate::respository::postgresql::list_last_processed_blocks(&self.pool).await
+    }
+
     async fn init_last_processed_block(&self, chain: &str, initial_block: u64) -> Result<(), AppError> {
         crate::respository::postgresql::init_last_processed_block(&self.pool, chain, initial_block).await
     }
     
-    async fn is_monitored_address(&self, _address: &str, _chain_name: &str) -> Result<bool, AppError> {
-        // Note: This function is no longer used in PostgreSQL repository
-        // All address lookups are done via RocksDB cache
-        // (populated from Backend via SQS + optional file cache)
-        Ok(false)
-    }
-
-    async fn save_deposit_event(
-        &self,
-        address: &str,
-        wallet_id: &str,
-        account_id: Option<&str>,
-        chain_name: &str,
-        tx_hash: &str,
-        block_number: u64,
-        amount: &str,
-        amount_decimal: Option<Decimal>,
-    ) -> Result<(), AppError> {
-        crate::respository::postgresql::save_deposit_event(
-            &self.pool,
-            address,
-            wallet_id,
-            account_id,
-            chain_name,
-            tx_hash,
-            block_number,
-            amount,
-            amount_decimal,
-        ).await
-    }
-
-    async fn get_address_metadata(&self, _address: &str, _chain_name: &str) -> Result<Option<(String, Option<String>)>, AppError> {
-        // PostgreSQL repository doesn't store address metadata
-        // All address metadata lookups are done via RocksDB cache
-        Ok(None)
+    async fn commit_block(&self, commit: &BlockCommit) -> Result<usize, AppError> {
+        crate::respository::postgresql::commit_block(&self.pool, commit).await
     }
 
     // Note: increment_customer_balance removed
     // Balance management is handled by blockbit-back-custody, not xScanner
 
-    async fn load_customer_addresses(&self, chain_name: &str) -> Result<usize, AppError> {
-        // PostgreSQL??? ???? LevelDB? ???? ??? ???,
-        // ???? ?? ??? ??
-        use sqlx::Row;
-        let query = format!(
-            "SELECT COUNT(*) FROM {} WHERE chain_name = $1",
-            crate::respository::postgresql::CUSTOMER_ADDRESSES_TABLE
-        );
-        
-        let row = sqlx::query(&query)
-            .bind(chain_name)
-            .fetch_one(self.pool.as_ref())
-            .await
-            .map_err(|e| AppError::Database(format!("Failed to count customer addresses: {}", e)))?;
-        
-        let count: i64 = row.try_get(0)
-            .map_err(|e| AppError::Database(format!("Failed to get count: {}", e)))?;
-        
-        Ok(count as usize)
+    async fn get_deposit_status(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Option<(DepositStatus, i64)>, AppError> {
+        crate::respository::postgresql::get_deposit_status(&self.pool, tx_hash, output_index, chain_name).await
     }
 
-    async fn deposit_exists(&self, tx_hash: &str, chain_name: &str) -> Result<bool, AppError> {
-        crate::respository::postgresql::deposit_exists(&self.pool, tx_hash, chain_name).await
+    async fn transition_deposit_status(
+        &self,
+        tx_hash: &str,
+        output_index: u32,
+        chain_name: &str,
+        transition: &StatusTransition,
+        notification: Option<&DepositEvent>,
+    ) -> Result<bool, AppError> {
+        crate::respository::postgresql::transition_deposit_status(&self.pool, tx_hash, output_index, chain_name, transition, notification).await
     }
 
-    async fn is_deposit_confirmed(&self, tx_hash: &str) -> Result<bool, AppError> {
-        crate::respository::postgresql::is_deposit_confirmed(&self.pool, tx_hash).await
+    async fn get_deposit_status_history(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Vec<DepositStatusChange>, AppError> {
+        crate::respository::postgresql::get_deposit_status_history(&self.pool, tx_hash, output_index, chain_name).await
+    }
+
+    async fn list_deposits(&self, filter: &DepositFilter) -> Result<Vec<DepositRecord>, AppError> {
+        crate::respository::postgresql::list_deposits(&self.pool, filter).await
     }
 
     async fn get_pending_deposits(&self) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
         crate::respository::postgresql::get_pending_deposits(&self.pool).await
     }
+
+    async fn get_recent_block_hashes(&self, chain: &str, limit: u64) -> Result<Vec<(u64, String)>, AppError> {
+        crate::respository::postgresql::get_recent_block_hashes(&self.pool, chain, limit).await
+    }
+
+    async fn rollback_to_block(&self, chain: &str, common_ancestor: u64, notify: bool) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
+        crate::respository::postgresql::rollback_to_block(&self.pool, chain, common_ancestor, notify).await
+    }
+
+    async fn get_pending_notifications(&self, sink: &str, limit: usize) -> Result<Vec<crate::tasks::OutboxEntry>, AppError> {
+        crate::respository::postgresql::get_pending_notifications(&self.pool, sink, limit).await
+    }
+
+    async fn mark_notification_sent(&self, id: i64, sink: &str) -> Result<(), AppError> {
+        crate::respository::postgresql::mark_notification_sent(&self.pool, id, sink).await
+    }
+
+    async fn mark_notification_failed(&self, id: i64, sink: &str, error: &str, retry_after_secs: u64) -> Result<(), AppError> {
+        crate::respository::postgresql::mark_notification_failed(&self.pool, id, sink, error, retry_after_secs).await
+    }
+
+    async fn mark_notification_dead(&self, id: i64, sink: &str, error: &str) -> Result<(), AppError> {
+        crate::respository::postgresql::mark_notification_dead(&self.pool, id, sink, error).await
+    }
+
+    async fn complete_notifications(&self, sinks: &[String]) -> Result<usize, AppError> {
+        crate::respository::postgresql::complete_notifications(&self.pool, sinks).await
+    }
+
+    async fn enqueue_notification(&self, event: &DepositEvent) -> Result<(), AppError> {
+        crate::respository::postgresql::enqueue_notification(&self.pool, event).await
+    }
+
+    async fn create_backfill_job(&self, chain: &str, from_block: u64, to_block: u64, address: Option<&str>) -> Result<BackfillJob, AppError> {
+        crate::respository::postgresql::create_backfill_job(&self.pool, chain, from_block, to_block, address).await
+    }
+
+    async fn update_backfill_job(&self, id: i64, next_block: u64, status: BackfillStatus, error: Option<&str>) -> Result<(), AppError> {
+        crate::respository::postgresql::update_backfill_job(&self.pool, id, next_block, status, error).await
+    }
+
+    async fn get_backfill_job(&self, id: i64) -> Result<Option<BackfillJob>, AppError> {
+        crate::respository::postgresql::get_backfill_job(&self.pool, id).await
+    }
+
+    async fn list_backfill_jobs(&self, unfinished_only: bool) -> Result<Vec<BackfillJob>, AppError> {
+        crate::respository::postgresql::list_backfill_jobs(&self.pool, unfinished_only).await
+    }
 }
@@ -1,17 +1,10 @@
 use crate::types::AppError;
+use crate::respository::address_index::{AddressMetadata, MonitoredAddress};
 use std::path::Path;
-use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "rocksdb-backend")]
 use rocksdb::{DB, Options, WriteBatch};
 
-/// Address metadata stored in RocksDB
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct AddressMetadata {
-    pub wallet_id: String,
-    pub account_id: Option<String>, // None for Omnibus (Master) Address
-}
-
 /// Open RocksDB database
 #[cfg(feature = "rocksdb-backend")]
 pub fn open_rocksdb(path_str: &str) -> Result<DB, AppError> {
@@ -87,6 +80,40 @@ pub fn get_address_metadata_from_rocksdb(
     }
 }
 
+/// 여러 주소의 메타데이터를 multi_get으로 한 번에 조회 (입력 순서대로 반환)
+#[cfg(feature = "rocksdb-backend")]
+pub fn get_address_metadata_batch_from_rocksdb(
+    db: &DB,
+    addresses: &[String],
+    chain_name: &str,
+) -> Result<Vec<Option<AddressMetadata>>, AppError> {
+    let keys: Vec<String> = addresses.iter()
+        .map(|address| format!("{}:{}", chain_name.to_lowercase(), address.to_lowercase()))
+        .collect();
+
+    db.multi_get(keys.iter().map(|k| k.as_bytes()))
+        .into_iter()
+        .map(|result| match result {
+            Ok(Some(value)) => serde_json::from_slice(&value)
+                .map(Some)
+                .map_err(|e| AppError::Database(format!("Failed to deserialize metadata: {}", e))),
+            Ok(None) => Ok(None),
+            Err(e) => Err(AppError::Database(format!("RocksDB multi_get failed: {}", e))),
+        })
+        .collect()
+}
+
+/// 저장된 모든 관리 대상 주소 키 (chain_name:address), Bloom filter 초기화용
+#[cfg(feature = "rocksdb-backend")]
+pub fn list_monitored_address_keys(db: &DB) -> Result<Vec<String>, AppError> {
+    let mut keys = Vec::new();
+    for item in db.iterator(rocksdb::IteratorMode::Start) {
+        let (key, _) = item.map_err(|e| AppError::Database(format!("RocksDB iteration failed: {}", e)))?;
+        keys.push(String::from_utf8_lossy(&key).into_owned());
+    }
+    Ok(keys)
+}
+
 /// 배치로 주소 추가 (SQS 메시지 처리용)
 #[cfg(feature = "rocksdb-backend")]
 pub fn batch_add_monitored_addresses(
@@ -118,6 +145,96 @@ pub fn batch_add_monitored_addresses(
     Ok(count)
 }
 
+/// 관리 대상 주소 삭제 (삭제된 주소가 있었는지 반환)
+#[cfg(feature = "rocksdb-backend")]
+pub fn remove_monitored_address_from_rocksdb(
+    db: &DB,
+    address: &str,
+    chain_name: &str,
+) -> Result<bool, AppError> {
+    let normalized_address = address.to_lowercase();
+    let key = format!("{}:{}", chain_name.to_lowercase(), normalized_address);
+    let existed = db.get(key.as_bytes())
+        .map_err(|e| AppError::Database(format!("RocksDB get failed: {}", e)))?
+        .is_some();
+    db.delete(key.as_bytes())
+        .map_err(|e| AppError::Database(format!("RocksDB delete failed: {}", e)))?;
+    Ok(existed)
+}
+
+/// wallet_id에 속한 모든 주소 삭제 (전체 순회 후 WriteBatch로 삭제)
+#[cfg(feature = "rocksdb-backend")]
+pub fn remove_wallet_addresses_from_rocksdb(
+    db: &DB,
+    wallet_id: &str,
+) -> Result<usize, AppError> {
+    let mut batch = WriteBatch::default();
+    let mut count = 0;
+
+    for item in db.iterator(rocksdb::IteratorMode::Start) {
+        let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iteration failed: {}", e)))?;
+        let Ok(metadata) = serde_json::from_slice::<AddressMetadata>(&value) else {
+            continue;
+        };
+        if metadata.wallet_id == wallet_id {
+            batch.delete(&key);
+            count += 1;
+        }
+    }
+
+    db.write(batch)
+        .map_err(|e| AppError::Database(format!("RocksDB batch delete failed: {}", e)))?;
+
+    Ok(count)
+}
+
+/// 체인별 관리 대상 주소 수 (chain_name: 접두사 순회)
+#[cfg(feature = "rocksdb-backend")]
+pub fn count_monitored_addresses_in_rocksdb(
+    db: &DB,
+    chain_name: &str,
+) -> Result<usize, AppError> {
+    let prefix = format!("{}:", chain_name.to_lowercase());
+    let mut count = 0;
+    for item in db.prefix_iterator(prefix.as_bytes()) {
+        let (key, _) = item.map_err(|e| AppError::Database(format!("RocksDB iteration failed: {}", e)))?;
+        if !key.starts_with(prefix.as_bytes()) {
+            break;
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// 관리 대상 주소 목록 (Key: chain_name:address, chain_name이 None이면 전체)
+#[cfg(feature = "rocksdb-backend")]
+pub fn list_monitored_addresses_in_rocksdb(
+    db: &DB,
+    chain_name: Option<&str>,
+) -> Result<Vec<MonitoredAddress>, AppError> {
+    let prefix = chain_name.map(|chain| format!("{}:", chain.to_lowercase())).unwrap_or_default();
+    let mut entries = Vec::new();
+    for item in db.prefix_iterator(prefix.as_bytes()) {
+        let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iteration failed: {}", e)))?;
+        if !key.starts_with(prefix.as_bytes()) {
+            break;
+        }
+
+        let key = String::from_utf8_lossy(&key);
+        let Some((chain_name, address)) = key.split_once(':') else {
+            continue;
+        };
+        let metadata: AddressMetadata = serde_json::from_slice(&value)
+            .map_err(|e| AppError::Database(format!("Failed to deserialize metadata for {}: {}", key, e)))?;
+        entries.push(MonitoredAddress {
+            address: address.to_string(),
+            chain_name: chain_name.to_string(),
+            metadata,
+        });
+    }
+    Ok(entries)
+}
+
 // Backward compatibility (deprecated)
 #[cfg(feature = "rocksdb-backend")]
 #[deprecated(note = "Use add_monitored_address_to_rocksdb instead")]
@@ -1,4 +1,8 @@
-use crate::types::AppError;
+use crate::notification::DepositEvent;
+use crate::types::{
+    AppError, AssetInfo, BackfillJob, BackfillStatus, BlockCommit, DepositFilter, DepositRecord, DepositStatus,
+    DepositStatusChange, DepositTransition, FailedDeposit, NewDeposit, StatusTransition,
+};
 use crate::respository::r#trait::Repository;
 use async_trait::async_trait;
 use rust_decimal::Decimal;
@@ -7,13 +11,20 @@ use std::str::FromStr;
 use log::info;
 
 #[cfg(feature = "rocksdb-backend")]
-use rocksdb::DB;
+use log::warn;
+#[cfg(feature = "rocksdb-backend")]
+use rocksdb::{DB, WriteBatch};
+#[cfg(feature = "rocksdb-backend")]
+use std::sync::Mutex;
 
 /// RocksDB 기반 Repository 구현
 #[derive(Clone)]
 pub struct RocksDBRepository {
     #[cfg(feature = "rocksdb-backend")]
     db: Arc<DB>,
+    // 입금 상태 읽기-비교-쓰기를 직렬화 (status_version 확인과 기록 사이에 다른 전이가 끼지 않도록)
+    #[cfg(feature = "rocksdb-backend")]
+    status_lock: Arc<Mutex<()>>,
     #[cfg(not(feature = "rocksdb-backend"))]
     _phantom: std::marker::PhantomData<()>,
 }
@@ -21,7 +32,58 @@ pub struct RocksDBRepository {
 #[cfg(feature = "rocksdb-backend")]
 impl RocksDBRepository {
     pub fn new(db: Arc<DB>) -> Self {
-        Self { db }
+        let repo = Self { db, status_lock: Arc::new(Mutex::new(())) };
+        if let Err(e) = repo.migrate_legacy_deposit_keys() {
+            warn!("Failed to migrate legacy deposit keys: {}", e);
+        }
+        repo
+    }
+
+    /// output index 도입 이전의 `deposit:{chain}:{tx_hash}` 키를 `deposit:{chain}:{tx_hash}:0`으로 옮기고,
+    /// 별도 키(`deposit_confirmed:{tx_hash}`)로 기록하던 확정 상태를 입금 이벤트의 `confirmed` 필드로 합친다
+    fn migrate_legacy_deposit_keys(&self) -> Result<(), AppError> {
+        use rocksdb::IteratorMode;
+
+        let prefix = b"deposit:";
+        let mut batch = WriteBatch::default();
+        let mut migrated = 0;
+
+        for item in self.db.iterator(IteratorMode::From(prefix, rocksdb::Direction::Forward)) {
+            let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iterator failed: {}", e)))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+
+            let key_str = String::from_utf8_lossy(&key[prefix.len()..]).to_string();
+            let parts: Vec<&str> = key_str.split(':').collect();
+            if parts.len() != 2 {
+                continue;
+            }
+            let (chain_name, tx_hash) = (parts[0], parts[1]);
+
+            let mut event: serde_json::Value = serde_json::from_slice(&value)
+                .map_err(|e| AppError::Database(format!("Failed to parse deposit event: {}", e)))?;
+            event["output_index"] = serde_json::Value::from(0);
+            let legacy_confirmed_key = format!("deposit_confirmed:{}", tx_hash);
+            if self.db.get(legacy_confirmed_key.as_bytes()).ok().flatten().is_some_and(|v| v.as_slice() == b"true") {
+                event["confirmed"] = serde_json::Value::Bool(true);
+            }
+            let updated = serde_json::to_vec(&event)
+                .map_err(|e| AppError::Database(format!("Failed to serialize deposit event: {}", e)))?;
+
+            batch.put(deposit_key(chain_name, tx_hash, 0).as_bytes(), updated);
+            batch.delete(&key);
+            batch.delete(legacy_confirmed_key.as_bytes());
+            migrated += 1;
+        }
+
+        if migrated > 0 {
+            self.db.write(batch)
+                .map_err(|e| AppError::Database(format!("RocksDB batch write failed: {}", e)))?;
+            info!("Migrated {} legacy deposit keys to (chain, tx_hash, output_index)", migrated);
+        }
+
+        Ok(())
     }
 
     pub fn db(&self) -> &DB {
@@ -83,6 +145,30 @@ impl Repository for RocksDBRepository {
         Err(AppError::Database("RocksDB feature not enabled".to_string()))
     }
 
+    #[cfg(feature = "rocksdb-backend")]
+    async fn list_last_processed_blocks(&self) -> Result<Vec<(String, u64)>, AppError> {
+        use rocksdb::IteratorMode;
+
+        let prefix = b"last_block:";
+        let mut blocks = Vec::new();
+        for item in self.db.iterator(IteratorMode::From(prefix, rocksdb::Direction::Forward)) {
+            let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iterator failed: {}", e)))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            let block_number = String::from_utf8_lossy(&value)
+                .parse::<u64>()
+                .map_err(|e| AppError::Database(format!("Failed to parse block number: {}", e)))?;
+            blocks.push((String::from_utf8_lossy(&key[prefix.len()..]).to_string(), block_number));
+        }
+        Ok(blocks)
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn list_last_processed_blocks(&self) -> Result<Vec<(String, u64)>, AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
     async fn init_last_processed_block(&self, chain: &str, initial_block: u64) -> Result<(), AppError> {
         let existing = self.get_last_processed_block(chain).await?;
 
@@ -98,127 +184,175 @@ impl Repository for RocksDBRepository {
     }
 
     #[cfg(feature = "rocksdb-backend")]
-    async fn is_monitored_address(&self, address: &str, chain_name: &str) -> Result<bool, AppError> {
-        use crate::respository::is_monitored_address_in_rocksdb;
-        is_monitored_address_in_rocksdb(&self.db, address, chain_name)
+    async fn commit_block(&self, commit: &BlockCommit) -> Result<usize, AppError> {
+        for deposit in &commit.transitions {
+            deposit.transition.validate()?;
+        }
+        let chain_name = commit.chain_name.as_str();
+        let _guard = self.lock_status()?;
+
+        // 블록의 입금, 상태 전이, 실패 기록, 알림, 진행 위치를 한 번의 WriteBatch로 기록
+        let mut batch = WriteBatch::default();
+        for deposit in &commit.deposits {
+            self.put_new_deposit(&mut batch, chain_name, deposit)?;
+        }
+        let mut applied = 0;
+        for deposit in &commit.transitions {
+            if self.put_transition(&mut batch, chain_name, deposit)? {
+                applied += 1;
+            }
+        }
+        for failed in &commit.failed_deposits {
+            put_failed_deposit(&mut batch, chain_name, failed)?;
+        }
+        if let Some(cursor) = &commit.cursor {
+            batch.put(format!("last_block:{}", chain_name).as_bytes(), commit.block_number.to_string().as_bytes());
+            if let Some(block_ref) = &cursor.block_ref {
+                batch.put(block_hash_key(chain_name, commit.block_number).as_bytes(), block_ref.hash.as_bytes());
+                for (block_number, _) in self.block_hashes(chain_name)? {
+                    if block_number >= cursor.prune_below {
+                        break;
+                    }
+                    batch.delete(block_hash_key(chain_name, block_number).as_bytes());
+                }
+            }
+        }
+
+        self.db.write(batch)
+            .map_err(|e| AppError::Database(format!("RocksDB write failed: {}", e)))?;
+        Ok(applied)
     }
 
     #[cfg(not(feature = "rocksdb-backend"))]
-    async fn is_monitored_address(&self, _address: &str, _chain_name: &str) -> Result<bool, AppError> {
+    async fn commit_block(&self, _commit: &BlockCommit) -> Result<usize, AppError> {
         Err(AppError::Database("RocksDB feature not enabled".to_string()))
     }
 
-    #[cfg(feature = "rocksdb-backend")]
-    async fn save_deposit_event(
-        &self,
-        address: &str,
-        wallet_id: &str,
-        account_id: Option<&str>,
-        chain_name: &str,
-        tx_hash: &str,
-        block_number: u64,
-        amount: &str,
-        amount_decimal: Option<Decimal>,
-    ) -> Result<(), AppError> {
-        let key = format!("deposit:{}:{}", chain_name, tx_hash);
-
-        // 중복 체크
-        if self.db.get(key.as_bytes()).is_ok_and(|v| v.is_some()) {
-            return Ok(()); // 이미 존재하면 무시
-        }
-
-        // DepositEvent를 JSON으로 직렬화하여 저장
-        let event = serde_json::json!({
-            "address": address,
-            "wallet_id": wallet_id,
-            "account_id": account_id,
-            "chain_name": chain_name,
-            "tx_hash": tx_hash,
-            "block_number": block_number,
-            "amount": amount,
-            "amount_decimal": amount_decimal.map(|d| d.to_string()),
-        });
-
-        let value = serde_json::to_string(&event)
-            .map_err(|e| AppError::Database(format!("Failed to serialize deposit event: {}", e)))?;
-
-        self.db.put(key.as_bytes(), value.as_bytes())
-            .map_err(|e| AppError::Database(format!("RocksDB put failed: {}", e)))?;
+    // Note: increment_customer_balance removed
+    // Balance management is handled by blockbit-back-custody, not xScanner
 
-        Ok(())
+    #[cfg(feature = "rocksdb-backend")]
+    async fn get_deposit_status(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Option<(DepositStatus, i64)>, AppError> {
+        Ok(self.deposit_event(chain_name, tx_hash, output_index)?
+            .map(|event| (event_status(&event), status_version(&event))))
     }
 
     #[cfg(not(feature = "rocksdb-backend"))]
-    async fn save_deposit_event(
-        &self,
-        _address: &str,
-        _wallet_id: &str,
-        _account_id: Option<&str>,
-        _chain_name: &str,
-        _tx_hash: &str,
-        _block_number: u64,
-        _amount: &str,
-        _amount_decimal: Option<Decimal>,
-    ) -> Result<(), AppError> {
+    async fn get_deposit_status(&self, _tx_hash: &str, _output_index: u32, _chain_name: &str) -> Result<Option<(DepositStatus, i64)>, AppError> {
         Err(AppError::Database("RocksDB feature not enabled".to_string()))
     }
 
     #[cfg(feature = "rocksdb-backend")]
-    async fn get_address_metadata(&self, address: &str, chain_name: &str) -> Result<Option<(String, Option<String>)>, AppError> {
-        match crate::respository::get_address_metadata_from_rocksdb(&self.db, address, chain_name) {
-            Ok(Some(metadata)) => Ok(Some((metadata.wallet_id, metadata.account_id))),
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
+    async fn transition_deposit_status(
+        &self,
+        tx_hash: &str,
+        output_index: u32,
+        chain_name: &str,
+        transition: &StatusTransition,
+        notification: Option<&DepositEvent>,
+    ) -> Result<bool, AppError> {
+        transition.validate()?;
+        let deposit = DepositTransition {
+            tx_hash: tx_hash.to_string(),
+            output_index,
+            transition: *transition,
+            notification: notification.cloned(),
+        };
+        let _guard = self.lock_status()?;
+
+        let mut batch = WriteBatch::default();
+        if !self.put_transition(&mut batch, chain_name, &deposit)? {
+            return Ok(false);
         }
+
+        self.db.write(batch)
+            .map_err(|e| AppError::Database(format!("RocksDB write failed: {}", e)))?;
+        Ok(true)
     }
 
     #[cfg(not(feature = "rocksdb-backend"))]
-    async fn get_address_metadata(&self, _address: &str, _chain_name: &str) -> Result<Option<(String, Option<String>)>, AppError> {
+    async fn transition_deposit_status(
+        &self,
+        _tx_hash: &str,
+        _output_index: u32,
+        _chain_name: &str,
+        _transition: &StatusTransition,
+        _notification: Option<&DepositEvent>,
+    ) -> Result<bool, AppError> {
         Err(AppError::Database("RocksDB feature not enabled".to_string()))
     }
 
-    // Note: increment_customer_balance removed
-    // Balance management is handled by blockbit-back-custody, not xScanner
-
-    async fn load_customer_addresses(&self, chain_name: &str) -> Result<usize, AppError> {
-        // RocksDB는 키-값 저장소이므로 특정 체인의 주소 개수를 세려면 반복이 필요
-        // 현재는 단순히 로그만 남기고 0 반환
-        // 실제로는 접두사 검색을 통해 주소 개수를 셀 수 있음
-        info!("RocksDBRepository: load_customer_addresses called for {}, but counting addresses requires iteration", chain_name);
-        Ok(0) // 주소 개수를 세려면 반복이 필요하므로 0 반환
-    }
-
     #[cfg(feature = "rocksdb-backend")]
-    async fn deposit_exists(&self, tx_hash: &str, chain_name: &str) -> Result<bool, AppError> {
-        let key = format!("deposit:{}:{}", chain_name, tx_hash);
-        match self.db.get(key.as_bytes()) {
-            Ok(Some(_)) => Ok(true),
-            Ok(None) => Ok(false),
-            Err(e) => Err(AppError::Database(format!("RocksDB get failed: {}", e))),
+    async fn get_deposit_status_history(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Vec<DepositStatusChange>, AppError> {
+        use rocksdb::IteratorMode;
+
+        let prefix = format!("{}:", status_history_prefix(chain_name, tx_hash, output_index));
+        let mut history = Vec::new();
+        for item in self.db.iterator(IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward)) {
+            let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iterator failed: {}", e)))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            history.push(serde_json::from_slice(&value)
+                .map_err(|e| AppError::Database(format!("Failed to parse deposit status history: {}", e)))?);
         }
+        Ok(history)
     }
 
     #[cfg(not(feature = "rocksdb-backend"))]
-    async fn deposit_exists(&self, _tx_hash: &str, _chain_name: &str) -> Result<bool, AppError> {
+    async fn get_deposit_status_history(&self, _tx_hash: &str, _output_index: u32, _chain_name: &str) -> Result<Vec<DepositStatusChange>, AppError> {
         Err(AppError::Database("RocksDB feature not enabled".to_string()))
     }
 
     #[cfg(feature = "rocksdb-backend")]
-    async fn is_deposit_confirmed(&self, tx_hash: &str) -> Result<bool, AppError> {
-        let key = format!("deposit_confirmed:{}", tx_hash);
-        match self.db.get(key.as_bytes()) {
-            Ok(Some(value)) => {
-                let confirmed_str = String::from_utf8(value.to_vec())
-                    .map_err(|e| AppError::Database(format!("Invalid UTF-8: {}", e)))?;
-                Ok(confirmed_str == "true")
+    async fn list_deposits(&self, filter: &DepositFilter) -> Result<Vec<DepositRecord>, AppError> {
+        use rocksdb::IteratorMode;
+
+        let prefix = b"deposit:";
+        let mut deposits = Vec::new();
+        for item in self.db.iterator(IteratorMode::From(prefix, rocksdb::Direction::Forward)) {
+            let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iterator failed: {}", e)))?;
+            if !key.starts_with(prefix) {
+                break;
             }
-            Ok(None) => Ok(false),
-            Err(e) => Err(AppError::Database(format!("RocksDB get failed: {}", e))),
+
+            let event: serde_json::Value = serde_json::from_slice(&value)
+                .map_err(|e| AppError::Database(format!("Failed to parse deposit event: {}", e)))?;
+            let deposit = pending_deposit_from_event(&event);
+            let matches = filter.wallet_id.as_ref().is_none_or(|w| deposit.wallet_id == *w)
+                && filter.account_id.as_ref().is_none_or(|a| deposit.account_id.as_ref() == Some(a))
+                && filter.address.as_ref().is_none_or(|a| deposit.address.eq_ignore_ascii_case(a))
+                && filter.chain_name.as_ref().is_none_or(|c| deposit.chain_name.eq_ignore_ascii_case(c))
+                && filter.tx_hash.as_ref().is_none_or(|t| deposit.tx_hash == *t)
+                && filter.status.is_none_or(|s| deposit.status == s);
+            if !matches {
+                continue;
+            }
+
+            deposits.push(DepositRecord {
+                confirmations: event.get("confirmations").and_then(|v| v.as_u64()).unwrap_or(1),
+                address: deposit.address,
+                wallet_id: deposit.wallet_id,
+                account_id: deposit.account_id,
+                chain: deposit.chain_name,
+                tx_hash: deposit.tx_hash,
+                output_index: deposit.output_index,
+                block_number: deposit.block_number,
+                block_hash: deposit.block_hash,
+                amount: deposit.amount,
+                amount_decimal: deposit.amount_decimal,
+                asset: deposit.asset,
+                status: deposit.status,
+                status_version: deposit.status_version,
+            });
         }
+
+        deposits.sort_by(|a, b| b.block_number.cmp(&a.block_number).then(a.output_index.cmp(&b.output_index)));
+        Ok(deposits.into_iter().skip(filter.offset).take(filter.limit).collect())
     }
 
     #[cfg(not(feature = "rocksdb-backend"))]
-    async fn is_deposit_confirmed(&self, _tx_hash: &str) -> Result<bool, AppError> {
+    async fn list_deposits(&self, _filter: &DepositFilter) -> Result<Vec<DepositRecord>, AppError> {
         Err(AppError::Database("RocksDB feature not enabled".to_string()))
     }
 
@@ -245,30 +379,9 @@ impl Repository for RocksDBRepository {
             let event: serde_json::Value = serde_json::from_str(&value_str)
                 .map_err(|e| AppError::Database(format!("Failed to parse deposit event: {}", e)))?;
 
-            // Check if confirmed field is false (or missing, which means unconfirmed)
-            let confirmed = event.get("confirmed").and_then(|v| v.as_bool()).unwrap_or(false);
-            if !confirmed {
-                let address = event.get("address").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                let wallet_id = event.get("wallet_id").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                let account_id = event.get("account_id").and_then(|v| v.as_str()).map(|s| s.to_string());
-                let chain_name = event.get("chain_name").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                let tx_hash = event.get("tx_hash").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                let block_number = event.get("block_number").and_then(|v| v.as_u64()).unwrap_or(0);
-                let amount = event.get("amount").and_then(|v| v.as_str()).unwrap_or("").to_string();
-                let amount_decimal = event.get("amount_decimal")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| Decimal::from_str(s).ok());
-
-                deposits.push(crate::tasks::PendingDeposit {
-                    address,
-                    wallet_id,
-                    account_id,
-                    chain_name,
-                    tx_hash,
-                    block_number,
-                    amount,
-                    amount_decimal,
-                });
+            // detected / confirming 상태만
+            if event_status(&event).is_pending() {
+                deposits.push(pending_deposit_from_event(&event));
             }
         }
 
@@ -279,14 +392,640 @@ impl Repository for RocksDBRepository {
     async fn get_pending_deposits(&self) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
         Err(AppError::Database("RocksDB feature not enabled".to_string()))
     }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn get_recent_block_hashes(&self, chain: &str, limit: u64) -> Result<Vec<(u64, String)>, AppError> {
+        let mut hashes = Vec::new();
+        for (block_number, hash) in self.block_hashes(chain)?.into_iter().rev() {
+            if hashes.len() as u64 >= limit {
+                break;
+            }
+            hashes.push((block_number, hash));
+        }
+        hashes.reverse();
+        Ok(hashes)
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn get_recent_block_hashes(&self, _chain: &str, _limit: u64) -> Result<Vec<(u64, String)>, AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn rollback_to_block(&self, chain: &str, common_ancestor: u64, notify: bool) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
+        use rocksdb::IteratorMode;
+
+        let last_processed_block = self.get_last_processed_block(chain).await?;
+        let _guard = self.lock_status()?;
+
+        // 모든 변경을 하나의 WriteBatch로 적용
+        let mut batch = WriteBatch::default();
+        let mut orphaned = Vec::new();
+
+        let prefix = format!("deposit:{}:", chain);
+        for item in self.db.iterator(IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward)) {
+            let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iterator failed: {}", e)))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+
+            let mut event: serde_json::Value = serde_json::from_slice(&value)
+                .map_err(|e| AppError::Database(format!("Failed to parse deposit event: {}", e)))?;
+            let block_number = event.get("block_number").and_then(|v| v.as_u64()).unwrap_or(0);
+            let status = event_status(&event);
+            if block_number <= common_ancestor || !status.can_transition_to(DepositStatus::Orphaned) {
+                continue;
+            }
+
+            let confirmations = event.get("confirmations").and_then(|v| v.as_u64()).unwrap_or(1);
+            event["status"] = serde_json::json!(DepositStatus::Orphaned);
+            event["status_version"] = serde_json::json!(status_version(&event) + 1);
+            let updated = serde_json::to_string(&event)
+                .map_err(|e| AppError::Database(format!("Failed to serialize deposit event: {}", e)))?;
+            batch.put(&key, updated.as_bytes());
+
+            let deposit = pending_deposit_from_event(&event);
+            put_status_history(&mut batch, chain, &deposit.tx_hash, deposit.output_index, Some(status), DepositStatus::Orphaned, confirmations)?;
+            if notify {
+                put_outbox_entry(&mut batch, &deposit.orphaned_event())?;
+            }
+            orphaned.push(deposit);
+        }
+
+        for (block_number, _) in self.block_hashes(chain)? {
+            if block_number > common_ancestor {
+                batch.delete(block_hash_key(chain, block_number).as_bytes());
+            }
+        }
+
+        if last_processed_block > common_ancestor {
+            batch.put(format!("last_block:{}", chain).as_bytes(), common_ancestor.to_string().as_bytes());
+        }
+
+        self.db.write(batch)
+            .map_err(|e| AppError::Database(format!("RocksDB batch write failed: {}", e)))?;
+
+        Ok(orphaned)
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn rollback_to_block(&self, _chain: &str, _common_ancestor: u64, _notify: bool) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn get_pending_notifications(&self, sink: &str, limit: usize) -> Result<Vec<crate::tasks::OutboxEntry>, AppError> {
+        let now = unix_now();
+        let mut blocked = std::collections::HashSet::new();
+        let mut entries = Vec::new();
+
+        for (id, record) in self.outbox_records()? {
+            if entries.len() >= limit {
+                break;
+            }
+
+            // 같은 입금의 이전 알림이 이 sink로 아직 전송되지 않았으면 이후 알림은 보내지 않음 (dead 알림은 제외)
+            let delivery = record.deliveries.get(sink);
+            if record.dead || delivery.is_some_and(SinkDelivery::is_finished) {
+                continue;
+            }
+            if !blocked.insert((record.chain_name.clone(), record.tx_hash.clone(), record.output_index))
+                || delivery.is_some_and(|d| d.next_attempt_at > now)
+            {
+                continue;
+            }
+
+            entries.push(crate::tasks::OutboxEntry {
+                id,
+                event_type: record.event_type,
+                payload: record.payload,
+                attempts: delivery.map_or(0, |d| d.attempts),
+            });
+        }
+
+        Ok(entries)
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn get_pending_notifications(&self, _sink: &str, _limit: usize) -> Result<Vec<crate::tasks::OutboxEntry>, AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn mark_notification_sent(&self, id: i64, sink: &str) -> Result<(), AppError> {
+        self.update_outbox_record(id, |record| {
+            record.deliveries.entry(sink.to_string()).or_default().sent = true;
+        })
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn mark_notification_sent(&self, _id: i64, _sink: &str) -> Result<(), AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn mark_notification_failed(&self, id: i64, sink: &str, _error: &str, retry_after_secs: u64) -> Result<(), AppError> {
+        self.update_outbox_record(id, |record| {
+            let delivery = record.deliveries.entry(sink.to_string()).or_default();
+            delivery.attempts += 1;
+            delivery.next_attempt_at = unix_now() + retry_after_secs;
+        })
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn mark_notification_failed(&self, _id: i64, _sink: &str, _error: &str, _retry_after_secs: u64) -> Result<(), AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn mark_notification_dead(&self, id: i64, sink: &str, _error: &str) -> Result<(), AppError> {
+        self.update_outbox_record(id, |record| {
+            let delivery = record.deliveries.entry(sink.to_string()).or_default();
+            delivery.attempts += 1;
+            delivery.dead = true;
+        })
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn mark_notification_dead(&self, _id: i64, _sink: &str, _error: &str) -> Result<(), AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn complete_notifications(&self, sinks: &[String]) -> Result<usize, AppError> {
+        // 모두 전송된 알림은 삭제, dead인 sink가 있으면 dead로 남김
+        let mut batch = WriteBatch::default();
+        let mut completed = 0;
+        for (id, mut record) in self.outbox_records()? {
+            if record.dead || !sinks.iter().all(|sink| record.deliveries.get(sink).is_some_and(SinkDelivery::is_finished)) {
+                continue;
+            }
+            completed += 1;
+            if sinks.iter().any(|sink| record.deliveries[sink].dead) {
+                record.dead = true;
+                let value = serde_json::to_vec(&record)
+                    .map_err(|e| AppError::Database(format!("Failed to serialize outbox entry: {}", e)))?;
+                batch.put(outbox_key(id).as_bytes(), value);
+            } else {
+                batch.delete(outbox_key(id).as_bytes());
+            }
+        }
+
+        self.db.write(batch)
+            .map_err(|e| AppError::Database(format!("RocksDB batch write failed: {}", e)))?;
+        Ok(completed)
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn complete_notifications(&self, _sinks: &[String]) -> Result<usize, AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn enqueue_notification(&self, event: &DepositEvent) -> Result<(), AppError> {
+        let mut batch = WriteBatch::default();
+        put_outbox_entry(&mut batch, event)?;
+        self.db.write(batch)
+            .map_err(|e| AppError::Database(format!("RocksDB write failed: {}", e)))
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn enqueue_notification(&self, _event: &DepositEvent) -> Result<(), AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn create_backfill_job(&self, chain: &str, from_block: u64, to_block: u64, address: Option<&str>) -> Result<BackfillJob, AppError> {
+        let _guard = self.status_lock.lock()
+            .map_err(|e| AppError::Database(format!("Backfill lock poisoned: {}", e)))?;
+
+        let id = self.backfill_jobs()?.last().map(|job| job.id + 1).unwrap_or(1);
+        let now = unix_now();
+        let job = BackfillJob {
+            id,
+            chain: chain.to_string(),
+            from_block,
+            to_block,
+            next_block: from_block,
+            address: address.map(str::to_string),
+            status: BackfillStatus::Pending,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        self.put_backfill_job(&job)?;
+        Ok(job)
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn create_backfill_job(&self, _chain: &str, _from_block: u64, _to_block: u64, _address: Option<&str>) -> Result<BackfillJob, AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn update_backfill_job(&self, id: i64, next_block: u64, status: BackfillStatus, error: Option<&str>) -> Result<(), AppError> {
+        let Some(mut job) = self.get_backfill_job(id).await? else {
+            return Ok(());
+        };
+        job.next_block = next_block;
+        job.status = status;
+        job.error = error.map(str::to_string);
+        job.updated_at = unix_now();
+        self.put_backfill_job(&job)
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn update_backfill_job(&self, _id: i64, _next_block: u64, _status: BackfillStatus, _error: Option<&str>) -> Result<(), AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn get_backfill_job(&self, id: i64) -> Result<Option<BackfillJob>, AppError> {
+        let value = self.db.get(backfill_key(id).as_bytes())
+            .map_err(|e| AppError::Database(format!("RocksDB get failed: {}", e)))?;
+        value
+            .map(|value| serde_json::from_slice(&value)
+                .map_err(|e| AppError::Database(format!("Failed to parse backfill job: {}", e))))
+            .transpose()
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn get_backfill_job(&self, _id: i64) -> Result<Option<BackfillJob>, AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
+
+    #[cfg(feature = "rocksdb-backend")]
+    async fn list_backfill_jobs(&self, unfinished_only: bool) -> Result<Vec<BackfillJob>, AppError> {
+        Ok(self.backfill_jobs()?
+            .into_iter()
+            .filter(|job| !unfinished_only || job.status.is_unfinished())
+            .collect())
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    async fn list_backfill_jobs(&self, _unfinished_only: bool) -> Result<Vec<BackfillJob>, AppError> {
+        Err(AppError::Database("RocksDB feature not enabled".to_string()))
+    }
 }
 
 #[cfg(feature = "rocksdb-backend")]
 impl RocksDBRepository {
-    pub async fn update_deposit_confirmed(&self, tx_hash: &str) -> Result<(), AppError> {
-        let key = format!("deposit_confirmed:{}", tx_hash);
-        self.db.put(key.as_bytes(), b"true")
-            .map_err(|e| AppError::Database(format!("RocksDB put failed: {}", e)))?;
-        Ok(())
+    /// 모든 backfill 작업 (id 오름차순)
+    fn backfill_jobs(&self) -> Result<Vec<BackfillJob>, AppError> {
+        let prefix = b"backfill:";
+        let mut jobs = Vec::new();
+        for item in self.db.prefix_iterator(prefix) {
+            let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iterator failed: {}", e)))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            jobs.push(serde_json::from_slice(&value)
+                .map_err(|e| AppError::Database(format!("Failed to parse backfill job: {}", e)))?);
+        }
+        Ok(jobs)
+    }
+
+    /// 모든 outbox 항목 (id 오름차순)
+    fn outbox_records(&self) -> Result<Vec<(i64, OutboxRecord)>, AppError> {
+        let prefix = b"outbox:";
+        let mut records = Vec::new();
+        for item in self.db.prefix_iterator(prefix) {
+            let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iterator failed: {}", e)))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            let id = String::from_utf8_lossy(&key[prefix.len()..])
+                .parse::<i64>()
+                .map_err(|e| AppError::Database(format!("Failed to parse outbox id: {}", e)))?;
+            let record = serde_json::from_slice(&value)
+                .map_err(|e| AppError::Database(format!("Failed to parse outbox entry: {}", e)))?;
+            records.push((id, record));
+        }
+        Ok(records)
+    }
+
+    /// outbox 항목 갱신 (이미 전송되어 삭제됐으면 무시)
+    fn update_outbox_record(&self, id: i64, update: impl FnOnce(&mut OutboxRecord)) -> Result<(), AppError> {
+        let key = outbox_key(id);
+        let Some(value) = self.db.get(key.as_bytes())
+            .map_err(|e| AppError::Database(format!("RocksDB get failed: {}", e)))? else {
+            return Ok(());
+        };
+
+        let mut record: OutboxRecord = serde_json::from_slice(&value)
+            .map_err(|e| AppError::Database(format!("Failed to parse outbox entry: {}", e)))?;
+        update(&mut record);
+
+        let value = serde_json::to_vec(&record)
+            .map_err(|e| AppError::Database(format!("Failed to serialize outbox entry: {}", e)))?;
+        self.db.put(key.as_bytes(), value)
+            .map_err(|e| AppError::Database(format!("RocksDB put failed: {}", e)))
+    }
+
+    fn put_backfill_job(&self, job: &BackfillJob) -> Result<(), AppError> {
+        let value = serde_json::to_vec(job)
+            .map_err(|e| AppError::Database(format!("Failed to serialize backfill job: {}", e)))?;
+        self.db.put(backfill_key(job.id).as_bytes(), value)
+            .map_err(|e| AppError::Database(format!("RocksDB write failed: {}", e)))
+    }
+}
+
+/// outbox 항목 (Key: outbox:{id}, 모든 sink로 전송 완료 시 삭제)
+#[cfg(feature = "rocksdb-backend")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct OutboxRecord {
+    event_type: String,
+    chain_name: String,
+    tx_hash: String,
+    #[serde(default)]
+    output_index: u32,
+    payload: String,
+    #[serde(default)]
+    deliveries: std::collections::HashMap<String, SinkDelivery>, // sink -> 전송 상태
+    #[serde(default)]
+    dead: bool, // 모든 sink 처리가 끝났고 dead인 sink가 있음 (더 이상 전송하지 않음)
+}
+
+/// sink 하나로의 전송 상태
+#[cfg(feature = "rocksdb-backend")]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct SinkDelivery {
+    attempts: u32,
+    next_attempt_at: u64, // unix seconds
+    sent: bool,
+    dead: bool, // 이 sink로는 재시도 한도 초과
+}
+
+#[cfg(feature = "rocksdb-backend")]
+impl SinkDelivery {
+    fn is_finished(&self) -> bool {
+        self.sent || self.dead
+    }
+}
+
+/// outbox 키 (id를 0으로 채워 사전순 = 기록 순서)
+#[cfg(feature = "rocksdb-backend")]
+fn outbox_key(id: i64) -> String {
+    format!("outbox:{:020}", id)
+}
+
+#[cfg(feature = "rocksdb-backend")]
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 알림을 outbox에 추가 (id는 기록 시각 기준 나노초, 한 batch에 여러 알림이 있어도 겹치지 않도록 단조 증가)
+#[cfg(feature = "rocksdb-backend")]
+fn put_outbox_entry(batch: &mut WriteBatch, event: &DepositEvent) -> Result<(), AppError> {
+    use std::sync::atomic::{AtomicI64, Ordering};
+    static LAST_OUTBOX_ID: AtomicI64 = AtomicI64::new(0);
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0);
+    let id = LAST_OUTBOX_ID
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| Some(now.max(last + 1)))
+        .map(|last| now.max(last + 1))
+        .unwrap_or(now);
+    let record = OutboxRecord {
+        event_type: event.event_type().to_string(),
+        chain_name: event.chain().to_string(),
+        tx_hash: event.tx_hash().to_string(),
+        output_index: event.output_index(),
+        payload: serde_json::to_string(event)
+            .map_err(|e| AppError::Database(format!("Failed to serialize notification: {}", e)))?,
+        deliveries: std::collections::HashMap::new(),
+        dead: false,
+    };
+    let value = serde_json::to_vec(&record)
+        .map_err(|e| AppError::Database(format!("Failed to serialize outbox entry: {}", e)))?;
+
+    batch.put(outbox_key(id).as_bytes(), value);
+    Ok(())
+}
+
+/// 실패한 트랜잭션 감사 기록 (Key: failed_deposit:{chain}:{tx_hash}:{output_index})
+#[cfg(feature = "rocksdb-backend")]
+fn put_failed_deposit(batch: &mut WriteBatch, chain_name: &str, failed: &FailedDeposit) -> Result<(), AppError> {
+    let key = format!("failed_deposit:{}:{}:{}", chain_name, failed.tx_hash, failed.output_index);
+
+    let event = serde_json::json!({
+        "address": failed.address,
+        "chain_name": chain_name,
+        "tx_hash": failed.tx_hash,
+        "output_index": failed.output_index,
+        "block_number": failed.block_number,
+        "amount": failed.amount,
+        "asset": failed.asset.symbol,
+        "token_address": failed.asset.token_address,
+        "reason": failed.reason,
+    });
+
+    let value = serde_json::to_string(&event)
+        .map_err(|e| AppError::Database(format!("Failed to serialize failed deposit: {}", e)))?;
+
+    batch.put(key.as_bytes(), value.as_bytes());
+    Ok(())
+}
+
+/// backfill 작업 키 (id를 0으로 채워 사전순 = 등록 순서)
+#[cfg(feature = "rocksdb-backend")]
+fn backfill_key(id: i64) -> String {
+    format!("backfill:{:020}", id)
+}
+
+/// 입금 이벤트 키 (chain, tx_hash, output_index)
+#[cfg(feature = "rocksdb-backend")]
+fn deposit_key(chain_name: &str, tx_hash: &str, output_index: u32) -> String {
+    format!("deposit:{}:{}:{}", chain_name, tx_hash, output_index)
+}
+
+/// 블록 해시 키 (block_number를 0으로 채워 사전순 = 번호순)
+#[cfg(feature = "rocksdb-backend")]
+fn block_hash_key(chain: &str, block_number: u64) -> String {
+    format!("block_hash:{}:{:020}", chain, block_number)
+}
+
+/// 상태 이력 키 prefix (뒤에 `:{기록 시각 나노초}`를 붙여 사전순 = 기록 순서)
+#[cfg(feature = "rocksdb-backend")]
+fn status_history_prefix(chain_name: &str, tx_hash: &str, output_index: u32) -> String {
+    format!("deposit_status:{}:{}:{}", chain_name, tx_hash, output_index)
+}
+
+#[cfg(feature = "rocksdb-backend")]
+fn put_status_history(
+    batch: &mut WriteBatch,
+    chain_name: &str,
+    tx_hash: &str,
+    output_index: u32,
+    from: Option<DepositStatus>,
+    to: DepositStatus,
+    confirmations: u64,
+) -> Result<(), AppError> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    let change = DepositStatusChange {
+        from_status: from,
+        to_status: to,
+        confirmations,
+        changed_at: now.as_secs(),
+    };
+    let value = serde_json::to_vec(&change)
+        .map_err(|e| AppError::Database(format!("Failed to serialize deposit status history: {}", e)))?;
+
+    let key = format!("{}:{:020}", status_history_prefix(chain_name, tx_hash, output_index), now.as_nanos());
+    batch.put(key.as_bytes(), value);
+    Ok(())
+}
+
+/// 입금 상태 (status 필드 도입 이전의 이벤트는 confirmed/orphaned 필드로 판단)
+#[cfg(feature = "rocksdb-backend")]
+fn event_status(event: &serde_json::Value) -> DepositStatus {
+    if let Some(status) = event.get("status").and_then(|v| v.as_str()) {
+        return DepositStatus::from_str(status).unwrap_or(DepositStatus::FlaggedForReview);
+    }
+    if event.get("orphaned").and_then(|v| v.as_bool()).unwrap_or(false) {
+        DepositStatus::Orphaned
+    } else if event.get("confirmed").and_then(|v| v.as_bool()).unwrap_or(false) {
+        DepositStatus::Confirmed
+    } else {
+        DepositStatus::Detected
+    }
+}
+
+#[cfg(feature = "rocksdb-backend")]
+fn status_version(event: &serde_json::Value) -> i64 {
+    event.get("status_version").and_then(|v| v.as_i64()).unwrap_or(0)
+}
+
+#[cfg(feature = "rocksdb-backend")]
+fn pending_deposit_from_event(event: &serde_json::Value) -> crate::tasks::PendingDeposit {
+    let chain_name = event.get("chain_name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    // 자산 필드 추가 이전의 이벤트는 네이티브 코인 입금
+    let asset = serde_json::from_value::<AssetInfo>(event.clone())
+        .unwrap_or_else(|_| AssetInfo { symbol: chain_name.clone(), token_address: None, decimals: None });
+
+    crate::tasks::PendingDeposit {
+        address: event.get("address").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        wallet_id: event.get("wallet_id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        account_id: event.get("account_id").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        chain_name,
+        tx_hash: event.get("tx_hash").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        output_index: event.get("output_index").and_then(|v| v.as_u64()).unwrap_or(0) as u32,
+        block_number: event.get("block_number").and_then(|v| v.as_u64()).unwrap_or(0),
+        amount: event.get("amount").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+        amount_decimal: event.get("amount_decimal")
+            .and_then(|v| v.as_str())
+            .and_then(|s| Decimal::from_str(s).ok()),
+        asset,
+        block_hash: event.get("block_hash").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        status: event_status(event),
+        status_version: status_version(event),
+    }
+}
+
+#[cfg(feature = "rocksdb-backend")]
+impl RocksDBRepository {
+    /// 체인의 저장된 블록 해시 전체 (block_number 오름차순)
+    fn block_hashes(&self, chain: &str) -> Result<Vec<(u64, String)>, AppError> {
+        use rocksdb::IteratorMode;
+
+        let prefix = format!("block_hash:{}:", chain);
+        let mut hashes = Vec::new();
+        for item in self.db.iterator(IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward)) {
+            let (key, value) = item.map_err(|e| AppError::Database(format!("RocksDB iterator failed: {}", e)))?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let block_number = String::from_utf8_lossy(&key[prefix.len()..])
+                .parse::<u64>()
+                .map_err(|e| AppError::Database(format!("Failed to parse block number: {}", e)))?;
+            hashes.push((block_number, String::from_utf8_lossy(&value).to_string()));
+        }
+        Ok(hashes)
+    }
+
+    /// 저장된 입금 이벤트 (JSON)
+    fn deposit_event(&self, chain_name: &str, tx_hash: &str, output_index: u32) -> Result<Option<serde_json::Value>, AppError> {
+        match self.db.get(deposit_key(chain_name, tx_hash, output_index).as_bytes()) {
+            Ok(Some(value)) => serde_json::from_slice(&value)
+                .map(Some)
+                .map_err(|e| AppError::Database(format!("Failed to parse deposit event: {}", e))),
+            Ok(None) => Ok(None),
+            Err(e) => Err(AppError::Database(format!("RocksDB get failed: {}", e))),
+        }
+    }
+
+    fn lock_status(&self) -> Result<std::sync::MutexGuard<'_, ()>, AppError> {
+        self.status_lock.lock()
+            .map_err(|_| AppError::Database("Deposit status lock poisoned".to_string()))
+    }
+
+    /// 새 입금을 detected로 batch에 추가 (+ 상태 이력, 알림) - status_lock을 잡은 상태에서 호출
+    fn put_new_deposit(&self, batch: &mut WriteBatch, chain_name: &str, deposit: &NewDeposit) -> Result<bool, AppError> {
+        // 중복 체크 (orphaned 입금은 canonical 체인에 다시 포함된 경우 덮어씀)
+        let previous = self.deposit_event(chain_name, &deposit.tx_hash, deposit.output_index)?
+            .map(|existing| (event_status(&existing), status_version(&existing)));
+        if previous.is_some_and(|(status, _)| status != DepositStatus::Orphaned) {
+            return Ok(false); // 이미 존재하면 무시
+        }
+
+        // DepositEvent를 JSON으로 직렬화하여 저장
+        let event = serde_json::json!({
+            "address": deposit.address,
+            "wallet_id": deposit.wallet_id,
+            "account_id": deposit.account_id,
+            "chain_name": chain_name,
+            "tx_hash": deposit.tx_hash,
+            "output_index": deposit.output_index,
+            "block_number": deposit.block_number,
+            "amount": deposit.amount,
+            "amount_decimal": deposit.amount_decimal.map(|d| d.to_string()),
+            "asset": deposit.asset.symbol,
+            "token_address": deposit.asset.token_address,
+            "decimals": deposit.asset.decimals,
+            "block_hash": deposit.block_hash,
+            "status": DepositStatus::Detected,
+            "status_version": previous.map(|(_, version)| version + 1).unwrap_or(0),
+            "confirmations": 1,
+        });
+
+        let value = serde_json::to_string(&event)
+            .map_err(|e| AppError::Database(format!("Failed to serialize deposit event: {}", e)))?;
+
+        batch.put(deposit_key(chain_name, &deposit.tx_hash, deposit.output_index).as_bytes(), value.as_bytes());
+        put_status_history(batch, chain_name, &deposit.tx_hash, deposit.output_index, previous.map(|(status, _)| status), DepositStatus::Detected, 1)?;
+        if let Some(notification) = &deposit.notification {
+            put_outbox_entry(batch, notification)?;
+        }
+        Ok(true)
+    }
+
+    /// 상태 전이를 batch에 추가 (+ 상태 이력, 알림), 현재 상태/버전이 다르면 false - status_lock을 잡은 상태에서 호출
+    fn put_transition(&self, batch: &mut WriteBatch, chain_name: &str, deposit: &DepositTransition) -> Result<bool, AppError> {
+        let transition = &deposit.transition;
+        let Some(mut event) = self.deposit_event(chain_name, &deposit.tx_hash, deposit.output_index)?
+            .filter(|e| event_status(e) == transition.from && status_version(e) == transition.expected_version)
+        else {
+            return Ok(false);
+        };
+
+        event["status"] = serde_json::json!(transition.to);
+        event["status_version"] = serde_json::json!(transition.expected_version + 1);
+        event["confirmations"] = serde_json::json!(transition.confirmations);
+        let value = serde_json::to_vec(&event)
+            .map_err(|e| AppError::Database(format!("Failed to serialize deposit event: {}", e)))?;
+
+        batch.put(deposit_key(chain_name, &deposit.tx_hash, deposit.output_index).as_bytes(), value);
+        put_status_history(batch, chain_name, &deposit.tx_hash, deposit.output_index, Some(transition.from), transition.to, transition.confirmations)?;
+        if let Some(notification) = &deposit.notification {
+            put_outbox_entry(batch, notification)?;
+        }
+        Ok(true)
     }
 }
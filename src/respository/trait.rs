@@ -1,6 +1,6 @@
-use crate::types::AppError;
+use crate::notification::DepositEvent;
+use crate::types::{AppError, BackfillJob, BackfillStatus, BlockCommit, DepositFilter, DepositRecord, DepositStatus, DepositStatusChange, StatusTransition};
 use async_trait::async_trait;
-use rust_decimal::Decimal;
 
 /// Repository trait - ?? ??? ??? ???? ?? ?????
 #[async_trait]
@@ -11,40 +11,89 @@ pub trait Repository: Send + Sync {
     /// ????? ??? ?? ?? ????
     async fn update_last_processed_block(&self, chain: &str, block_number: u64) -> Result<(), AppError>;
     
+    /// 체인별 마지막 처리 블록 (chain_name 오름차순)
+    async fn list_last_processed_blocks(&self) -> Result<Vec<(String, u64)>, AppError>;
+
     /// ????? ??? ?? ?? ???
     async fn init_last_processed_block(&self, chain: &str, initial_block: u64) -> Result<(), AppError>;
-    
-    /// 주소가 관리 대상인지 확인 (RocksDB 캐시 조회용)
-    async fn is_monitored_address(&self, address: &str, chain_name: &str) -> Result<bool, AppError>;
-
-    /// 입금 이벤트 저장 (wallet_id, account_id 추가)
-    async fn save_deposit_event(
-        &self,
-        address: &str,
-        wallet_id: &str,
-        account_id: Option<&str>,
-        chain_name: &str,
-        tx_hash: &str,
-        block_number: u64,
-        amount: &str,
-        amount_decimal: Option<Decimal>,
-    ) -> Result<(), AppError>;
 
-    /// 주소의 메타데이터 조회 (wallet_id, account_id)
-    async fn get_address_metadata(&self, address: &str, chain_name: &str) -> Result<Option<(String, Option<String>)>, AppError>;
+    /// 블록 하나의 분석 결과를 하나의 트랜잭션으로 적용
+    ///
+    /// 새 입금(상태 이력, 알림 포함), 상태 전이, 실패한 입금 시도, 그리고 live 블록이면 last_processed_block과
+    /// 블록 해시까지 모두 기록하거나 아무것도 기록하지 않는다. 실패하면 같은 블록을 다시 처리하면 된다.
+    /// 다른 작업이 먼저 상태를 바꾼 전이는 건너뛰고, 적용된 상태 전이 수를 반환한다.
+    async fn commit_block(&self, commit: &BlockCommit) -> Result<usize, AppError>;
 
     // Note: increment_customer_balance removed
     // Balance management is handled by blockbit-back-custody, not xScanner
 
-    /// ?? ???? ?? (????)
-    async fn load_customer_addresses(&self, chain_name: &str) -> Result<usize, AppError>;
+    /// 입금 상태와 status_version 조회 (저장되지 않은 입금이면 None)
+    ///
+    /// 입금 식별자는 (chain_name, tx_hash, output_index) - 한 트랜잭션에 입금이 여러 개일 수 있다.
+    async fn get_deposit_status(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Option<(DepositStatus, i64)>, AppError>;
 
-    /// Check if a deposit already exists in the database
-    async fn deposit_exists(&self, tx_hash: &str, chain_name: &str) -> Result<bool, AppError>;
+    /// 입금 상태 전이 (현재 상태가 transition.from이고 status_version이 expected_version일 때만 적용)
+    ///
+    /// 적용되면 deposit_status_history와 notification(outbox)을 같은 트랜잭션에 기록하고 true를 반환한다.
+    /// 그 사이 다른 작업이 상태를 바꿨으면 아무것도 기록하지 않고 false를 반환한다.
+    async fn transition_deposit_status(
+        &self,
+        tx_hash: &str,
+        output_index: u32,
+        chain_name: &str,
+        transition: &StatusTransition,
+        notification: Option<&DepositEvent>,
+    ) -> Result<bool, AppError>;
 
-    /// Check if a deposit is already confirmed
-    async fn is_deposit_confirmed(&self, tx_hash: &str) -> Result<bool, AppError>;
+    /// 입금 상태 전이 이력 (오래된 순)
+    async fn get_deposit_status_history(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Vec<DepositStatusChange>, AppError>;
 
-    /// Get all pending (unconfirmed) deposits for confirmation checking
+    /// 입금 조회 (block_number 내림차순, filter.limit / filter.offset 적용)
+    ///
+    /// address, chain 조건은 대소문자를 구분하지 않는다.
+    async fn list_deposits(&self, filter: &DepositFilter) -> Result<Vec<DepositRecord>, AppError>;
+
+    /// Get all pending (detected / confirming) deposits for confirmation checking
     async fn get_pending_deposits(&self) -> Result<Vec<crate::tasks::PendingDeposit>, AppError>;
+
+    /// 최근 블록 해시 조회 (block_number 오름차순, 최대 limit개)
+    async fn get_recent_block_hashes(&self, chain: &str, limit: u64) -> Result<Vec<(u64, String)>, AppError>;
+
+    /// Reorg rollback - common_ancestor 이후 입금을 orphaned로 전이하고, 블록 해시를 삭제하고,
+    /// last_processed_block을 common_ancestor로 되돌린다. orphaned 처리된 입금 목록을 반환
+    ///
+    /// notify가 true면 DepositOrphaned 알림을 같은 트랜잭션에서 outbox에 기록한다.
+    async fn rollback_to_block(&self, chain: &str, common_ancestor: u64, notify: bool) -> Result<Vec<crate::tasks::PendingDeposit>, AppError>;
+
+    /// sink로 전송 대기 중인 알림 (id 오름차순, attempts는 이 sink로 시도한 횟수)
+    ///
+    /// 전송 상태는 sink별로 관리한다. 이 sink로 전송했거나 dead인 알림은 제외하고, 같은 입금(output)의 이전 알림이
+    /// 이 sink로 아직 전송되지 않았으면 이후 알림도 제외한다 (다른 sink의 실패는 영향 없음).
+    async fn get_pending_notifications(&self, sink: &str, limit: usize) -> Result<Vec<crate::tasks::OutboxEntry>, AppError>;
+
+    /// sink로 알림 전송 완료 표시
+    async fn mark_notification_sent(&self, id: i64, sink: &str) -> Result<(), AppError>;
+
+    /// sink로 알림 전송 실패 기록, retry_after_secs 후 이 sink의 전송 대상이 됨
+    async fn mark_notification_failed(&self, id: i64, sink: &str, error: &str, retry_after_secs: u64) -> Result<(), AppError>;
+
+    /// 재시도 한도를 넘은 알림을 이 sink에 대해 dead로 표시 (이 sink로 더 이상 전송하지 않고, 같은 입금의 이후 알림도 막지 않음)
+    async fn mark_notification_dead(&self, id: i64, sink: &str, error: &str) -> Result<(), AppError>;
+
+    /// 모든 `sinks`로 전송(또는 dead 처리)된 알림을 outbox에서 완료 처리하고 그 수를 반환
+    async fn complete_notifications(&self, sinks: &[String]) -> Result<usize, AppError>;
+
+    /// 알림을 outbox에 새로 기록 (운영자 재전송용 - consumer는 같은 event_id로 중복 제거)
+    async fn enqueue_notification(&self, event: &DepositEvent) -> Result<(), AppError>;
+
+    /// backfill 작업 등록 (pending, next_block = from_block)
+    async fn create_backfill_job(&self, chain: &str, from_block: u64, to_block: u64, address: Option<&str>) -> Result<BackfillJob, AppError>;
+
+    /// backfill 진행 위치 / 상태 저장
+    async fn update_backfill_job(&self, id: i64, next_block: u64, status: BackfillStatus, error: Option<&str>) -> Result<(), AppError>;
+
+    async fn get_backfill_job(&self, id: i64) -> Result<Option<BackfillJob>, AppError>;
+
+    /// backfill 작업 목록 (id 오름차순, unfinished_only면 pending / running만)
+    async fn list_backfill_jobs(&self, unfinished_only: bool) -> Result<Vec<BackfillJob>, AppError>;
 }
@@ -1,9 +1,10 @@
 use crate::respository::{
-    connect_db, setup_db_schema,
+    connect_db, migrations,
     Repository, MemoryRepository, PostgreSQLRepository,
 };
 use crate::config::Settings;
-use crate::types::AppError;
+use crate::notification::DepositEvent;
+use crate::types::{AppError, BackfillJob, BackfillStatus, BlockCommit, DepositFilter, DepositRecord, DepositStatus, DepositStatusChange, StatusTransition};
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -11,27 +12,67 @@ use std::sync::Arc;
 pub enum RepositoryWrapper {
     Memory(Arc<MemoryRepository>),
     PostgreSQL(Arc<PostgreSQLRepository>),
+    #[cfg(feature = "rocksdb-backend")]
+    RocksDB(Arc<crate::respository::RocksDBRepository>),
 }
 
 impl RepositoryWrapper {
     pub async fn from_settings(settings: &Settings) -> Result<Self, AppError> {
         if settings.repository.memory_db {
             Ok(RepositoryWrapper::Memory(Arc::new(MemoryRepository::new())))
+        } else if let Some(path) = &settings.repository.rocksdb_path {
+            Self::open_rocksdb(path)
         } else {
             let db_connection_pool = connect_db(&settings.repository.postgresql_url).await
                 .map_err(|e| AppError::Database(format!("Failed to connect to PostgreSQL: {}", e)))?;
-            setup_db_schema(&db_connection_pool).await
-                .map_err(|e| AppError::Database(format!("Failed to setup database schema: {}", e)))?;
+            if settings.repository.auto_migrate {
+                migrations::run_migrations(&db_connection_pool, false).await?;
+            } else {
+                let pending = migrations::pending_migrations(&db_connection_pool).await?;
+                if !pending.is_empty() {
+                    return Err(AppError::Database(format!(
+                        "{} pending schema migration(s), run `xscanner migrate` first (auto_migrate = false)",
+                        pending.len()
+                    )));
+                }
+            }
             Ok(RepositoryWrapper::PostgreSQL(Arc::new(PostgreSQLRepository::new(Arc::new(db_connection_pool)))))
         }
     }
     
+    #[cfg(feature = "rocksdb-backend")]
+    fn open_rocksdb(path: &str) -> Result<Self, AppError> {
+        let db = crate::respository::open_rocksdb(path)?;
+        Ok(RepositoryWrapper::RocksDB(Arc::new(crate::respository::RocksDBRepository::new(Arc::new(db)))))
+    }
+
+    #[cfg(not(feature = "rocksdb-backend"))]
+    fn open_rocksdb(_path: &str) -> Result<Self, AppError> {
+        Err(AppError::Config("repository.rocksdb_path requires the rocksdb-backend feature".to_string()))
+    }
+
     pub fn get_postgresql_repo(&self) -> Option<Arc<PostgreSQLRepository>> {
         match self {
             RepositoryWrapper::PostgreSQL(r) => Some(r.clone()),
             _ => None,
         }
     }
+
+    /// 저장소 연결 확인 (readiness probe)
+    pub async fn health_check(&self) -> Result<(), AppError> {
+        match self {
+            RepositoryWrapper::Memory(_) => Ok(()),
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(_) => Ok(()),
+            RepositoryWrapper::PostgreSQL(r) => {
+                sqlx::query("SELECT 1")
+                    .execute(r.pool())
+                    .await
+                    .map_err(|e| AppError::Database(format!("PostgreSQL is unreachable: {}", e)))?;
+                Ok(())
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -40,6 +81,8 @@ impl Repository for RepositoryWrapper {
         match self {
             RepositoryWrapper::Memory(r) => r.get_last_processed_block(chain).await,
             RepositoryWrapper::PostgreSQL(r) => r.get_last_processed_block(chain).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.get_last_processed_block(chain).await,
         }
     }
     
@@ -47,100 +90,208 @@ impl Repository for RepositoryWrapper {
         match self {
             RepositoryWrapper::Memory(r) => r.update_last_processed_block(chain, block_number).await,
             RepositoryWrapper::PostgreSQL(r) => r.update_last_processed_block(chain, block_number).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.update_last_processed_block(chain, block_number).await,
         }
     }
     
+    async fn list_last_processed_blocks(&self) -> Result<Vec<(String, u64)>, AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.list_last_processed_blocks().await,
+            RepositoryWrapper::PostgreSQL(r) => r.list_last_processed_blocks().await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.list_last_processed_blocks().await,
+        }
+    }
+
     async fn init_last_processed_block(&self, chain: &str, initial_block: u64) -> Result<(), AppError> {
         match self {
             RepositoryWrapper::Memory(r) => r.init_last_processed_block(chain, initial_block).await,
             RepositoryWrapper::PostgreSQL(r) => r.init_last_processed_block(chain, initial_block).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.init_last_processed_block(chain, initial_block).await,
         }
     }
     
-    async fn is_monitored_address(&self, address: &str, chain_name: &str) -> Result<bool, AppError> {
+    async fn commit_block(&self, commit: &BlockCommit) -> Result<usize, AppError> {
         match self {
-            RepositoryWrapper::Memory(r) => r.is_monitored_address(address, chain_name).await,
-            RepositoryWrapper::PostgreSQL(r) => r.is_monitored_address(address, chain_name).await,
+            RepositoryWrapper::Memory(r) => r.commit_block(commit).await,
+            RepositoryWrapper::PostgreSQL(r) => r.commit_block(commit).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.commit_block(commit).await,
         }
     }
 
-    async fn save_deposit_event(
+    // Note: increment_customer_balance removed
+    // Balance management is handled by blockbit-back-custody, not xScanner
+
+    async fn get_deposit_status(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Option<(DepositStatus, i64)>, AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.get_deposit_status(tx_hash, output_index, chain_name).await,
+            RepositoryWrapper::PostgreSQL(r) => r.get_deposit_status(tx_hash, output_index, chain_name).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.get_deposit_status(tx_hash, output_index, chain_name).await,
+        }
+    }
+
+    async fn transition_deposit_status(
         &self,
-        address: &str,
-        wallet_id: &str,
-        account_id: Option<&str>,
-        chain_name: &str,
         tx_hash: &str,
-        block_number: u64,
-        amount: &str,
-        amount_decimal: Option<rust_decimal::Decimal>,
-    ) -> Result<(), AppError> {
+        output_index: u32,
+        chain_name: &str,
+        transition: &StatusTransition,
+        notification: Option<&DepositEvent>,
+    ) -> Result<bool, AppError> {
         match self {
-            RepositoryWrapper::Memory(r) => r.save_deposit_event(address, wallet_id, account_id, chain_name, tx_hash, block_number, amount, amount_decimal).await,
-            RepositoryWrapper::PostgreSQL(r) => r.save_deposit_event(address, wallet_id, account_id, chain_name, tx_hash, block_number, amount, amount_decimal).await,
+            RepositoryWrapper::Memory(r) => r.transition_deposit_status(tx_hash, output_index, chain_name, transition, notification).await,
+            RepositoryWrapper::PostgreSQL(r) => r.transition_deposit_status(tx_hash, output_index, chain_name, transition, notification).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.transition_deposit_status(tx_hash, output_index, chain_name, transition, notification).await,
         }
     }
 
-    async fn get_address_metadata(&self, address: &str, chain_name: &str) -> Result<Option<(String, Option<String>)>, AppError> {
+    async fn get_deposit_status_history(&self, tx_hash: &str, output_index: u32, chain_name: &str) -> Result<Vec<DepositStatusChange>, AppError> {
         match self {
-            RepositoryWrapper::Memory(r) => r.get_address_metadata(address, chain_name).await,
-            RepositoryWrapper::PostgreSQL(r) => r.get_address_metadata(address, chain_name).await,
+            RepositoryWrapper::Memory(r) => r.get_deposit_status_history(tx_hash, output_index, chain_name).await,
+            RepositoryWrapper::PostgreSQL(r) => r.get_deposit_status_history(tx_hash, output_index, chain_name).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.get_deposit_status_history(tx_hash, output_index, chain_name).await,
         }
     }
 
-    // Note: increment_customer_balance removed
-    // Balance management is handled by blockbit-back-custody, not xScanner
+    async fn list_deposits(&self, filter: &DepositFilter) -> Result<Vec<DepositRecord>, AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.list_deposits(filter).await,
+            RepositoryWrapper::PostgreSQL(r) => r.list_deposits(filter).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.list_deposits(filter).await,
+        }
+    }
 
-    async fn load_customer_addresses(&self, chain_name: &str) -> Result<usize, AppError> {
+    async fn get_pending_deposits(&self) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
         match self {
-            RepositoryWrapper::Memory(r) => r.load_customer_addresses(chain_name).await,
-            RepositoryWrapper::PostgreSQL(r) => r.load_customer_addresses(chain_name).await,
+            RepositoryWrapper::Memory(r) => r.get_pending_deposits().await,
+            RepositoryWrapper::PostgreSQL(r) => r.get_pending_deposits().await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.get_pending_deposits().await,
         }
     }
 
-    async fn deposit_exists(&self, tx_hash: &str, chain_name: &str) -> Result<bool, AppError> {
+    async fn get_recent_block_hashes(&self, chain: &str, limit: u64) -> Result<Vec<(u64, String)>, AppError> {
         match self {
-            RepositoryWrapper::Memory(r) => r.deposit_exists(tx_hash, chain_name).await,
-            RepositoryWrapper::PostgreSQL(r) => r.deposit_exists(tx_hash, chain_name).await,
+            RepositoryWrapper::Memory(r) => r.get_recent_block_hashes(chain, limit).await,
+            RepositoryWrapper::PostgreSQL(r) => r.get_recent_block_hashes(chain, limit).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.get_recent_block_hashes(chain, limit).await,
         }
     }
 
-    async fn is_deposit_confirmed(&self, tx_hash: &str) -> Result<bool, AppError> {
+    async fn rollback_to_block(&self, chain: &str, common_ancestor: u64, notify: bool) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
         match self {
-            RepositoryWrapper::Memory(r) => r.is_deposit_confirmed(tx_hash).await,
-            RepositoryWrapper::PostgreSQL(r) => r.is_deposit_confirmed(tx_hash).await,
+            RepositoryWrapper::Memory(r) => r.rollback_to_block(chain, common_ancestor, notify).await,
+            RepositoryWrapper::PostgreSQL(r) => r.rollback_to_block(chain, common_ancestor, notify).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.rollback_to_block(chain, common_ancestor, notify).await,
         }
     }
 
-    async fn get_pending_deposits(&self) -> Result<Vec<crate::tasks::PendingDeposit>, AppError> {
+    async fn get_pending_notifications(&self, sink: &str, limit: usize) -> Result<Vec<crate::tasks::OutboxEntry>, AppError> {
         match self {
-            RepositoryWrapper::Memory(r) => r.get_pending_deposits().await,
-            RepositoryWrapper::PostgreSQL(r) => r.get_pending_deposits().await,
+            RepositoryWrapper::Memory(r) => r.get_pending_notifications(sink, limit).await,
+            RepositoryWrapper::PostgreSQL(r) => r.get_pending_notifications(sink, limit).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.get_pending_notifications(sink, limit).await,
         }
     }
-}
 
-impl RepositoryWrapper {
-    /// Update deposit confirmation status
-    pub async fn update_deposit_confirmed(&self, tx_hash: &str) -> Result<(), AppError> {
-        match self {
-            RepositoryWrapper::Memory(r) => r.update_deposit_confirmed(tx_hash).await,
-            RepositoryWrapper::PostgreSQL(_) => {
-                // For PostgreSQL, use the postgresql module function
-                if let Some(pg_repo) = self.get_postgresql_repo() {
-                    crate::respository::postgresql::update_deposit_confirmed(pg_repo.pool(), tx_hash).await
-                } else {
-                    Err(AppError::Database("PostgreSQL repository not available".to_string()))
-                }
-            }
+    async fn mark_notification_sent(&self, id: i64, sink: &str) -> Result<(), AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.mark_notification_sent(id, sink).await,
+            RepositoryWrapper::PostgreSQL(r) => r.mark_notification_sent(id, sink).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.mark_notification_sent(id, sink).await,
+        }
+    }
+
+    async fn mark_notification_failed(&self, id: i64, sink: &str, error: &str, retry_after_secs: u64) -> Result<(), AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.mark_notification_failed(id, sink, error, retry_after_secs).await,
+            RepositoryWrapper::PostgreSQL(r) => r.mark_notification_failed(id, sink, error, retry_after_secs).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.mark_notification_failed(id, sink, error, retry_after_secs).await,
+        }
+    }
+
+    async fn mark_notification_dead(&self, id: i64, sink: &str, error: &str) -> Result<(), AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.mark_notification_dead(id, sink, error).await,
+            RepositoryWrapper::PostgreSQL(r) => r.mark_notification_dead(id, sink, error).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.mark_notification_dead(id, sink, error).await,
+        }
+    }
+
+    async fn complete_notifications(&self, sinks: &[String]) -> Result<usize, AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.complete_notifications(sinks).await,
+            RepositoryWrapper::PostgreSQL(r) => r.complete_notifications(sinks).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.complete_notifications(sinks).await,
+        }
+    }
+
+    async fn enqueue_notification(&self, event: &DepositEvent) -> Result<(), AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.enqueue_notification(event).await,
+            RepositoryWrapper::PostgreSQL(r) => r.enqueue_notification(event).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.enqueue_notification(event).await,
         }
     }
 
+    async fn create_backfill_job(&self, chain: &str, from_block: u64, to_block: u64, address: Option<&str>) -> Result<BackfillJob, AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.create_backfill_job(chain, from_block, to_block, address).await,
+            RepositoryWrapper::PostgreSQL(r) => r.create_backfill_job(chain, from_block, to_block, address).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.create_backfill_job(chain, from_block, to_block, address).await,
+        }
+    }
+
+    async fn update_backfill_job(&self, id: i64, next_block: u64, status: BackfillStatus, error: Option<&str>) -> Result<(), AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.update_backfill_job(id, next_block, status, error).await,
+            RepositoryWrapper::PostgreSQL(r) => r.update_backfill_job(id, next_block, status, error).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.update_backfill_job(id, next_block, status, error).await,
+        }
+    }
+
+    async fn get_backfill_job(&self, id: i64) -> Result<Option<BackfillJob>, AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.get_backfill_job(id).await,
+            RepositoryWrapper::PostgreSQL(r) => r.get_backfill_job(id).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.get_backfill_job(id).await,
+        }
+    }
+
+    async fn list_backfill_jobs(&self, unfinished_only: bool) -> Result<Vec<BackfillJob>, AppError> {
+        match self {
+            RepositoryWrapper::Memory(r) => r.list_backfill_jobs(unfinished_only).await,
+            RepositoryWrapper::PostgreSQL(r) => r.list_backfill_jobs(unfinished_only).await,
+            #[cfg(feature = "rocksdb-backend")]
+            RepositoryWrapper::RocksDB(r) => r.list_backfill_jobs(unfinished_only).await,
+        }
+    }
+}
+
+impl RepositoryWrapper {
     #[cfg(feature = "rocksdb-backend")]
     pub fn get_rocksdb_repo(&self) -> Option<Arc<crate::respository::RocksDBRepository>> {
         match self {
-            RepositoryWrapper::PostgreSQL(_) => None,
-            RepositoryWrapper::Memory(_) => None,
+            RepositoryWrapper::RocksDB(r) => Some(r.clone()),
+            _ => None,
         }
     }
 }
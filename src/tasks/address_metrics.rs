@@ -0,0 +1,24 @@
+use crate::respository::AddressIndex;
+use log::{info, warn};
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tokio_util::sync::CancellationToken;
+
+/// 체인별 관리 대상 주소 수를 주기적으로 `xscanner_monitored_addresses`에 기록
+pub async fn run_address_metrics(address_index: Arc<dyn AddressIndex>, chains: Vec<String>, interval_secs: u64, shutdown: CancellationToken) {
+    info!("[AddressMetrics] Counting monitored addresses every {}s for {} chain(s)", interval_secs, chains.len());
+
+    let mut tick = interval(Duration::from_secs(interval_secs));
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {}
+            _ = shutdown.cancelled() => break,
+        }
+        for chain in &chains {
+            match address_index.count(chain).await {
+                Ok(count) => crate::metrics::set_monitored_addresses(chain, count),
+                Err(e) => warn!("[AddressMetrics] Failed to count {} addresses: {}", chain, e),
+            }
+        }
+    }
+}
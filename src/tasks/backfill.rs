@@ -0,0 +1,428 @@
+use crate::fetcher::fetcher::BlockFetcher;
+use crate::respository::{Repository, RepositoryWrapper};
+use crate::types::{BackfillJob, BackfillStatus, BlockData, BlockSender, ChainEvent};
+use log::{error, info, warn};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::{oneshot, Mutex, Notify};
+use tokio::task::JoinSet;
+use tokio::time::{interval, sleep, Duration, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
+
+const FETCH_RETRY_ATTEMPTS: u32 = 3;
+const FETCH_RETRY_DELAY_SECS: u64 = 2;
+
+/// backfill 요청 (to_block이 없으면 현재 last_processed_block까지)
+#[derive(Debug, Clone)]
+pub struct BackfillRequest {
+    pub chain_name: String,
+    pub from_block: u64,
+    pub to_block: Option<u64>,
+    /// 지정되면 그 주소의 입금만 처리 (늦게 등록된 주소를 first_seen_block부터 backfill하는 경우)
+    pub address: Option<String>,
+}
+
+/// Configuration for backfill scheduler
+#[derive(Debug, Clone)]
+pub struct BackfillConfig {
+    pub blocks_per_second: f64,
+    pub poll_interval_secs: u64,
+    pub checkpoint_blocks: u64,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            blocks_per_second: 5.0,
+            poll_interval_secs: 10,
+            checkpoint_blocks: 10,
+        }
+    }
+}
+
+/// backfill 작업 등록 (실행은 스캐너의 BackfillScheduler가 담당)
+///
+/// last_processed_block 이후 블록은 live fetcher가 처리하므로 그 이전 구간만 등록할 수 있다.
+pub async fn create_backfill_job(repository: &RepositoryWrapper, request: BackfillRequest) -> Result<BackfillJob, String> {
+    let chain_name = request.chain_name.to_uppercase();
+    let last_processed_block = repository.get_last_processed_block(&chain_name).await
+        .map_err(|e| format!("Failed to get last processed block for {}: {}", chain_name, e))?;
+
+    let to_block = request.to_block.unwrap_or(last_processed_block);
+    if to_block > last_processed_block {
+        return Err(format!(
+            "to_block {} is beyond last processed block {} for {}",
+            to_block, last_processed_block, chain_name
+        ));
+    }
+    if request.from_block > to_block {
+        return Err(format!("from_block {} is after to_block {}", request.from_block, to_block));
+    }
+
+    let job = repository
+        .create_backfill_job(&chain_name, request.from_block, to_block, request.address.as_deref())
+        .await
+        .map_err(|e| format!("Failed to create backfill job: {}", e))?;
+
+    info!("[Backfill] Job {} registered: {} blocks {}..={}", job.id, chain_name, job.from_block, job.to_block);
+    Ok(job)
+}
+
+/// backfill 작업 실행기
+///
+/// 블록은 `ChainEvent::Rescan`으로 그 체인의 analyzer에 전달된다 - 입금은 다시 저장되지만 (이미 저장된 입금은 무시)
+/// live cursor(last_processed_block)와 블록 해시는 바뀌지 않는다. 체인당 한 번에 한 작업을 id 순서로 실행하고,
+/// analyzer가 처리를 마친 블록까지 진행 위치를 저장해 재시작 후 이어서 실행한다.
+pub struct BackfillScheduler {
+    // chain_name -> (fetcher, 체인의 analyzer 채널)
+    chains: HashMap<String, (Arc<dyn BlockFetcher>, BlockSender)>,
+    repository: Arc<RepositoryWrapper>,
+    config: BackfillConfig,
+    // 작업을 실행 중인 체인
+    active: Mutex<HashSet<String>>,
+    wake: Notify,
+}
+
+impl BackfillScheduler {
+    pub fn new(repository: Arc<RepositoryWrapper>, config: BackfillConfig) -> Self {
+        Self {
+            chains: HashMap::new(),
+            repository,
+            config,
+            active: Mutex::new(HashSet::new()),
+            wake: Notify::new(),
+        }
+    }
+
+    /// 체인 등록 (fetcher.chain_name() 기준)
+    pub fn register(&mut self, fetcher: Arc<dyn BlockFetcher>, sender: BlockSender) {
+        self.chains.insert(fetcher.chain_name().to_uppercase(), (fetcher, sender));
+    }
+
+    /// backfill할 수 있는 체인인지 확인
+    pub fn supports(&self, chain_name: &str) -> bool {
+        self.chains.contains_key(&chain_name.to_uppercase())
+    }
+
+    /// 작업 등록 후 바로 실행 (같은 체인의 작업이 실행 중이면 그 다음에 실행)
+    pub async fn submit(&self, request: BackfillRequest) -> Result<BackfillJob, String> {
+        if !self.supports(&request.chain_name) {
+            return Err(format!("Unknown chain: {}", request.chain_name));
+        }
+
+        let job = create_backfill_job(&self.repository, request).await?;
+        self.wake.notify_one();
+        Ok(job)
+    }
+
+    /// 실행 중인 작업이 없는 체인의 가장 오래된 미완료 작업 시작
+    async fn start_pending_jobs(self: &Arc<Self>, running: &mut JoinSet<()>, shutdown: &CancellationToken) {
+        let jobs = match self.repository.list_backfill_jobs(true).await {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                error!("[Backfill] Failed to list backfill jobs: {}", e);
+                return;
+            }
+        };
+
+        let mut active = self.active.lock().await;
+        for job in jobs {
+            if active.contains(&job.chain) {
+                continue;
+            }
+            // 이 스캐너가 처리하지 않는 체인의 작업은 pending으로 남겨둔다
+            let Some((fetcher, sender)) = self.chains.get(&job.chain).cloned() else {
+                continue;
+            };
+
+            active.insert(job.chain.clone());
+            running.spawn(run_job(self.clone(), fetcher, sender, job, shutdown.clone()));
+        }
+    }
+}
+
+/// Run backfill scheduler - 재시작 전 작업과 다른 프로세스(CLI)가 등록한 작업을 poll_interval마다 확인
+///
+/// `shutdown`이 취소되면 새 작업을 시작하지 않고, 실행 중인 작업이 진행 위치를 저장할 때까지 기다린 뒤 종료한다.
+pub async fn run_backfill_scheduler(scheduler: Arc<BackfillScheduler>, shutdown: CancellationToken) {
+    info!(
+        "[Backfill] Starting scheduler ({} blocks/s per chain, poll_interval: {}s)",
+        scheduler.config.blocks_per_second, scheduler.config.poll_interval_secs
+    );
+
+    let mut running = JoinSet::new();
+    loop {
+        // 끝난 작업 정리
+        while running.try_join_next().is_some() {}
+
+        scheduler.start_pending_jobs(&mut running, &shutdown).await;
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = sleep(Duration::from_secs(scheduler.config.poll_interval_secs)) => {}
+            _ = scheduler.wake.notified() => {}
+        }
+    }
+
+    while running.join_next().await.is_some() {}
+    info!("[Backfill] Stopped");
+}
+
+/// 작업이 멈춘 이유
+enum Stop {
+    /// 블록 조회/분석 실패 - failed로 기록
+    Failed { next_block: u64, error: String },
+    /// shutdown 또는 analyzer 종료 - running으로 남겨 재시작 후 이어서 실행
+    Interrupted { next_block: u64 },
+}
+
+async fn run_job(
+    scheduler: Arc<BackfillScheduler>,
+    fetcher: Arc<dyn BlockFetcher>,
+    sender: BlockSender,
+    job: BackfillJob,
+    shutdown: CancellationToken,
+) {
+    let (next_block, status, error) = match backfill_blocks(&scheduler, fetcher.as_ref(), &sender, &job, &shutdown).await {
+        Ok(()) => {
+            info!("[Backfill] Job {} finished: {} blocks {}..={}", job.id, job.chain, job.from_block, job.to_block);
+            (job.to_block + 1, BackfillStatus::Finished, None)
+        }
+        Err(Stop::Failed { next_block, error }) => {
+            warn!("[Backfill] Job {} ({}) failed at block {}: {}", job.id, job.chain, next_block, error);
+            (next_block, BackfillStatus::Failed, Some(error))
+        }
+        Err(Stop::Interrupted { next_block }) => {
+            info!("[Backfill] Job {} ({}) interrupted at block {}, will resume after restart", job.id, job.chain, next_block);
+            (next_block, BackfillStatus::Running, None)
+        }
+    };
+
+    if let Err(e) = scheduler.repository.update_backfill_job(job.id, next_block, status, error.as_deref()).await {
+        error!("[Backfill] Failed to save job {} progress: {}", job.id, e);
+    }
+
+    scheduler.active.lock().await.remove(&job.chain);
+    if status != BackfillStatus::Running {
+        // 같은 체인의 다음 작업 시작
+        scheduler.wake.notify_one();
+    }
+}
+
+async fn backfill_blocks(
+    scheduler: &BackfillScheduler,
+    fetcher: &dyn BlockFetcher,
+    sender: &BlockSender,
+    job: &BackfillJob,
+    shutdown: &CancellationToken,
+) -> Result<(), Stop> {
+    info!(
+        "[Backfill] Job {} started: {} blocks {}..={} (next block {})",
+        job.id, job.chain, job.from_block, job.to_block, job.next_block
+    );
+    if let Err(e) = scheduler.repository.update_backfill_job(job.id, job.next_block, BackfillStatus::Running, None).await {
+        warn!("[Backfill] Failed to mark job {} running: {}", job.id, e);
+    }
+
+    // live fetcher와 별개로 체인당 blocks_per_second 이하로 조회
+    let mut rate = interval(Duration::from_secs_f64(1.0 / scheduler.config.blocks_per_second.max(0.001)));
+    rate.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut next_block = job.next_block;
+    while next_block <= job.to_block {
+        tokio::select! {
+            _ = shutdown.cancelled() => return Err(Stop::Interrupted { next_block }),
+            _ = rate.tick() => {}
+        }
+
+        let block = fetch_with_retry(fetcher, next_block).await
+            .map_err(|error| Stop::Failed { next_block, error })?;
+
+        let (processed, done) = oneshot::channel();
+        if sender.send(ChainEvent::Rescan { block, address: job.address.clone(), processed }).await.is_err() {
+            return Err(Stop::Interrupted { next_block });
+        }
+        match done.await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => return Err(Stop::Failed { next_block, error }),
+            Err(_) => return Err(Stop::Interrupted { next_block }),
+        }
+
+        next_block += 1;
+        if (next_block - job.from_block).is_multiple_of(scheduler.config.checkpoint_blocks.max(1)) {
+            if let Err(e) = scheduler.repository.update_backfill_job(job.id, next_block, BackfillStatus::Running, None).await {
+                warn!("[Backfill] Failed to checkpoint job {} at block {}: {}", job.id, next_block, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn fetch_with_retry(fetcher: &dyn BlockFetcher, block_number: u64) -> Result<BlockData, String> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match fetcher.fetch_block(block_number).await {
+            Ok(block) => return Ok(block),
+            Err(e) if attempt < FETCH_RETRY_ATTEMPTS => {
+                warn!("[Backfill] Failed to fetch block {} (attempt {}): {}", block_number, attempt, e);
+                sleep(Duration::from_secs(FETCH_RETRY_DELAY_SECS)).await;
+            }
+            Err(e) => return Err(format!("Failed to fetch block {}: {}", block_number, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coin::bitcoin::model::BitcoinBlock;
+    use crate::respository::MemoryRepository;
+    use crate::types::AppError;
+    use async_trait::async_trait;
+    use tokio::sync::mpsc;
+    use tokio::task::JoinHandle;
+
+    /// 요청한 번호의 빈 블록을 돌려주는 fetcher
+    struct EmptyChain;
+
+    #[async_trait]
+    impl BlockFetcher for EmptyChain {
+        async fn fetch_block(&self, block_number: u64) -> Result<BlockData, AppError> {
+            Ok(BlockData::Bitcoin(BitcoinBlock {
+                hash: format!("0x{:x}", block_number),
+                ver: 1,
+                prev_block: format!("0x{:x}", block_number.saturating_sub(1)),
+                mrkl_root: String::new(),
+                time: 0,
+                bits: 0,
+                next_block: None,
+                fee: 0,
+                nonce: 0,
+                n_tx: 0,
+                size: 0,
+                block_index: block_number,
+                main_chain: true,
+                height: block_number,
+                weight: 0,
+                tx: Vec::new(),
+            }))
+        }
+
+        fn chain_name(&self) -> &'static str {
+            "BTC"
+        }
+    }
+
+    /// analyzer 대신 Rescan 블록을 받아 processed에 응답
+    ///
+    /// 블록마다 (블록 번호, 그 시점에 저장된 작업의 next_block)을 기록한다.
+    /// `fail_at` 블록은 처리 실패로 응답하고, `stop_after` 블록에 응답한 뒤에는 shutdown을 취소하고 종료한다.
+    fn fake_analyzer(
+        repository: Arc<RepositoryWrapper>,
+        job_id: i64,
+        mut receiver: mpsc::Receiver<ChainEvent>,
+        fail_at: Option<u64>,
+        stop_after: Option<(u64, CancellationToken)>,
+    ) -> JoinHandle<Vec<(u64, u64)>> {
+        tokio::spawn(async move {
+            let mut received = Vec::new();
+            while let Some(event) = receiver.recv().await {
+                let ChainEvent::Rescan { block, processed, .. } = event else {
+                    continue;
+                };
+                let BlockData::Bitcoin(block) = block else {
+                    continue;
+                };
+                let saved = repository.get_backfill_job(job_id).await.unwrap().unwrap().next_block;
+                received.push((block.height, saved));
+
+                if fail_at == Some(block.height) {
+                    let _ = processed.send(Err("Failed to commit BTC block".to_string()));
+                    continue;
+                }
+                let _ = processed.send(Ok(()));
+                if let Some((_, shutdown)) = stop_after.as_ref().filter(|(height, _)| *height == block.height) {
+                    shutdown.cancel();
+                    break;
+                }
+            }
+            received
+        })
+    }
+
+    fn scheduler(checkpoint_blocks: u64) -> (Arc<BackfillScheduler>, Arc<RepositoryWrapper>) {
+        let repository = Arc::new(RepositoryWrapper::Memory(Arc::new(MemoryRepository::new())));
+        let config = BackfillConfig { blocks_per_second: 1000.0, poll_interval_secs: 1, checkpoint_blocks };
+        (Arc::new(BackfillScheduler::new(repository.clone(), config)), repository)
+    }
+
+    async fn job(repository: &RepositoryWrapper, id: i64) -> BackfillJob {
+        repository.get_backfill_job(id).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn job_checkpoints_every_checkpoint_blocks() {
+        let (scheduler, repository) = scheduler(3);
+        let created = repository.create_backfill_job("BTC", 100, 107, None).await.unwrap();
+        let (sender, receiver) = mpsc::channel(1);
+        let analyzer = fake_analyzer(repository.clone(), created.id, receiver, None, None);
+
+        run_job(scheduler, Arc::new(EmptyChain), sender, created.clone(), CancellationToken::new()).await;
+
+        // 블록을 처리할 때 저장돼 있던 진행 위치 - 3블록마다 갱신
+        assert_eq!(
+            analyzer.await.unwrap(),
+            [(100, 100), (101, 100), (102, 100), (103, 103), (104, 103), (105, 103), (106, 106), (107, 106)]
+        );
+        let finished = job(&repository, created.id).await;
+        assert_eq!(finished.status, BackfillStatus::Finished);
+        assert_eq!(finished.next_block, 108);
+    }
+
+    #[tokio::test]
+    async fn interrupted_job_stays_running_and_resumes() {
+        let (scheduler, repository) = scheduler(3);
+        let created = repository.create_backfill_job("BTC", 100, 107, None).await.unwrap();
+        let shutdown = CancellationToken::new();
+        let (sender, receiver) = mpsc::channel(1);
+        let analyzer = fake_analyzer(repository.clone(), created.id, receiver, None, Some((104, shutdown.clone())));
+
+        run_job(scheduler.clone(), Arc::new(EmptyChain), sender, created.clone(), shutdown).await;
+
+        assert_eq!(analyzer.await.unwrap().last(), Some(&(104, 103)));
+        let interrupted = job(&repository, created.id).await;
+        assert_eq!(interrupted.status, BackfillStatus::Running);
+        assert_eq!(interrupted.next_block, 105);
+        assert_eq!(interrupted.error, None);
+
+        // 재시작 후 미완료 작업으로 다시 조회되고, 처리한 다음 블록부터 이어서 실행
+        let resumed = repository.list_backfill_jobs(true).await.unwrap();
+        assert_eq!(resumed.len(), 1);
+        let (sender, receiver) = mpsc::channel(1);
+        let analyzer = fake_analyzer(repository.clone(), created.id, receiver, None, None);
+
+        run_job(scheduler, Arc::new(EmptyChain), sender, resumed[0].clone(), CancellationToken::new()).await;
+
+        let heights: Vec<u64> = analyzer.await.unwrap().into_iter().map(|(height, _)| height).collect();
+        assert_eq!(heights, [105, 106, 107]);
+        assert_eq!(job(&repository, created.id).await.status, BackfillStatus::Finished);
+    }
+
+    #[tokio::test]
+    async fn failed_block_marks_job_failed_at_that_block() {
+        let (scheduler, repository) = scheduler(3);
+        let created = repository.create_backfill_job("BTC", 100, 107, None).await.unwrap();
+        let (sender, receiver) = mpsc::channel(1);
+        let analyzer = fake_analyzer(repository.clone(), created.id, receiver, Some(102), None);
+
+        run_job(scheduler, Arc::new(EmptyChain), sender, created.clone(), CancellationToken::new()).await;
+
+        assert_eq!(analyzer.await.unwrap().len(), 3);
+        let failed = job(&repository, created.id).await;
+        assert_eq!(failed.status, BackfillStatus::Failed);
+        assert_eq!(failed.next_block, 102);
+        assert!(failed.error.is_some_and(|error| error.contains("Failed to commit")));
+        assert!(repository.list_backfill_jobs(true).await.unwrap().is_empty());
+    }
+}
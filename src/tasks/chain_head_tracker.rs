@@ -0,0 +1,99 @@
+use crate::fetcher::fetcher::BlockFetcher;
+use crate::types::AppError;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration};
+use tokio_util::sync::CancellationToken;
+
+/// 체인별 실제 최신 블록 높이 추적
+///
+/// confirmation 수를 스캐너 진행 상황(last_processed_block)이 아닌 네트워크 기준으로 계산하고,
+/// 입금 블록이 여전히 canonical인지 확인하는 데 사용한다.
+pub struct ChainHeadTracker {
+    // chain_name -> (fetcher, poll interval)
+    fetchers: HashMap<String, (Arc<dyn BlockFetcher>, Duration)>,
+    // chain_name -> 최신 블록 번호
+    heads: RwLock<HashMap<String, u64>>,
+}
+
+impl ChainHeadTracker {
+    pub fn new() -> Self {
+        Self {
+            fetchers: HashMap::new(),
+            heads: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 체인 등록 (fetcher.chain_name() 기준)
+    pub fn register(&mut self, fetcher: Arc<dyn BlockFetcher>, poll_interval: Duration) {
+        self.fetchers.insert(fetcher.chain_name().to_uppercase(), (fetcher, poll_interval));
+    }
+
+    /// 마지막으로 조회한 최신 블록 번호 (head 조회를 지원하지 않거나 아직 조회 전이면 None)
+    pub async fn head(&self, chain_name: &str) -> Option<u64> {
+        self.heads.read().await.get(&chain_name.to_uppercase()).copied()
+    }
+
+    /// block_number에 포함된 트랜잭션의 confirmation 수
+    /// 체인 head를 모르면 fallback_head(스캐너가 처리한 블록) 기준으로 계산
+    pub async fn confirmations(&self, chain_name: &str, block_number: u64, fallback_head: u64) -> u64 {
+        let head = self.head(chain_name).await.unwrap_or(fallback_head);
+        head.saturating_sub(block_number) + 1
+    }
+
+    /// block_number의 블록 해시가 여전히 canonical 체인의 해시인지 확인
+    /// 체인이 등록되지 않았거나 블록 해시를 알 수 없으면 확인 불가로 보고 true
+    pub async fn is_canonical(&self, chain_name: &str, block_number: u64, block_hash: &str) -> Result<bool, AppError> {
+        let Some((fetcher, _)) = self.fetchers.get(&chain_name.to_uppercase()) else {
+            return Ok(true);
+        };
+
+        let canonical = fetcher.fetch_block_hash(block_number).await?;
+        Ok(canonical.is_none_or(|canonical| canonical.eq_ignore_ascii_case(block_hash)))
+    }
+}
+
+impl Default for ChainHeadTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 등록된 체인마다 최신 블록 번호를 주기적으로 조회 (`shutdown`이 취소되면 종료)
+pub async fn run_chain_head_tracker(tracker: Arc<ChainHeadTracker>, shutdown: CancellationToken) -> Vec<JoinHandle<()>> {
+    info!("[ChainHeadTracker] Tracking {} chain(s)", tracker.fetchers.len());
+
+    let mut handles = Vec::new();
+    for (chain_name, (fetcher, poll_interval)) in &tracker.fetchers {
+        let tracker = tracker.clone();
+        let chain_name = chain_name.clone();
+        let fetcher = fetcher.clone();
+        let poll_interval = *poll_interval;
+        let shutdown = shutdown.clone();
+
+        handles.push(tokio::spawn(async move {
+            let mut tick = interval(poll_interval);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tick.tick() => {}
+                }
+                match fetcher.fetch_latest_block_number().await {
+                    Ok(Some(head)) => {
+                        crate::metrics::set_chain_head(&chain_name, head);
+                        tracker.heads.write().await.insert(chain_name.clone(), head);
+                    }
+                    Ok(None) => {
+                        info!("[ChainHeadTracker] {} does not support head queries, using scanner progress", chain_name);
+                        break;
+                    }
+                    Err(e) => warn!("[ChainHeadTracker] Failed to fetch {} head: {}", chain_name, e),
+                }
+            }
+        }));
+    }
+    handles
+}
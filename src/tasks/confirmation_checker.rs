@@ -1,10 +1,13 @@
 use crate::respository::{RepositoryWrapper, Repository};
-use crate::notification::sqs_client::SqsNotifier;
+use crate::notification::{DepositEvent, EventPublisher};
 use crate::config::ChainConfig;
+use crate::tasks::ChainHeadTracker;
+use crate::types::{DepositStatus, StatusTransition};
 use log::{info, error, warn};
 use std::sync::Arc;
 use std::collections::HashMap;
 use tokio::time::{interval, Duration};
+use tokio_util::sync::CancellationToken;
 
 /// Pending deposit information from database
 #[derive(Debug, Clone)]
@@ -14,9 +17,57 @@ pub struct PendingDeposit {
     pub account_id: Option<String>,
     pub chain_name: String,
     pub tx_hash: String,
+    pub output_index: u32,
     pub block_number: u64,
     pub amount: String,
     pub amount_decimal: Option<rust_decimal::Decimal>,
+    pub asset: crate::types::AssetInfo,
+    pub block_hash: Option<String>, // 입금이 포함된 블록 해시 (canonical 확인용)
+    pub status: DepositStatus,
+    pub status_version: i64, // 상태 전이 시 낙관적 동시성 제어용
+}
+
+impl PendingDeposit {
+    /// 현재 상태(status, status_version)에서 to로 가는 전이
+    pub fn transition_to(&self, to: DepositStatus, confirmations: u64) -> StatusTransition {
+        StatusTransition {
+            from: self.status,
+            to,
+            expected_version: self.status_version,
+            confirmations,
+        }
+    }
+
+    pub fn confirmed_event(&self, confirmations: u64) -> DepositEvent {
+        DepositEvent::DepositConfirmed {
+            address: self.address.clone(),
+            wallet_id: self.wallet_id.clone(),
+            account_id: self.account_id.clone(),
+            chain: self.chain_name.to_uppercase(),
+            tx_hash: self.tx_hash.clone(),
+            output_index: self.output_index,
+            amount: self.amount.clone(),
+            asset: self.asset.clone(),
+            block_number: self.block_number,
+            block_hash: self.block_hash.clone(),
+            confirmations,
+        }
+    }
+
+    pub fn orphaned_event(&self) -> DepositEvent {
+        DepositEvent::DepositOrphaned {
+            address: self.address.clone(),
+            wallet_id: self.wallet_id.clone(),
+            account_id: self.account_id.clone(),
+            chain: self.chain_name.to_uppercase(),
+            tx_hash: self.tx_hash.clone(),
+            output_index: self.output_index,
+            amount: self.amount.clone(),
+            asset: self.asset.clone(),
+            block_number: self.block_number,
+            block_hash: self.block_hash.clone(),
+        }
+    }
 }
 
 /// Configuration for confirmation checker
@@ -36,13 +87,15 @@ impl Default for ConfirmationCheckerConfig {
 
 /// Run confirmation checker - periodically checks pending deposits
 ///
-/// This service periodically queries the database for unconfirmed deposits
-/// and checks if they have reached the required number of confirmations.
+/// This service periodically queries the database for detected / confirming deposits
+/// and moves them to confirmed once they have reached the required number of confirmations.
 pub async fn run_confirmation_checker(
     repository: Arc<RepositoryWrapper>,
     chain_configs: HashMap<String, ChainConfig>,
-    sqs_notifier: Option<Arc<SqsNotifier>>,
+    publisher: Option<Arc<EventPublisher>>,
     config: ConfirmationCheckerConfig,
+    head_tracker: Arc<ChainHeadTracker>,
+    shutdown: CancellationToken,
 ) {
     if !config.enabled {
         info!("[ConfirmationChecker] Disabled by configuration, skipping...");
@@ -54,25 +107,32 @@ pub async fn run_confirmation_checker(
     let mut check_interval = interval(Duration::from_secs(config.check_interval_secs));
 
     loop {
-        check_interval.tick().await;
+        tokio::select! {
+            _ = check_interval.tick() => {}
+            _ = shutdown.cancelled() => break,
+        }
 
         if let Err(e) = check_pending_deposits(
             &repository,
             &chain_configs,
-            sqs_notifier.as_deref(),
+            publisher.is_some(),
+            &head_tracker,
         ).await {
             error!("[ConfirmationChecker] Error checking pending deposits: {}", e);
         }
     }
+
+    info!("[ConfirmationChecker] Stopped");
 }
 
 /// Check all pending deposits and send confirmations if ready
 async fn check_pending_deposits(
     repository: &Arc<RepositoryWrapper>,
     chain_configs: &HashMap<String, ChainConfig>,
-    sqs_notifier: Option<&SqsNotifier>,
+    notifications_enabled: bool,
+    head_tracker: &ChainHeadTracker,
 ) -> Result<(), String> {
-    // Get all pending (unconfirmed) deposits from database
+    // Get all pending (detected / confirming) deposits from database
     let pending_deposits = repository
         .get_pending_deposits()
         .await
@@ -92,13 +152,16 @@ async fn check_pending_deposits(
             .map(|c| c.required_confirmations)
             .unwrap_or(12); // Default to 12 if not found
 
-        // Get current block number for this chain
-        let current_block = match repository.get_last_processed_block(&deposit.chain_name).await {
-            Ok(block) => block,
-            Err(e) => {
-                error!("[ConfirmationChecker] Failed to get last processed block for {}: {}", deposit.chain_name, e);
-                continue;
-            }
+        // Get current block number for this chain (head를 조회할 수 없는 체인은 스캐너 진행 상황 기준)
+        let current_block = match head_tracker.head(&deposit.chain_name).await {
+            Some(head) => head,
+            None => match repository.get_last_processed_block(&deposit.chain_name).await {
+                Ok(block) => block,
+                Err(e) => {
+                    error!("[ConfirmationChecker] Failed to get last processed block for {}: {}", deposit.chain_name, e);
+                    continue;
+                }
+            },
         };
 
         // Calculate confirmations
@@ -112,46 +175,64 @@ async fn check_pending_deposits(
 
         // Check if reached required confirmations
         if confirmations >= required_confirmations {
-            // Check if already confirmed (double-check to prevent duplicates)
-            let is_confirmed = repository
-                .is_deposit_confirmed(&deposit.tx_hash)
-                .await
-                .map_err(|e| format!("Failed to check confirmation status: {}", e))?;
-
-            if is_confirmed {
-                warn!("[ConfirmationChecker] Deposit {} already confirmed, skipping", deposit.tx_hash);
-                continue;
+            // 입금 블록이 여전히 canonical인지 확인 (아니면 reorg 처리에서 orphaned로 정리됨)
+            if let Some(block_hash) = &deposit.block_hash {
+                match head_tracker.is_canonical(&deposit.chain_name, deposit.block_number, block_hash).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        warn!(
+                            "[ConfirmationChecker] Deposit {} block #{} ({}) is no longer canonical, not confirming",
+                            deposit.tx_hash, deposit.block_number, block_hash
+                        );
+                        continue;
+                    }
+                    Err(e) => {
+                        warn!("[ConfirmationChecker] Failed to verify block #{} for {}: {}", deposit.block_number, deposit.tx_hash, e);
+                        continue;
+                    }
+                }
             }
 
-            info!(
-                "[ConfirmationChecker] ✅ Deposit {} reached {} confirmations, sending DEPOSIT_CONFIRMED",
-                deposit.tx_hash, confirmations
-            );
-
-            // Update database
-            repository
-                .update_deposit_confirmed(&deposit.tx_hash)
+            let event = deposit.confirmed_event(confirmations);
+
+            // 상태 전이 (알림은 같은 트랜잭션에서 outbox에 기록, 전송은 outbox dispatcher가 담당)
+            let applied = repository
+                .transition_deposit_status(
+                    &deposit.tx_hash,
+                    deposit.output_index,
+                    &deposit.chain_name,
+                    &deposit.transition_to(DepositStatus::Confirmed, confirmations),
+                    notifications_enabled.then_some(&event),
+                )
                 .await
-                .map_err(|e| format!("Failed to update deposit confirmation: {}", e))?;
-
-            // Send SQS notification
-            if let Some(notifier) = sqs_notifier {
-                if let Err(e) = notifier.send_deposit_confirmed(
-                    deposit.address.clone(),
-                    deposit.wallet_id.clone(),
-                    deposit.account_id.clone(),
-                    deposit.chain_name.to_uppercase(),
-                    deposit.tx_hash.clone(),
-                    deposit.amount.clone(),
-                    deposit.block_number,
-                    confirmations,
-                ).await {
-                    error!("[ConfirmationChecker] Failed to send SQS notification: {}", e);
-                } else {
-                    info!("[ConfirmationChecker] ✅ SQS DEPOSIT_CONFIRMED sent for {}", deposit.tx_hash);
-                }
+                .map_err(|e| format!("Failed to confirm deposit: {}", e))?;
+
+            if applied {
+                crate::metrics::inc_deposits_confirmed(&deposit.chain_name);
+                info!(
+                    "[ConfirmationChecker] ✅ Deposit {} reached {} confirmations, queueing DEPOSIT_CONFIRMED",
+                    deposit.tx_hash, confirmations
+                );
+            } else {
+                // Analyzer나 reorg 처리가 먼저 상태를 바꿈 - 다음 확인 주기에 다시 판단
+                warn!("[ConfirmationChecker] Deposit {} status changed concurrently, skipping", deposit.tx_hash);
             }
         } else {
+            if deposit.status == DepositStatus::Detected && confirmations > 1 {
+                if let Err(e) = repository
+                    .transition_deposit_status(
+                        &deposit.tx_hash,
+                        deposit.output_index,
+                        &deposit.chain_name,
+                        &deposit.transition_to(DepositStatus::Confirming, confirmations),
+                        None,
+                    )
+                    .await
+                {
+                    warn!("[ConfirmationChecker] Failed to mark deposit {} as confirming: {}", deposit.tx_hash, e);
+                }
+            }
+
             info!(
                 "[ConfirmationChecker] Deposit {} needs {} more confirmations",
                 deposit.tx_hash, required_confirmations - confirmations
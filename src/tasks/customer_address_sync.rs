@@ -1,22 +1,95 @@
-use crate::respository::batch_add_monitored_addresses;
+use crate::respository::{AddressIndex, AddressMetadata, MonitoredAddress};
+use crate::tasks::backfill::{BackfillRequest, BackfillScheduler};
 use crate::types::AppError;
+use async_trait::async_trait;
 use log::{info, warn, error};
-use rocksdb::DB;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tokio::time::{interval, Duration};
+use tokio_util::sync::CancellationToken;
 use aws_sdk_sqs::Client as SqsClient;
-use reqwest::Client as HttpClient;
+use aws_sdk_sqs::types::MessageSystemAttributeName;
 
+/// Backend에서 보내는 주소 변경 이벤트 (`event` 필드로 구분)
 #[derive(Debug, Deserialize, Serialize)]
-pub struct CustomerAddressEvent {
-    pub event: String, // "AddressAdded"
-    pub address: String,
-    pub chain: String,
-    pub wallet_id: String,
-    pub account_id: Option<String>, // None for Omnibus (Master) Address
-    pub timestamp: String,
+#[serde(tag = "event")]
+pub enum CustomerAddressEvent {
+    /// 새 관리 대상 주소
+    CustomerAddressAdded {
+        address: String,
+        chain: String,
+        wallet_id: String,
+        account_id: Option<String>, // None for Omnibus (Master) Address
+        /// 주소가 처음 사용된 블록 - 있으면 이 블록부터 현재 진행 위치까지 backfill
+        #[serde(default)]
+        first_seen_block: Option<u64>,
+        timestamp: String,
+    },
+    /// 주소 관리 중단 (고객 offboarding)
+    CustomerAddressRemoved {
+        address: String,
+        chain: String,
+        timestamp: String,
+    },
+    /// 주소의 wallet/account 재할당
+    CustomerAddressUpdated {
+        address: String,
+        chain: String,
+        wallet_id: String,
+        account_id: Option<String>,
+        timestamp: String,
+    },
+    /// 지갑 비활성화 - 지갑에 속한 모든 주소 관리 중단
+    WalletDeactivated {
+        wallet_id: String,
+        timestamp: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// 배치 writer가 순서대로 적용할 주소 변경
+#[derive(Debug)]
+enum AddressChange {
+    Upsert { entry: MonitoredAddress, first_seen_block: Option<u64> },
+    Remove { address: String, chain: String },
+    DeactivateWallet { wallet_id: String },
+}
+
+impl CustomerAddressEvent {
+    fn into_change(self) -> Option<AddressChange> {
+        match self {
+            CustomerAddressEvent::CustomerAddressAdded { address, chain, wallet_id, account_id, first_seen_block, .. } => {
+                Some(AddressChange::Upsert {
+                    entry: MonitoredAddress {
+                        address,
+                        chain_name: chain,
+                        metadata: AddressMetadata { wallet_id, account_id },
+                    },
+                    first_seen_block,
+                })
+            }
+            CustomerAddressEvent::CustomerAddressUpdated { address, chain, wallet_id, account_id, .. } => {
+                Some(AddressChange::Upsert {
+                    entry: MonitoredAddress {
+                        address,
+                        chain_name: chain,
+                        metadata: AddressMetadata { wallet_id, account_id },
+                    },
+                    first_seen_block: None,
+                })
+            }
+            CustomerAddressEvent::CustomerAddressRemoved { address, chain, .. } => {
+                Some(AddressChange::Remove { address, chain })
+            }
+            CustomerAddressEvent::WalletDeactivated { wallet_id, .. } => {
+                Some(AddressChange::DeactivateWallet { wallet_id })
+            }
+            CustomerAddressEvent::Unknown => None,
+        }
+    }
 }
 
 /// Configuration for customer address synchronization
@@ -26,6 +99,11 @@ pub struct CustomerSyncConfig {
     pub batch_size: usize,
     pub flush_interval_secs: u64,
     pub cache_file_path: Option<String>, // e.g., "./customer_addresses.json"
+    pub visibility_timeout_secs: u64,
+    pub max_receive_count: u32,
+    pub retry_delay_secs: u64,
+    pub dlq_url: Option<String>,
+    pub poison_queue_url: Option<String>,
 }
 
 impl Default for CustomerSyncConfig {
@@ -36,29 +114,42 @@ impl Default for CustomerSyncConfig {
             batch_size: 100,
             flush_interval_secs: 5,
             cache_file_path: None,
+            visibility_timeout_secs: 60,
+            max_receive_count: 5,
+            retry_delay_secs: 30,
+            dlq_url: None,
+            poison_queue_url: None,
         }
     }
 }
 
+/// 주소 캐시 파일 형식 (`cache_file_path`, `xscanner address export|import`)
 #[derive(Debug, Deserialize, Serialize)]
-struct CustomerAddressData {
-    address: String,
-    chain: String,
-    wallet_id: String,
-    account_id: Option<String>,
+pub struct CustomerAddressData {
+    pub address: String,
+    pub chain: String,
+    pub wallet_id: String,
+    pub account_id: Option<String>,
 }
 
 /// Run customer address synchronization from SQS
 ///
 /// This service listens to SQS messages from the backend when new customer addresses are added,
-/// and batch-updates the RocksDB cache for fast lookup during block scanning.
+/// and batch-updates the address index for fast lookup during block scanning.
 ///
 /// On startup, it first loads all existing addresses from Backend API (if configured)
 /// to handle cases where xScanner was down and missed SQS messages.
+///
+/// `first_seen_block`이 있는 추가 이벤트는 반영 후 해당 주소의 backfill 작업을 등록한다 (backfill이 설정된 경우).
+///
+/// `shutdown`이 취소되면 SQS 수신을 멈추고 버퍼에 남은 변경을 주소 인덱스에 flush한 뒤 종료한다.
+/// 반환된 handle은 마지막 flush가 끝나면 완료된다.
 pub async fn run_customer_address_sync(
-    rocksdb: Arc<DB>,
+    address_index: Arc<dyn AddressIndex>,
+    backfill: Option<Arc<BackfillScheduler>>,
     config: CustomerSyncConfig,
-) {
+    shutdown: CancellationToken,
+) -> JoinHandle<()> {
     info!(
         "[CustomerSync] Starting customer address sync service (batch_size: {}, flush_interval: {}s)",
         config.batch_size, config.flush_interval_secs
@@ -68,7 +159,7 @@ pub async fn run_customer_address_sync(
     // This handles the case where xScanner was down and missed SQS messages
     if let Some(cache_file) = &config.cache_file_path {
         info!("[CustomerSync] Loading customer addresses from cache file: {}", cache_file);
-        match load_addresses_from_file(&rocksdb, cache_file).await {
+        match load_addresses_from_file(address_index.as_ref(), cache_file).await {
             Ok(count) => {
                 info!("✅ [CustomerSync] Loaded {} customer addresses from file", count);
             }
@@ -80,78 +171,74 @@ pub async fn run_customer_address_sync(
         info!("[CustomerSync] No cache file configured, relying on SQS messages only");
     }
 
+    let sqs = connect_sqs(&config.sqs_queue_url, &config.aws_region).await;
+    let queue = Arc::new(SyncQueue {
+        sqs,
+        queue_url: config.sqs_queue_url.clone(),
+        dlq_url: config.dlq_url.clone(),
+        poison_queue_url: config.poison_queue_url.clone(),
+        max_receive_count: config.max_receive_count,
+        retry_delay_secs: config.retry_delay_secs,
+    });
+
     // Channel for batching
-    let (sender, mut receiver) = mpsc::channel::<CustomerAddressEvent>(1000);
+    let (sender, mut receiver) = mpsc::channel::<PendingChange>(1000);
 
     // SQS Consumer Task
-    let sqs_queue_url = config.sqs_queue_url.clone();
-    let aws_region = config.aws_region.clone();
+    let consumer_queue = queue.clone();
+    let visibility_timeout_secs = config.visibility_timeout_secs;
     tokio::spawn(async move {
-        let is_local = sqs_queue_url.starts_with("http://localhost") || sqs_queue_url.starts_with("http://127.0.0.1");
-
-        let mut aws_config_loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
-            .region(aws_sdk_sqs::config::Region::new(aws_region.clone()));
-
-        // For local development with ElasticMQ
-        if is_local {
-            info!("[CustomerSync] Using local ElasticMQ endpoint with dummy credentials");
-            aws_config_loader = aws_config_loader
-                .endpoint_url("http://localhost:9324")
-                .credentials_provider(aws_sdk_sqs::config::Credentials::new(
-                    "dummy",
-                    "dummy",
-                    None,
-                    None,
-                    "static",
-                ));
-        }
-
-        let aws_config = aws_config_loader.load().await;
-        let sqs = SqsClient::new(&aws_config);
-
-        info!("[CustomerSync] SQS Consumer started, queue: {}", sqs_queue_url);
+        let queue = consumer_queue;
+        info!("[CustomerSync] SQS Consumer started, queue: {}", queue.queue_url);
 
         loop {
-            match sqs
-                .receive_message()
-                .queue_url(&sqs_queue_url)
-                .max_number_of_messages(10) // Receive up to 10 messages at once
-                .wait_time_seconds(20) // Long polling (reduce empty responses)
-                .send()
-                .await
-            {
+            // 종료 시 long polling 중인 요청은 버림 (받지 못한 메시지는 SQS에 남음)
+            let received = tokio::select! {
+                received = queue.sqs
+                    .receive_message()
+                    .queue_url(&queue.queue_url)
+                    .max_number_of_messages(10) // Receive up to 10 messages at once
+                    .wait_time_seconds(20) // Long polling (reduce empty responses)
+                    .visibility_timeout(visibility_timeout_secs as i32) // 배치 반영 전까지 다른 consumer에게 보이지 않도록
+                    .message_system_attribute_names(MessageSystemAttributeName::ApproximateReceiveCount)
+                    .send() => received,
+                _ = shutdown.cancelled() => break,
+            };
+
+            match received {
                 Ok(output) => {
                     if let Some(messages) = output.messages {
                         info!("[CustomerSync] Received {} SQS messages", messages.len());
 
                         for msg in messages {
-                            if let Some(body) = &msg.body {
-                                match serde_json::from_str::<CustomerAddressEvent>(body) {
-                                    Ok(event) => {
-                                        if event.event == "CustomerAddressAdded" {
-                                            if sender.send(event).await.is_err() {
-                                                error!("[CustomerSync] Failed to send to batch buffer (channel closed)");
-                                            }
-                                        } else {
-                                            warn!("[CustomerSync] Unknown event type: {}", event.event);
+                            let Some(receipt_handle) = msg.receipt_handle.clone() else {
+                                continue;
+                            };
+                            let message = QueuedMessage {
+                                receipt_handle,
+                                body: msg.body.clone().unwrap_or_default(),
+                                receive_count: msg.attributes()
+                                    .and_then(|attrs| attrs.get(&MessageSystemAttributeName::ApproximateReceiveCount))
+                                    .and_then(|count| count.parse().ok())
+                                    .unwrap_or(1),
+                            };
+
+                            // 메시지는 배치 writer가 주소 인덱스에 반영한 뒤에만 삭제
+                            match serde_json::from_str::<CustomerAddressEvent>(&message.body) {
+                                Ok(event) => match event.into_change() {
+                                    Some(change) => {
+                                        if sender.send(PendingChange { change, message }).await.is_err() {
+                                            error!("[CustomerSync] Failed to send to batch buffer (channel closed)");
                                         }
                                     }
-                                    Err(e) => {
-                                        error!("[CustomerSync] Failed to parse SQS message: {} | body: {}", e, body);
+                                    None => {
+                                        warn!("[CustomerSync] Unknown event type | body: {}", message.body);
+                                        queue.quarantine(&message).await;
                                     }
-                                }
-                            }
-
-                            // Delete message after processing
-                            if let Some(receipt_handle) = msg.receipt_handle {
-                                if let Err(e) = sqs
-                                    .delete_message()
-                                    .queue_url(&sqs_queue_url)
-                                    .receipt_handle(receipt_handle)
-                                    .send()
-                                    .await
-                                {
-                                    warn!("[CustomerSync] Failed to delete SQS message: {}", e);
+                                },
+                                Err(e) => {
+                                    error!("[CustomerSync] Failed to parse SQS message: {} | body: {}", e, message.body);
+                                    queue.quarantine(&message).await;
                                 }
                             }
                         }
@@ -163,11 +250,14 @@ pub async fn run_customer_address_sync(
                 }
             }
         }
+
+        // sender가 drop되면 batch writer가 남은 변경을 flush하고 종료
+        info!("[CustomerSync] SQS Consumer stopped");
     });
 
-    // Batch Writer Task
+    // Batch Writer Task (consumer가 멈추고 채널이 비면 종료)
     tokio::spawn(async move {
-        let mut buffer: Vec<(String, String, String, Option<String>)> = Vec::with_capacity(config.batch_size);
+        let mut buffer: Vec<PendingChange> = Vec::with_capacity(config.batch_size);
         let mut flush_interval = interval(Duration::from_secs(config.flush_interval_secs));
         flush_interval.tick().await; // Skip first immediate tick
 
@@ -176,22 +266,19 @@ pub async fn run_customer_address_sync(
         loop {
             tokio::select! {
                 // New event received
-                Some(event) = receiver.recv() => {
-                    buffer.push((
-                        event.address.clone(),
-                        event.chain.clone(),
-                        event.wallet_id.clone(),
-                        event.account_id.clone(),
-                    ));
-                    info!(
-                        "[CustomerSync] Buffered: {} (chain: {}, wallet: {}, account: {:?}) | Buffer size: {}/{}",
-                        event.address, event.chain, event.wallet_id, event.account_id, buffer.len(), config.batch_size
-                    );
+                received = receiver.recv() => {
+                    let Some(pending) = received else {
+                        info!("[CustomerSync] Consumer stopped, flushing {} remaining items...", buffer.len());
+                        flush_batch(address_index.as_ref(), backfill.as_deref(), queue.as_ref(), &mut buffer).await;
+                        break;
+                    };
+                    info!("[CustomerSync] Buffered: {:?} | Buffer size: {}/{}", pending.change, buffer.len() + 1, config.batch_size);
+                    buffer.push(pending);
 
                     // Flush when batch size reached
                     if buffer.len() >= config.batch_size {
                         info!("[CustomerSync] Batch size reached, flushing...");
-                        flush_batch(&rocksdb, &mut buffer).await;
+                        flush_batch(address_index.as_ref(), backfill.as_deref(), queue.as_ref(), &mut buffer).await;
                     }
                 }
 
@@ -199,29 +286,293 @@ pub async fn run_customer_address_sync(
                 _ = flush_interval.tick() => {
                     if !buffer.is_empty() {
                         info!("[CustomerSync] Flush interval reached, flushing {} items...", buffer.len());
-                        flush_batch(&rocksdb, &mut buffer).await;
+                        flush_batch(address_index.as_ref(), backfill.as_deref(), queue.as_ref(), &mut buffer).await;
                     }
                 }
             }
         }
-    });
+
+        info!("[CustomerSync] Batch writer stopped");
+    })
+}
+
+/// SQS 클라이언트 생성 (localhost URL이면 ElasticMQ)
+async fn connect_sqs(sqs_queue_url: &str, aws_region: &str) -> SqsClient {
+    let is_local = sqs_queue_url.starts_with("http://localhost") || sqs_queue_url.starts_with("http://127.0.0.1");
+
+    let mut aws_config_loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+        .region(aws_sdk_sqs::config::Region::new(aws_region.to_string()));
+
+    // For local development with ElasticMQ
+    if is_local {
+        info!("[CustomerSync] Using local ElasticMQ endpoint with dummy credentials");
+        aws_config_loader = aws_config_loader
+            .endpoint_url("http://localhost:9324")
+            .credentials_provider(aws_sdk_sqs::config::Credentials::new(
+                "dummy",
+                "dummy",
+                None,
+                None,
+                "static",
+            ));
+    }
+
+    let aws_config = aws_config_loader.load().await;
+    SqsClient::new(&aws_config)
+}
+
+/// 아직 삭제(ack)하지 않은 SQS 메시지
+#[derive(Debug)]
+struct QueuedMessage {
+    receipt_handle: String,
+    body: String,
+    receive_count: u32,
+}
+
+/// 주소 인덱스 반영을 기다리는 변경과 그 원본 메시지
+struct PendingChange {
+    change: AddressChange,
+    message: QueuedMessage,
+}
+
+/// flush 결과를 메시지 큐에 반영 (SQS가 아닌 구현으로 flush 순서를 확인할 수 있도록 분리)
+#[async_trait]
+trait MessageQueue: Send + Sync {
+    /// 처리 완료된 메시지 삭제
+    async fn ack(&self, message: &QueuedMessage);
+    /// 처리 실패 - 나중에 다시 전달
+    async fn nack(&self, message: &QueuedMessage);
+}
+
+/// customer sync 큐 처리 (ack / 재전달 / DLQ / poison queue)
+struct SyncQueue {
+    sqs: SqsClient,
+    queue_url: String,
+    dlq_url: Option<String>,
+    poison_queue_url: Option<String>,
+    max_receive_count: u32,
+    retry_delay_secs: u64,
+}
+
+#[async_trait]
+impl MessageQueue for SyncQueue {
+    async fn ack(&self, message: &QueuedMessage) {
+        if let Err(e) = self.sqs
+            .delete_message()
+            .queue_url(&self.queue_url)
+            .receipt_handle(&message.receipt_handle)
+            .send()
+            .await
+        {
+            // 삭제 실패 시 재전달되지만 같은 변경을 다시 적용해도 결과는 같음
+            warn!("[CustomerSync] Failed to delete SQS message: {}", e);
+        }
+    }
+
+    /// max_receive_count에 도달하면 DLQ로 이동, 아니면 재전달 지연 후 다시 처리
+    async fn nack(&self, message: &QueuedMessage) {
+        if message.receive_count >= self.max_receive_count {
+            if let Some(dlq_url) = &self.dlq_url {
+                warn!(
+                    "[CustomerSync] Message failed {} times, moving to DLQ | body: {}",
+                    message.receive_count, message.body
+                );
+                if self.forward(dlq_url, message).await {
+                    self.ack(message).await;
+                }
+                return;
+            }
+        }
+
+        // 실패 횟수에 비례해 재전달 지연 (SQS 최대 12시간)
+        let delay = (self.retry_delay_secs * message.receive_count as u64).min(43_200);
+        if let Err(e) = self.sqs
+            .change_message_visibility()
+            .queue_url(&self.queue_url)
+            .receipt_handle(&message.receipt_handle)
+            .visibility_timeout(delay as i32)
+            .send()
+            .await
+        {
+            warn!("[CustomerSync] Failed to change message visibility: {}", e);
+        }
+    }
+
 }
 
-async fn flush_batch(rocksdb: &Arc<DB>, buffer: &mut Vec<(String, String, String, Option<String>)>) {
+impl SyncQueue {
+    /// 파싱할 수 없는 메시지를 poison queue로 이동 (설정이 없으면 처리 실패와 같이 max_receive_count 후 DLQ로 이동)
+    async fn quarantine(&self, message: &QueuedMessage) {
+        crate::metrics::inc_customer_sync_messages("malformed", 1);
+        match &self.poison_queue_url {
+            Some(poison_queue_url) => {
+                if self.forward(poison_queue_url, message).await {
+                    self.ack(message).await;
+                }
+            }
+            None => {
+                warn!("[CustomerSync] No poison queue configured, handling malformed message as a failure");
+                self.nack(message).await;
+            }
+        }
+    }
+
+    async fn forward(&self, target_url: &str, message: &QueuedMessage) -> bool {
+        match self.sqs
+            .send_message()
+            .queue_url(target_url)
+            .message_body(&message.body)
+            .send()
+            .await
+        {
+            Ok(_) => true,
+            Err(e) => {
+                error!("[CustomerSync] Failed to forward message to {}: {}", target_url, e);
+                false
+            }
+        }
+    }
+}
+
+/// 버퍼의 변경을 수신 순서대로 적용 (연속된 추가/수정은 한 번의 insert로 묶음)
+///
+/// 메시지는 해당 변경이 주소 인덱스에 반영된 뒤에만 삭제한다.
+/// 중간에 실패하면 순서가 뒤바뀌지 않도록 이후 변경은 적용하지 않고 모두 재전달한다.
+async fn flush_batch(
+    address_index: &dyn AddressIndex,
+    backfill: Option<&BackfillScheduler>,
+    queue: &dyn MessageQueue,
+    buffer: &mut Vec<PendingChange>,
+) {
     if buffer.is_empty() {
         return;
     }
 
-    let count = buffer.len();
-    match batch_add_monitored_addresses(rocksdb, buffer.clone()) {
+    let mut upserts: Vec<PendingChange> = Vec::new();
+    let mut pending = buffer.drain(..);
+
+    while let Some(item) = pending.next() {
+        if matches!(item.change, AddressChange::Upsert { .. }) {
+            upserts.push(item);
+            continue;
+        }
+
+        if !flush_upserts(address_index, backfill, queue, &mut upserts).await || !apply_change(address_index, &item.change).await {
+            queue.nack(&item.message).await;
+            let mut failed = 1;
+            for rest in pending.by_ref() {
+                queue.nack(&rest.message).await;
+                failed += 1;
+            }
+            crate::metrics::inc_customer_sync_messages("failed", failed);
+            return;
+        }
+        queue.ack(&item.message).await;
+        crate::metrics::inc_customer_sync_messages("applied", 1);
+    }
+
+    flush_upserts(address_index, backfill, queue, &mut upserts).await;
+}
+
+/// 삭제 / 지갑 비활성화 적용
+async fn apply_change(address_index: &dyn AddressIndex, change: &AddressChange) -> bool {
+    match change {
+        AddressChange::Upsert { entry, .. } => address_index.insert(std::slice::from_ref(entry)).await.is_ok(),
+        AddressChange::Remove { address, chain } => {
+            match address_index.remove(address, chain).await {
+                Ok(true) => info!("✅ [CustomerSync] Removed monitored address {} (chain: {})", address, chain),
+                Ok(false) => warn!("[CustomerSync] Address to remove not found: {} (chain: {})", address, chain),
+                Err(e) => {
+                    error!("❌ [CustomerSync] Failed to remove address {}: {}", address, e);
+                    return false;
+                }
+            }
+            true
+        }
+        AddressChange::DeactivateWallet { wallet_id } => {
+            match address_index.remove_wallet(wallet_id).await {
+                Ok(removed) => info!("✅ [CustomerSync] Wallet {} deactivated, removed {} address(es)", wallet_id, removed),
+                Err(e) => {
+                    error!("❌ [CustomerSync] Failed to deactivate wallet {}: {}", wallet_id, e);
+                    return false;
+                }
+            }
+            true
+        }
+    }
+}
+
+/// 모아둔 추가/수정을 한 번에 쓰고 성공하면 메시지 삭제, 실패하면 재전달
+async fn flush_upserts(
+    address_index: &dyn AddressIndex,
+    backfill: Option<&BackfillScheduler>,
+    queue: &dyn MessageQueue,
+    upserts: &mut Vec<PendingChange>,
+) -> bool {
+    if upserts.is_empty() {
+        return true;
+    }
+
+    let entries: Vec<MonitoredAddress> = upserts.iter()
+        .filter_map(|item| match &item.change {
+            AddressChange::Upsert { entry, .. } => Some(entry.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let committed = match address_index.insert(&entries).await {
         Ok(written) => {
-            info!("✅ [CustomerSync] Flushed {} monitored addresses to RocksDB cache", written);
-            buffer.clear();
+            info!("✅ [CustomerSync] Flushed {} monitored addresses to address index", written);
+            true
         }
         Err(e) => {
-            error!("❌ [CustomerSync] Failed to flush batch to RocksDB: {}", e);
-            // Clear buffer even on failure to prevent infinite retry
-            buffer.clear();
+            error!("❌ [CustomerSync] Failed to flush batch to address index: {} (messages will be redelivered)", e);
+            false
+        }
+    };
+
+    if committed {
+        if let Some(backfill) = backfill {
+            schedule_backfills(backfill, upserts).await;
+        }
+    }
+
+    crate::metrics::inc_customer_sync_messages(if committed { "applied" } else { "failed" }, upserts.len());
+    for item in upserts.drain(..) {
+        if committed {
+            queue.ack(&item.message).await;
+        } else {
+            queue.nack(&item.message).await;
+        }
+    }
+
+    committed
+}
+
+/// first_seen_block이 있는 새 주소의 backfill 작업 등록
+///
+/// 주소는 이미 반영됐으므로 등록에 실패해도 메시지는 삭제한다 (`xscanner rescan --address`로 다시 등록).
+async fn schedule_backfills(backfill: &BackfillScheduler, upserts: &[PendingChange]) {
+    for item in upserts {
+        let AddressChange::Upsert { entry, first_seen_block: Some(first_seen_block) } = &item.change else {
+            continue;
+        };
+
+        let request = BackfillRequest {
+            chain_name: entry.chain_name.clone(),
+            from_block: *first_seen_block,
+            to_block: None,
+            address: Some(entry.address.clone()),
+        };
+        match backfill.submit(request).await {
+            Ok(job) => info!(
+                "[CustomerSync] Backfill job {} queued for {} (chain: {}) from block {}",
+                job.id, entry.address, entry.chain_name, first_seen_block
+            ),
+            Err(e) => warn!(
+                "[CustomerSync] Failed to queue backfill for {} (chain: {}) from block {}: {}",
+                entry.address, entry.chain_name, first_seen_block, e
+            ),
         }
     }
 }
@@ -229,7 +580,7 @@ async fn flush_batch(rocksdb: &Arc<DB>, buffer: &mut Vec<(String, String, String
 /// Load monitored addresses from JSON file
 /// File format: [{"address": "0x123", "chain": "ETH"}, ...]
 async fn load_addresses_from_file(
-    rocksdb: &Arc<DB>,
+    address_index: &dyn AddressIndex,
     file_path: &str,
 ) -> Result<usize, AppError> {
     use tokio::fs;
@@ -253,15 +604,190 @@ async fn load_addresses_from_file(
     }
 
     // Convert to batch format
-    let batch_data: Vec<(String, String, String, Option<String>)> = addresses
+    let batch_data: Vec<MonitoredAddress> = addresses
         .into_iter()
-        .map(|addr| (addr.address, addr.chain, addr.wallet_id, addr.account_id))
+        .map(|addr| MonitoredAddress {
+            address: addr.address,
+            chain_name: addr.chain,
+            metadata: AddressMetadata {
+                wallet_id: addr.wallet_id,
+                account_id: addr.account_id,
+            },
+        })
         .collect();
 
-    let count = batch_data.len();
+    // Write to address index
+    address_index.insert(&batch_data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// 적용한 변경을 기록하는 주소 인덱스 (`failing`에 포함된 작업은 실패)
+    #[derive(Default)]
+    struct RecordingIndex {
+        failing: Vec<&'static str>,
+        applied: Mutex<Vec<String>>,
+    }
+
+    impl RecordingIndex {
+        fn failing(operations: &[&'static str]) -> Self {
+            Self { failing: operations.to_vec(), ..Self::default() }
+        }
+
+        fn record(&self, operation: &'static str, target: String) -> Result<(), AppError> {
+            if self.failing.contains(&operation) {
+                return Err(AppError::Database(format!("{} failed", operation)));
+            }
+            self.applied.lock().unwrap().push(format!("{} {}", operation, target));
+            Ok(())
+        }
+
+        fn applied(&self) -> Vec<String> {
+            self.applied.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl AddressIndex for RecordingIndex {
+        async fn lookup(&self, _address: &str, _chain_name: &str) -> Result<Option<AddressMetadata>, AppError> {
+            Ok(None)
+        }
+
+        async fn insert(&self, entries: &[MonitoredAddress]) -> Result<usize, AppError> {
+            let addresses: Vec<&str> = entries.iter().map(|entry| entry.address.as_str()).collect();
+            self.record("insert", addresses.join(","))?;
+            Ok(entries.len())
+        }
+
+        async fn remove(&self, address: &str, _chain_name: &str) -> Result<bool, AppError> {
+            self.record("remove", address.to_string())?;
+            Ok(true)
+        }
+
+        async fn remove_wallet(&self, wallet_id: &str) -> Result<usize, AppError> {
+            self.record("remove_wallet", wallet_id.to_string())?;
+            Ok(1)
+        }
+
+        async fn count(&self, _chain_name: &str) -> Result<usize, AppError> {
+            Ok(0)
+        }
+
+        async fn list(&self, _chain_name: Option<&str>) -> Result<Vec<MonitoredAddress>, AppError> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// ack / nack 결과를 receipt handle별로 기록하는 큐
+    #[derive(Default)]
+    struct RecordingQueue {
+        results: Mutex<HashMap<String, &'static str>>,
+    }
+
+    impl RecordingQueue {
+        fn result(&self, receipt_handle: &str) -> Option<&'static str> {
+            self.results.lock().unwrap().get(receipt_handle).copied()
+        }
+    }
 
-    // Write to RocksDB
-    batch_add_monitored_addresses(rocksdb, batch_data)?;
+    #[async_trait]
+    impl MessageQueue for RecordingQueue {
+        async fn ack(&self, message: &QueuedMessage) {
+            self.results.lock().unwrap().insert(message.receipt_handle.clone(), "ack");
+        }
+
+        async fn nack(&self, message: &QueuedMessage) {
+            self.results.lock().unwrap().insert(message.receipt_handle.clone(), "nack");
+        }
+    }
+
+    fn pending(receipt_handle: &str, change: AddressChange) -> PendingChange {
+        PendingChange {
+            change,
+            message: QueuedMessage { receipt_handle: receipt_handle.to_string(), body: String::new(), receive_count: 1 },
+        }
+    }
+
+    fn added(receipt_handle: &str, address: &str) -> PendingChange {
+        pending(receipt_handle, AddressChange::Upsert {
+            entry: MonitoredAddress {
+                address: address.to_string(),
+                chain_name: "ETH".to_string(),
+                metadata: AddressMetadata { wallet_id: "wallet-1".to_string(), account_id: None },
+            },
+            first_seen_block: None,
+        })
+    }
+
+    fn removed(receipt_handle: &str, address: &str) -> PendingChange {
+        pending(receipt_handle, AddressChange::Remove { address: address.to_string(), chain: "ETH".to_string() })
+    }
 
-    Ok(count)
+    fn deactivated(receipt_handle: &str, wallet_id: &str) -> PendingChange {
+        pending(receipt_handle, AddressChange::DeactivateWallet { wallet_id: wallet_id.to_string() })
+    }
+
+    #[tokio::test]
+    async fn upserts_are_flushed_before_a_remove() {
+        let index = RecordingIndex::default();
+        let queue = RecordingQueue::default();
+        let mut buffer = vec![added("m1", "0xa"), added("m2", "0xb"), removed("m3", "0xa"), added("m4", "0xc")];
+
+        flush_batch(&index, None, &queue, &mut buffer).await;
+
+        // 0xa를 추가한 뒤 삭제 - 순서가 바뀌면 삭제한 주소가 다시 관리 대상이 된다
+        assert_eq!(index.applied(), ["insert 0xa,0xb", "remove 0xa", "insert 0xc"]);
+        for message in ["m1", "m2", "m3", "m4"] {
+            assert_eq!(queue.result(message), Some("ack"), "{}", message);
+        }
+        assert!(buffer.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_remove_nacks_every_later_change() {
+        let index = RecordingIndex::failing(&["remove"]);
+        let queue = RecordingQueue::default();
+        let mut buffer = vec![added("m1", "0xa"), removed("m2", "0xb"), added("m3", "0xb"), deactivated("m4", "wallet-2")];
+
+        flush_batch(&index, None, &queue, &mut buffer).await;
+
+        assert_eq!(index.applied(), ["insert 0xa"]);
+        assert_eq!(queue.result("m1"), Some("ack"));
+        for message in ["m2", "m3", "m4"] {
+            assert_eq!(queue.result(message), Some("nack"), "{}", message);
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_deactivate_nacks_every_later_change() {
+        let index = RecordingIndex::failing(&["remove_wallet"]);
+        let queue = RecordingQueue::default();
+        let mut buffer = vec![deactivated("m1", "wallet-1"), added("m2", "0xa"), removed("m3", "0xb")];
+
+        flush_batch(&index, None, &queue, &mut buffer).await;
+
+        assert!(index.applied().is_empty());
+        for message in ["m1", "m2", "m3"] {
+            assert_eq!(queue.result(message), Some("nack"), "{}", message);
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_upsert_flush_nacks_the_following_remove() {
+        let index = RecordingIndex::failing(&["insert"]);
+        let queue = RecordingQueue::default();
+        let mut buffer = vec![added("m1", "0xa"), removed("m2", "0xa"), added("m3", "0xb")];
+
+        flush_batch(&index, None, &queue, &mut buffer).await;
+
+        // 추가가 반영되지 않았으면 이후 삭제도 적용하지 않고 재전달
+        assert!(index.applied().is_empty());
+        for message in ["m1", "m2", "m3"] {
+            assert_eq!(queue.result(message), Some("nack"), "{}", message);
+        }
+    }
 }
@@ -1,7 +1,15 @@
 pub mod balance_reconciliation;
 pub mod customer_address_sync;
 pub mod confirmation_checker;
+pub mod chain_head_tracker;
+pub mod notification_outbox;
+pub mod backfill;
+pub mod address_metrics;
 
 pub use balance_reconciliation::{ReconciliationConfig, run_balance_reconciliation};
-pub use customer_address_sync::{CustomerSyncConfig, run_customer_address_sync, CustomerAddressEvent};
+pub use customer_address_sync::{CustomerSyncConfig, run_customer_address_sync, CustomerAddressEvent, CustomerAddressData};
 pub use confirmation_checker::{ConfirmationCheckerConfig, run_confirmation_checker, PendingDeposit};
+pub use chain_head_tracker::{ChainHeadTracker, run_chain_head_tracker};
+pub use notification_outbox::{OutboxDispatcherConfig, OutboxEntry, run_outbox_dispatcher};
+pub use backfill::{BackfillConfig, BackfillRequest, BackfillScheduler, create_backfill_job, run_backfill_scheduler};
+pub use address_metrics::run_address_metrics;
@@ -0,0 +1,336 @@
+use crate::respository::{RepositoryWrapper, Repository};
+use crate::notification::{DepositEvent, EventPublisher};
+use log::{info, error, warn};
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tokio_util::sync::CancellationToken;
+
+/// sink 하나로 전송 대기 중인 알림 (notification_outbox, attempts는 그 sink로 시도한 횟수)
+#[derive(Debug, Clone)]
+pub struct OutboxEntry {
+    pub id: i64,
+    pub event_type: String,
+    pub payload: String, // 직렬화된 DepositEvent
+    pub attempts: u32,
+}
+
+/// Configuration for outbox dispatcher
+pub struct OutboxDispatcherConfig {
+    pub poll_interval_secs: u64,
+    pub batch_size: usize,
+    pub retry_base_delay_secs: u64,
+    pub max_retry_delay_secs: u64,
+    pub max_attempts: u32,
+}
+
+impl Default for OutboxDispatcherConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: 5,
+            batch_size: 100,
+            retry_base_delay_secs: 5,
+            max_retry_delay_secs: 600,
+            max_attempts: 20,
+        }
+    }
+}
+
+/// Run outbox dispatcher - 입금 저장과 같은 트랜잭션에 기록된 알림을 설정된 sink로 전송
+///
+/// 전송 상태는 sink별로 기록한다. 실패한 sink만 지수 backoff 후 다시 시도하고, 이미 받은 sink에는 다시 보내지 않는다.
+/// `max_attempts`번 실패하면 그 sink에 대해서만 dead로 표시한다 (`deposits resend`로 다시 기록).
+/// 모든 sink로 전송(또는 dead 처리)된 알림은 outbox에서 완료 처리된다.
+/// 종료 시 전송 중인 배치는 끝까지 보내고, 남은 알림은 outbox에 남아 재시작 후 전송된다.
+pub async fn run_outbox_dispatcher(
+    repository: Arc<RepositoryWrapper>,
+    publisher: Arc<EventPublisher>,
+    config: OutboxDispatcherConfig,
+    shutdown: CancellationToken,
+) {
+    info!(
+        "[OutboxDispatcher] Starting with sinks: {}, poll_interval: {}s, batch_size: {}, max_attempts: {}",
+        publisher.name(), config.poll_interval_secs, config.batch_size, config.max_attempts
+    );
+
+    let mut poll_interval = interval(Duration::from_secs(config.poll_interval_secs));
+
+    loop {
+        tokio::select! {
+            _ = poll_interval.tick() => {}
+            _ = shutdown.cancelled() => break,
+        }
+
+        if let Err(e) = dispatch_pending(&repository, publisher.as_ref(), &config).await {
+            error!("[OutboxDispatcher] Error dispatching notifications: {}", e);
+        }
+    }
+
+    info!("[OutboxDispatcher] Stopped");
+}
+
+/// sink마다 전송 가능한 알림을 한 배치씩 전송한 뒤 모든 sink 처리가 끝난 알림을 완료 처리
+async fn dispatch_pending(
+    repository: &Arc<RepositoryWrapper>,
+    publisher: &EventPublisher,
+    config: &OutboxDispatcherConfig,
+) -> Result<(), String> {
+    let sinks = publisher.sinks();
+    for sink in &sinks {
+        dispatch_to_sink(repository, publisher, sink, config).await?;
+    }
+
+    repository
+        .complete_notifications(&sinks)
+        .await
+        .map_err(|e| format!("Failed to complete notifications: {}", e))?;
+
+    Ok(())
+}
+
+/// sink 하나로 전송 가능한 알림을 한 배치 전송
+async fn dispatch_to_sink(
+    repository: &Arc<RepositoryWrapper>,
+    publisher: &EventPublisher,
+    sink: &str,
+    config: &OutboxDispatcherConfig,
+) -> Result<(), String> {
+    let entries = repository
+        .get_pending_notifications(sink, config.batch_size)
+        .await
+        .map_err(|e| format!("Failed to get pending notifications for {}: {}", sink, e))?;
+
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    info!("[OutboxDispatcher] Dispatching {} notification(s) to {}", entries.len(), sink);
+
+    for entry in entries {
+        let result = match serde_json::from_str::<DepositEvent>(&entry.payload) {
+            Ok(event) => publisher.publish_to(sink, event).await,
+            Err(e) => Err(format!("Failed to parse outbox payload: {}", e)),
+        };
+
+        match result {
+            Ok(()) => {
+                repository
+                    .mark_notification_sent(entry.id, sink)
+                    .await
+                    .map_err(|e| format!("Failed to mark notification {} as sent to {}: {}", entry.id, sink, e))?;
+            }
+            Err(e) if entry.attempts + 1 >= config.max_attempts => {
+                error!(
+                    "[OutboxDispatcher] Giving up on {} #{} to {} after {} attempt(s), marking it dead: {}",
+                    entry.event_type, entry.id, sink, entry.attempts + 1, e
+                );
+                repository
+                    .mark_notification_dead(entry.id, sink, &e)
+                    .await
+                    .map_err(|e| format!("Failed to mark notification {} as dead for {}: {}", entry.id, sink, e))?;
+            }
+            Err(e) => {
+                let retry_after_secs = retry_delay(config, entry.attempts);
+                warn!(
+                    "[OutboxDispatcher] Failed to send {} #{} to {} (attempt {}), retrying in {}s: {}",
+                    entry.event_type, entry.id, sink, entry.attempts + 1, retry_after_secs, e
+                );
+                repository
+                    .mark_notification_failed(entry.id, sink, &e, retry_after_secs)
+                    .await
+                    .map_err(|e| format!("Failed to record notification {} failure for {}: {}", entry.id, sink, e))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 재시도 지연 (retry_base_delay_secs * 2^attempts, 최대 max_retry_delay_secs)
+fn retry_delay(config: &OutboxDispatcherConfig, attempts: u32) -> u64 {
+    config.retry_base_delay_secs
+        .saturating_mul(1u64 << attempts.min(20))
+        .min(config.max_retry_delay_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::envelope::EventEnvelope;
+    use crate::notification::notifier::Notifier;
+    use crate::respository::MemoryRepository;
+    use crate::types::AssetInfo;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// 받은 이벤트를 기록하는 sink (fail이면 항상 실패)
+    struct RecordingSink {
+        name: &'static str,
+        fail: bool,
+        received: Mutex<Vec<String>>,
+    }
+
+    impl RecordingSink {
+        fn new(name: &'static str, fail: bool) -> Arc<Self> {
+            Arc::new(Self { name, fail, received: Mutex::new(Vec::new()) })
+        }
+
+        fn received(&self) -> Vec<String> {
+            self.received.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl Notifier for RecordingSink {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn notify(&self, envelope: &EventEnvelope) -> Result<(), String> {
+            self.received.lock().unwrap().push(envelope.event.event_type().to_string());
+            if self.fail {
+                Err("connection refused".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn detected(tx_hash: &str) -> DepositEvent {
+        DepositEvent::DepositDetected {
+            address: "0xdeposit".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            account_id: None,
+            chain: "ETHEREUM".to_string(),
+            tx_hash: tx_hash.to_string(),
+            output_index: 0,
+            amount: "1000".to_string(),
+            asset: AssetInfo::native("ETH", 18),
+            block_number: 100,
+            block_hash: None,
+            confirmations: 1,
+        }
+    }
+
+    fn confirmed(tx_hash: &str) -> DepositEvent {
+        DepositEvent::DepositConfirmed {
+            address: "0xdeposit".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            account_id: None,
+            chain: "ETHEREUM".to_string(),
+            tx_hash: tx_hash.to_string(),
+            output_index: 0,
+            amount: "1000".to_string(),
+            asset: AssetInfo::native("ETH", 18),
+            block_number: 100,
+            block_hash: None,
+            confirmations: 12,
+        }
+    }
+
+    fn memory_repository() -> Arc<RepositoryWrapper> {
+        Arc::new(RepositoryWrapper::Memory(Arc::new(MemoryRepository::new())))
+    }
+
+    /// 실패하면 바로 다시 시도하는 설정
+    fn config(max_attempts: u32) -> OutboxDispatcherConfig {
+        OutboxDispatcherConfig {
+            retry_base_delay_secs: 0,
+            max_attempts,
+            ..OutboxDispatcherConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_sink_does_not_resend_to_healthy_sinks() {
+        let repository = memory_repository();
+        let sqs = RecordingSink::new("sqs", false);
+        let webhook = RecordingSink::new("webhook", true);
+        let publisher = EventPublisher::new(vec![sqs.clone(), webhook.clone()], "scanner-1".to_string(), None);
+        repository.enqueue_notification(&detected("0xa")).await.unwrap();
+        repository.enqueue_notification(&confirmed("0xa")).await.unwrap();
+
+        for _ in 0..3 {
+            dispatch_pending(&repository, &publisher, &config(3)).await.unwrap();
+        }
+
+        // sqs는 webhook 실패와 관계없이 순서대로 한 번씩만 받는다
+        assert_eq!(sqs.received(), ["DepositDetected", "DepositConfirmed"]);
+        // webhook은 DepositDetected가 dead가 될 때까지 DepositConfirmed를 받지 않는다
+        assert_eq!(webhook.received(), ["DepositDetected"; 3]);
+
+        dispatch_pending(&repository, &publisher, &config(3)).await.unwrap();
+        assert_eq!(sqs.received().len(), 2);
+        assert_eq!(webhook.received().last().map(String::as_str), Some("DepositConfirmed"));
+    }
+
+    #[tokio::test]
+    async fn notification_completes_when_every_sink_is_done() {
+        let repository = memory_repository();
+        let sqs = RecordingSink::new("sqs", false);
+        let webhook = RecordingSink::new("webhook", true);
+        let publisher = EventPublisher::new(vec![sqs.clone(), webhook.clone()], "scanner-1".to_string(), None);
+        repository.enqueue_notification(&detected("0xa")).await.unwrap();
+
+        dispatch_pending(&repository, &publisher, &config(2)).await.unwrap();
+        // webhook이 아직 재시도 중이므로 완료되지 않음
+        assert_eq!(repository.complete_notifications(&publisher.sinks()).await.unwrap(), 0);
+        assert!(repository.get_pending_notifications("sqs", 10).await.unwrap().is_empty());
+        assert_eq!(repository.get_pending_notifications("webhook", 10).await.unwrap().len(), 1);
+
+        // webhook이 dead가 되면 완료 - 새로 추가된 sink에도 더 이상 보내지 않는다
+        dispatch_pending(&repository, &publisher, &config(2)).await.unwrap();
+        assert!(repository.get_pending_notifications("webhook", 10).await.unwrap().is_empty());
+        assert!(repository.get_pending_notifications("file", 10).await.unwrap().is_empty());
+        assert_eq!(sqs.received().len(), 1);
+    }
+
+    #[test]
+    fn sinks_of_the_same_kind_get_distinct_keys() {
+        let publisher = EventPublisher::new(
+            vec![
+                RecordingSink::new("webhook", false),
+                RecordingSink::new("sqs", false),
+                RecordingSink::new("webhook", false),
+            ],
+            "scanner-1".to_string(),
+            None,
+        );
+        assert_eq!(publisher.sinks(), ["webhook", "sqs", "webhook#2"]);
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_maximum() {
+        let config = OutboxDispatcherConfig { retry_base_delay_secs: 5, max_retry_delay_secs: 600, ..OutboxDispatcherConfig::default() };
+        assert_eq!(retry_delay(&config, 0), 5);
+        assert_eq!(retry_delay(&config, 1), 10);
+        assert_eq!(retry_delay(&config, 6), 320);
+        assert_eq!(retry_delay(&config, 7), 600);
+        // 시도 횟수가 커도 overflow 없이 최대값
+        assert_eq!(retry_delay(&config, u32::MAX), 600);
+
+        let huge = OutboxDispatcherConfig { retry_base_delay_secs: u64::MAX / 2, max_retry_delay_secs: u64::MAX, ..OutboxDispatcherConfig::default() };
+        assert_eq!(retry_delay(&huge, 3), u64::MAX);
+    }
+
+    #[tokio::test]
+    async fn notification_is_marked_dead_after_max_attempts() {
+        let repository = memory_repository();
+        let webhook = RecordingSink::new("webhook", true);
+        let publisher = EventPublisher::new(vec![webhook.clone()], "scanner-1".to_string(), None);
+        repository.enqueue_notification(&detected("0xa")).await.unwrap();
+
+        for attempts in 1..3 {
+            dispatch_pending(&repository, &publisher, &config(3)).await.unwrap();
+            let pending = repository.get_pending_notifications("webhook", 10).await.unwrap();
+            assert_eq!(pending.len(), 1);
+            assert_eq!(pending[0].attempts, attempts);
+        }
+
+        // 세 번째 실패에서 dead - 더 이상 전송하지 않는다
+        dispatch_pending(&repository, &publisher, &config(3)).await.unwrap();
+        dispatch_pending(&repository, &publisher, &config(3)).await.unwrap();
+        assert_eq!(webhook.received().len(), 3);
+        assert!(repository.get_pending_notifications("webhook", 10).await.unwrap().is_empty());
+    }
+}
+
@@ -1,6 +1,10 @@
 use crate::coin::bitcoin::model::BitcoinBlock;
 use crate::coin::ethereum::model::EthereumBlock;
-use tokio::sync::mpsc::{Sender, Receiver};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use crate::notification::DepositEvent;
+use tokio::sync::mpsc::{Sender, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
 
 // ====== BlockData - ?? ???? ?? ======
 #[derive(Debug)]
@@ -20,9 +24,361 @@ pub enum BlockData {
   // ?? ????? ??? ??
 }
 
+impl BlockData {
+  /// 블록 해시 / 부모 해시 (reorg 감지용, 해시 정보가 없는 체인은 None)
+  pub fn block_ref(&self) -> Option<BlockRef> {
+    match self {
+      BlockData::Ethereum(block) => block.result.as_ref().map(|r| BlockRef::new(&r.hash, &r.parent_hash)),
+      BlockData::Bitcoin(block) => Some(BlockRef::new(&block.hash, &block.prev_block)),
+      BlockData::Tron(block) => Some(BlockRef::new(&block.block_id, &block.block_header.raw_data.parent_hash)),
+      BlockData::Theta(block) => Some(BlockRef::new(&block.hash, &block.parent)),
+      BlockData::Icon(block) => Some(BlockRef::new(&block.block_hash, &block.prev_block_hash)),
+      BlockData::Aion(block) => block.result.as_ref().map(|r| BlockRef::new(&r.hash, &r.parent_hash)),
+      BlockData::Algorand(block) => Some(BlockRef::new(&block.hash, &block.prev_block_hash)),
+      BlockData::Gxchain(block) => Some(BlockRef::new(&block.hash, &block.previous)),
+      BlockData::Quark(_) | BlockData::Terra(_) | BlockData::Tezos(_) | BlockData::Wayki(_) => None,
+    }
+  }
+}
+
+/// 블록 해시 정보 (hash, parent_hash)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockRef {
+  pub hash: String,
+  pub parent_hash: String,
+}
+
+impl BlockRef {
+  pub fn new(hash: &str, parent_hash: &str) -> Self {
+    Self {
+      hash: hash.to_lowercase(),
+      parent_hash: parent_hash.to_lowercase(),
+    }
+  }
+}
+
+// ====== AssetInfo - 입금 자산 (native coin 또는 토큰) ======
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetInfo {
+  /// 자산 심볼 (예: "ETH", "USDT")
+  #[serde(rename = "asset")]
+  pub symbol: String,
+  /// 토큰 컨트랙트 주소 (native coin은 None)
+  #[serde(default)]
+  pub token_address: Option<String>,
+  /// 소수점 자릿수 (알 수 없는 토큰은 None)
+  #[serde(default)]
+  pub decimals: Option<u32>,
+}
+
+impl AssetInfo {
+  pub fn native(symbol: &str, decimals: u32) -> Self {
+    Self {
+      symbol: symbol.to_string(),
+      token_address: None,
+      decimals: Some(decimals),
+    }
+  }
+
+  pub fn token(symbol: &str, token_address: &str, decimals: Option<u32>) -> Self {
+    Self {
+      symbol: symbol.to_string(),
+      token_address: Some(token_address.to_string()),
+      decimals,
+    }
+  }
+}
+
+// ====== DepositStatus - 입금 상태 (deposit_events.status) ======
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositStatus {
+  /// 블록에서 감지됨 (DepositDetected 알림)
+  Detected,
+  /// confirmation 누적 중 (required_confirmations 미달)
+  Confirming,
+  /// required_confirmations 도달 (DepositConfirmed 알림)
+  Confirmed,
+  /// Reorg로 canonical 체인에서 사라짐 (DepositOrphaned 알림)
+  Orphaned,
+  /// 실행이 실패한 것으로 확인됨 (입금 아님)
+  Failed,
+  /// 운영자 확인 필요
+  FlaggedForReview,
+}
+
+impl DepositStatus {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      DepositStatus::Detected => "detected",
+      DepositStatus::Confirming => "confirming",
+      DepositStatus::Confirmed => "confirmed",
+      DepositStatus::Orphaned => "orphaned",
+      DepositStatus::Failed => "failed",
+      DepositStatus::FlaggedForReview => "flagged_for_review",
+    }
+  }
+
+  /// 아직 확정되지 않은 상태 (ConfirmationChecker 대상)
+  pub fn is_pending(&self) -> bool {
+    matches!(self, DepositStatus::Detected | DepositStatus::Confirming)
+  }
+
+  /// 허용된 상태 전이
+  ///
+  /// - orphaned 입금은 canonical 체인에 다시 포함되면 detected로 돌아간다
+  /// - confirmed 입금도 깊은 reorg에서는 orphaned가 될 수 있다
+  /// - failed는 운영자 확인 대상으로만 옮길 수 있다
+  pub fn can_transition_to(&self, next: DepositStatus) -> bool {
+    use DepositStatus::*;
+    matches!(
+      (self, next),
+      (Detected, Confirming | Confirmed | Orphaned | Failed | FlaggedForReview)
+        | (Confirming, Confirmed | Orphaned | Failed | FlaggedForReview)
+        | (Confirmed, Orphaned | FlaggedForReview)
+        | (Orphaned, Detected | FlaggedForReview)
+        | (Failed, FlaggedForReview)
+        | (FlaggedForReview, Detected | Confirmed | Orphaned | Failed)
+    )
+  }
+}
+
+impl std::fmt::Display for DepositStatus {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl std::str::FromStr for DepositStatus {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "detected" => Ok(DepositStatus::Detected),
+      "confirming" => Ok(DepositStatus::Confirming),
+      "confirmed" => Ok(DepositStatus::Confirmed),
+      "orphaned" => Ok(DepositStatus::Orphaned),
+      "failed" => Ok(DepositStatus::Failed),
+      "flagged_for_review" => Ok(DepositStatus::FlaggedForReview),
+      other => Err(format!("Unknown deposit status: {}", other)),
+    }
+  }
+}
+
+/// 입금 상태 전이 요청 (expected_version이 저장된 status_version과 같을 때만 적용)
+#[derive(Debug, Clone, Copy)]
+pub struct StatusTransition {
+  pub from: DepositStatus,
+  pub to: DepositStatus,
+  pub expected_version: i64,
+  pub confirmations: u64,
+}
+
+impl StatusTransition {
+  pub fn validate(&self) -> Result<(), AppError> {
+    if self.from.can_transition_to(self.to) {
+      Ok(())
+    } else {
+      Err(AppError::Database(format!("Invalid deposit status transition: {} -> {}", self.from, self.to)))
+    }
+  }
+}
+
+/// deposit_status_history 한 줄 (from_status가 None이면 최초 감지)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositStatusChange {
+  pub from_status: Option<DepositStatus>,
+  pub to_status: DepositStatus,
+  pub confirmations: u64,
+  pub changed_at: u64, // unix seconds
+}
+
+/// 입금 조회 조건 (None이면 조건 없음)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DepositFilter {
+  pub wallet_id: Option<String>,
+  pub account_id: Option<String>,
+  pub address: Option<String>,
+  #[serde(rename = "chain")]
+  pub chain_name: Option<String>,
+  pub tx_hash: Option<String>,
+  pub status: Option<DepositStatus>,
+  #[serde(default = "default_deposit_limit")]
+  pub limit: usize,
+  #[serde(default)]
+  pub offset: usize,
+}
+
+fn default_deposit_limit() -> usize {
+  100
+}
+
+/// 저장된 입금 (조회 API 응답)
+#[derive(Debug, Clone, Serialize)]
+pub struct DepositRecord {
+  pub address: String,
+  pub wallet_id: String,
+  pub account_id: Option<String>,
+  pub chain: String,
+  pub tx_hash: String,
+  pub output_index: u32,
+  pub block_number: u64,
+  pub block_hash: Option<String>,
+  pub amount: String,
+  pub amount_decimal: Option<Decimal>,
+  #[serde(flatten)]
+  pub asset: AssetInfo,
+  pub status: DepositStatus,
+  pub status_version: i64,
+  pub confirmations: u64,
+}
+
+// ====== BlockCommit - 블록 하나의 분석 결과 (Repository::commit_block) ======
+
+/// 새로 감지한 입금 - detected로 저장 (orphaned 입금이면 다시 detected)
+#[derive(Debug, Clone)]
+pub struct NewDeposit {
+  pub address: String,
+  pub wallet_id: String,
+  pub account_id: Option<String>,
+  pub tx_hash: String,
+  pub output_index: u32,
+  pub block_number: u64,
+  pub amount: String,
+  pub amount_decimal: Option<Decimal>,
+  pub asset: AssetInfo,
+  pub block_hash: Option<String>,
+  pub notification: Option<DepositEvent>,
+}
+
+/// 이미 저장된 입금의 상태 전이 (status_version이 다르면 건너뜀)
+#[derive(Debug, Clone)]
+pub struct DepositTransition {
+  pub tx_hash: String,
+  pub output_index: u32,
+  pub transition: StatusTransition,
+  pub notification: Option<DepositEvent>,
+}
+
+/// 실패한 트랜잭션의 입금 시도 (감사용, 입금으로 처리하지 않음)
+#[derive(Debug, Clone)]
+pub struct FailedDeposit {
+  pub address: String,
+  pub tx_hash: String,
+  pub output_index: u32,
+  pub block_number: u64,
+  pub amount: String,
+  pub asset: AssetInfo,
+  pub reason: String,
+}
+
+/// live 블록의 진행 위치 갱신
+#[derive(Debug, Clone)]
+pub struct CursorUpdate {
+  /// reorg 감지용 블록 해시 (reorg 감지가 꺼져 있거나 해시가 없는 체인은 None)
+  pub block_ref: Option<BlockRef>,
+  /// 블록 해시를 저장할 때 이 번호 미만의 해시는 삭제 (최근 reorg_depth개만 유지)
+  pub prune_below: u64,
+}
+
+/// 블록 하나에서 나온 모든 변경 - 전부 기록되거나 아무것도 기록되지 않는다
+#[derive(Debug, Clone)]
+pub struct BlockCommit {
+  pub chain_name: String,
+  pub block_number: u64,
+  pub deposits: Vec<NewDeposit>,
+  pub transitions: Vec<DepositTransition>,
+  pub failed_deposits: Vec<FailedDeposit>,
+  /// live 블록이면 last_processed_block = block_number (backfill 블록은 None)
+  pub cursor: Option<CursorUpdate>,
+}
+
+// ====== Backfill - 블록 구간 재스캔 작업 (backfill_jobs) ======
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackfillStatus {
+  /// 실행 대기 (체인당 한 작업씩 id 순서로 실행)
+  Pending,
+  Running,
+  Finished,
+  /// 블록 조회 실패 등으로 중단 (next_block부터 다시 등록 가능)
+  Failed,
+}
+
+impl BackfillStatus {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      BackfillStatus::Pending => "pending",
+      BackfillStatus::Running => "running",
+      BackfillStatus::Finished => "finished",
+      BackfillStatus::Failed => "failed",
+    }
+  }
+
+  /// 재시작 시 이어서 실행할 상태
+  pub fn is_unfinished(&self) -> bool {
+    matches!(self, BackfillStatus::Pending | BackfillStatus::Running)
+  }
+}
+
+impl std::str::FromStr for BackfillStatus {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "pending" => Ok(BackfillStatus::Pending),
+      "running" => Ok(BackfillStatus::Running),
+      "finished" => Ok(BackfillStatus::Finished),
+      "failed" => Ok(BackfillStatus::Failed),
+      other => Err(format!("Unknown backfill status: {}", other)),
+    }
+  }
+}
+
+/// 저장된 backfill 작업 - next_block은 analyzer가 처리를 마친 다음 블록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackfillJob {
+  pub id: i64,
+  pub chain: String,
+  pub from_block: u64,
+  pub to_block: u64,
+  pub next_block: u64,
+  /// 주소 지정 작업이면 그 주소 (그 주소의 입금만 처리)
+  pub address: Option<String>,
+  pub status: BackfillStatus,
+  pub error: Option<String>,
+  pub created_at: u64, // unix seconds
+  pub updated_at: u64, // unix seconds
+}
+
+// ====== ChainEvent - Fetcher -> Analyzer ======
+#[derive(Debug)]
+pub enum ChainEvent {
+  /// 새 블록
+  Block {
+    block_number: u64,
+    block: BlockData,
+  },
+  /// Reorg 감지: common_ancestor 이후 블록은 canonical 체인이 아님
+  Reorg {
+    chain_name: String,
+    common_ancestor: u64,
+  },
+  /// backfill 블록: 입금만 처리하고 last_processed_block / 블록 해시는 건드리지 않음
+  /// 분석이 끝나면 `processed`로 결과를 알린다 (backfill 진행 위치 저장용)
+  Rescan {
+    block: BlockData,
+    /// 주소 지정 backfill이면 그 주소의 입금만 처리
+    address: Option<String>,
+    processed: oneshot::Sender<Result<(), String>>,
+  },
+}
+
 // ====== Channel aliases ======
-pub type BlockSender = Sender<BlockData>;
-pub type BlockReceiver = Receiver<BlockData>;
+pub type BlockSender = Sender<ChainEvent>;
+/// Analyzer -> Fetcher: commit에 실패한 블록 번호 (fetcher가 그 블록부터 다시 가져온다)
+pub type RetrySender = UnboundedSender<u64>;
+pub type RetryReceiver = UnboundedReceiver<u64>;
 
 // ====== Unified Error Type ======
 #[derive(Debug, thiserror::Error)]
@@ -39,9 +395,6 @@ pub enum AppError {
   #[error("Configuration error: {0}")]
   Config(String),
   
-  #[error("Processing error in Analyzer: {0}")]
-  Analyzer(String),
-  
   #[error("Initialization error: {0}")]
   Initialization(String),
   
@@ -52,6 +405,21 @@ pub enum AppError {
   Block(String),
 }
 
+impl AppError {
+  /// 메트릭 라벨용 에러 종류
+  pub fn kind(&self) -> &'static str {
+    match self {
+      AppError::Client(_) => "client",
+      AppError::SendError(_) => "send",
+      AppError::JoinError(_) => "join",
+      AppError::Config(_) => "config",
+      AppError::Initialization(_) => "initialization",
+      AppError::Database(_) => "database",
+      AppError::Block(_) => "block",
+    }
+  }
+}
+
 // ====== Error Conversions (From impls) ======
 
 impl From<reqwest::Error> for AppError {
@@ -83,3 +451,73 @@ impl From<serde_json::Error> for AppError {
     AppError::Client(format!("JSON parse error: {}", err))
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use DepositStatus::*;
+
+  const ALL_STATUSES: [DepositStatus; 6] = [Detected, Confirming, Confirmed, Orphaned, Failed, FlaggedForReview];
+
+  #[test]
+  fn pending_deposits_move_forward_or_get_orphaned() {
+    for from in [Detected, Confirming] {
+      assert!(from.can_transition_to(Confirmed), "{} -> confirmed", from);
+      assert!(from.can_transition_to(Orphaned), "{} -> orphaned", from);
+      assert!(from.can_transition_to(Failed), "{} -> failed", from);
+    }
+    assert!(Detected.can_transition_to(Confirming));
+    assert!(!Confirming.can_transition_to(Detected));
+  }
+
+  #[test]
+  fn deep_reorg_can_orphan_confirmed_deposit() {
+    assert!(Confirmed.can_transition_to(Orphaned));
+    assert!(!Failed.can_transition_to(Orphaned));
+  }
+
+  #[test]
+  fn orphaned_deposit_is_detected_again_before_confirming() {
+    assert!(Orphaned.can_transition_to(Detected));
+    assert!(!Orphaned.can_transition_to(Confirming));
+    assert!(!Orphaned.can_transition_to(Confirmed));
+  }
+
+  #[test]
+  fn failed_deposit_only_goes_to_review() {
+    for to in ALL_STATUSES {
+      assert_eq!(Failed.can_transition_to(to), to == FlaggedForReview, "failed -> {}", to);
+    }
+  }
+
+  #[test]
+  fn any_deposit_can_be_flagged_for_review() {
+    for from in ALL_STATUSES.into_iter().filter(|s| *s != FlaggedForReview) {
+      assert!(from.can_transition_to(FlaggedForReview), "{} -> flagged_for_review", from);
+    }
+    // 운영자 확인 후에는 진행 중 상태가 아닌 결론으로만 옮긴다
+    assert!(!FlaggedForReview.can_transition_to(Confirming));
+  }
+
+  #[test]
+  fn deposit_status_never_transitions_to_itself() {
+    for status in ALL_STATUSES {
+      assert!(!status.can_transition_to(status), "{} -> {}", status, status);
+    }
+  }
+
+  #[test]
+  fn confirmed_deposit_cannot_go_back_to_pending() {
+    assert!(!Confirmed.can_transition_to(Detected));
+    assert!(!Confirmed.can_transition_to(Confirming));
+    assert!(!Failed.can_transition_to(Confirmed));
+  }
+
+  #[test]
+  fn deposit_status_round_trips_through_str() {
+    for status in ALL_STATUSES {
+      assert_eq!(status.as_str().parse::<DepositStatus>(), Ok(status));
+    }
+    assert!("unknown".parse::<DepositStatus>().is_err());
+  }
+}